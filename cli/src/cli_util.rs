@@ -151,6 +151,7 @@ use crate::command_error::internal_error_with_message;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::CommitTemplateLanguageExtension;
@@ -186,6 +187,9 @@ use crate::ui::Ui;
 
 const SHORT_CHANGE_ID_TEMPLATE_TEXT: &str = "format_short_change_id(self.change_id())";
 
+/// Operation tag holding the note recorded by `--evolog-note`.
+pub const EVOLOG_NOTE_TAG: &str = "evolog-note";
+
 #[derive(Clone)]
 struct ChromeTracingFlushGuard {
     _inner: Option<Rc<tracing_chrome::FlushGuard>>,
@@ -943,6 +947,37 @@ impl WorkspaceCommandEnvironment {
         Ok(commit_id_iter.next().transpose()?)
     }
 
+    /// Parses the given strings as file patterns.
+    pub fn parse_file_patterns(
+        &self,
+        ui: &Ui,
+        values: &[String],
+    ) -> Result<FilesetExpression, CommandError> {
+        // TODO: This function might be superseded by parse_union_filesets(),
+        // but it would be weird if parse_union_*() had a special case for the
+        // empty arguments.
+        if values.is_empty() {
+            Ok(FilesetExpression::all())
+        } else {
+            self.parse_union_filesets(ui, values)
+        }
+    }
+
+    /// Parses the given fileset expressions and concatenates them all.
+    pub fn parse_union_filesets(
+        &self,
+        ui: &Ui,
+        file_args: &[String], // TODO: introduce FileArg newtype?
+    ) -> Result<FilesetExpression, CommandError> {
+        let mut diagnostics = FilesetDiagnostics::new();
+        let expressions: Vec<_> = file_args
+            .iter()
+            .map(|arg| fileset::parse_maybe_bare(&mut diagnostics, arg, &self.path_converter))
+            .try_collect()?;
+        print_parse_diagnostics(ui, "In fileset expression", &diagnostics)?;
+        Ok(FilesetExpression::union_all(expressions))
+    }
+
     pub fn template_aliases_map(&self) -> &TemplateAliasesMap {
         &self.template_aliases_map
     }
@@ -959,14 +994,111 @@ impl WorkspaceCommandEnvironment {
         L: TemplateLanguage<'a> + ?Sized,
         L::Property: WrapTemplateProperty<'a, C>,
     {
+        self.parse_template_with_aliases(ui, language, template_text, &self.template_aliases_map)
+    }
+
+    /// Parses template of the given language into evaluation tree, rendering
+    /// timestamps formatted by the `format_timestamp()` and
+    /// `format_time_range()` aliases in ISO 8601.
+    pub fn parse_template_with_iso_time<'a, C, L>(
+        &self,
+        ui: &Ui,
+        language: &L,
+        template_text: &str,
+    ) -> Result<TemplateRenderer<'a, C>, CommandError>
+    where
+        C: Clone + 'a,
+        L: TemplateLanguage<'a> + ?Sized,
+        L::Property: WrapTemplateProperty<'a, C>,
+    {
+        self.parse_template_with_alias_overrides(
+            ui,
+            language,
+            template_text,
+            &[
+                (
+                    "format_timestamp(timestamp)",
+                    r#"timestamp.format("%Y-%m-%dT%H:%M:%S%:z")"#,
+                ),
+                (
+                    "format_time_range(time_range)",
+                    r#"format_timestamp(time_range.start()) ++ " - " ++ format_timestamp(time_range.end())"#,
+                ),
+            ],
+        )
+    }
+
+    /// Parses template of the given language into evaluation tree, with the
+    /// given `(declaration, definition)` aliases taking precedence over the
+    /// configured ones.
+    pub fn parse_template_with_alias_overrides<'a, C, L>(
+        &self,
+        ui: &Ui,
+        language: &L,
+        template_text: &str,
+        overrides: &[(&str, &str)],
+    ) -> Result<TemplateRenderer<'a, C>, CommandError>
+    where
+        C: Clone + 'a,
+        L: TemplateLanguage<'a> + ?Sized,
+        L::Property: WrapTemplateProperty<'a, C>,
+    {
+        let mut aliases_map = self.template_aliases_map.clone();
+        for &(decl, defn) in overrides {
+            aliases_map.insert(decl, defn).unwrap();
+        }
+        self.parse_template_with_aliases(ui, language, template_text, &aliases_map)
+    }
+
+    fn parse_template_with_aliases<'a, C, L>(
+        &self,
+        ui: &Ui,
+        language: &L,
+        template_text: &str,
+        aliases_map: &TemplateAliasesMap,
+    ) -> Result<TemplateRenderer<'a, C>, CommandError>
+    where
+        C: Clone + 'a,
+        L: TemplateLanguage<'a> + ?Sized,
+        L::Property: WrapTemplateProperty<'a, C>,
+    {
+        let mut diagnostics = TemplateDiagnostics::new();
+        let template =
+            template_builder::parse(language, &mut diagnostics, template_text, aliases_map)?;
+        print_parse_diagnostics(ui, "In template expression", &diagnostics)?;
+        Ok(template)
+    }
+
+    /// Parses template of the given language loaded from the file at `path`.
+    ///
+    /// Relative `path` is resolved against the current directory. Parse errors
+    /// and warnings refer to the file by the given `path`.
+    pub fn parse_template_file<'a, C, L>(
+        &self,
+        ui: &Ui,
+        language: &L,
+        path: &str,
+    ) -> Result<TemplateRenderer<'a, C>, CommandError>
+    where
+        C: Clone + 'a,
+        L: TemplateLanguage<'a> + ?Sized,
+        L::Property: WrapTemplateProperty<'a, C>,
+    {
+        let template_text =
+            std::fs::read_to_string(self.command.cwd().join(path)).map_err(|err| {
+                user_error_with_message(format!("Failed to read template file {path}"), err)
+            })?;
         let mut diagnostics = TemplateDiagnostics::new();
         let template = template_builder::parse(
             language,
             &mut diagnostics,
-            template_text,
+            &template_text,
             &self.template_aliases_map,
-        )?;
-        print_parse_diagnostics(ui, "In template expression", &diagnostics)?;
+        )
+        .map_err(|err| err.with_path(path))?;
+        let mut path_diagnostics = TemplateDiagnostics::new();
+        path_diagnostics.extend_with(diagnostics, |diag| diag.with_path(path));
+        print_parse_diagnostics(ui, "In template expression", &path_diagnostics)?;
         Ok(template)
     }
 
@@ -1331,14 +1463,7 @@ to the current parents may contain changes from multiple commits.
         ui: &Ui,
         values: &[String],
     ) -> Result<FilesetExpression, CommandError> {
-        // TODO: This function might be superseded by parse_union_filesets(),
-        // but it would be weird if parse_union_*() had a special case for the
-        // empty arguments.
-        if values.is_empty() {
-            Ok(FilesetExpression::all())
-        } else {
-            self.parse_union_filesets(ui, values)
-        }
+        self.env.parse_file_patterns(ui, values)
     }
 
     /// Parses the given fileset expressions and concatenates them all.
@@ -1347,13 +1472,7 @@ to the current parents may contain changes from multiple commits.
         ui: &Ui,
         file_args: &[String], // TODO: introduce FileArg newtype?
     ) -> Result<FilesetExpression, CommandError> {
-        let mut diagnostics = FilesetDiagnostics::new();
-        let expressions: Vec<_> = file_args
-            .iter()
-            .map(|arg| fileset::parse_maybe_bare(&mut diagnostics, arg, self.path_converter()))
-            .try_collect()?;
-        print_parse_diagnostics(ui, "In fileset expression", &diagnostics)?;
-        Ok(FilesetExpression::union_all(expressions))
+        self.env.parse_union_filesets(ui, file_args)
     }
 
     pub fn auto_tracking_matcher(&self, ui: &Ui) -> Result<Box<dyn Matcher>, CommandError> {
@@ -1462,7 +1581,9 @@ to the current parents may contain changes from multiple commits.
         args: &DiffFormatArgs,
     ) -> Result<DiffRenderer<'_>, CommandError> {
         let formats = diff_util::diff_formats_for(self.settings(), args)?;
-        Ok(self.diff_renderer(formats))
+        Ok(self
+            .diff_renderer(formats)
+            .with_change_types(args.diff_type.clone()))
     }
 
     /// Loads textual diff renderer from the settings and log-like command
@@ -1474,7 +1595,10 @@ to the current parents may contain changes from multiple commits.
         patch: bool,
     ) -> Result<Option<DiffRenderer<'_>>, CommandError> {
         let formats = diff_util::diff_formats_for_log(self.settings(), args, patch)?;
-        Ok((!formats.is_empty()).then(|| self.diff_renderer(formats)))
+        Ok((!formats.is_empty()).then(|| {
+            self.diff_renderer(formats)
+                .with_change_types(args.diff_type.clone())
+        }))
     }
 
     /// Loads diff editor from the settings.
@@ -1692,6 +1816,21 @@ to the current parents may contain changes from multiple commits.
         self.env.parse_template(ui, language, template_text)
     }
 
+    /// Parses template of the given language loaded from the file at `path`.
+    pub fn parse_template_file<'a, C, L>(
+        &self,
+        ui: &Ui,
+        language: &L,
+        path: &str,
+    ) -> Result<TemplateRenderer<'a, C>, CommandError>
+    where
+        C: Clone + 'a,
+        L: TemplateLanguage<'a> + ?Sized,
+        L::Property: WrapTemplateProperty<'a, C>,
+    {
+        self.env.parse_template_file(ui, language, path)
+    }
+
     /// Parses template that is validated by `Self::new()`.
     fn reparse_valid_template<'a, C, L>(
         &self,
@@ -2413,6 +2552,12 @@ impl WorkspaceCommandTransaction<'_> {
         self.tx.repo_mut()
     }
 
+    /// Records a note explaining the rewrite in the operation to be committed.
+    /// The note is shown by `jj evolog`.
+    pub fn set_evolog_note(&mut self, note: &str) {
+        self.tx.set_tag(EVOLOG_NOTE_TAG.to_owned(), note.to_owned());
+    }
+
     pub fn check_out(&mut self, commit: &Commit) -> Result<Commit, CheckOutCommitError> {
         let name = self.helper.workspace_name().to_owned();
         self.id_prefix_context.take(); // invalidate
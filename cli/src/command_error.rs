@@ -815,13 +815,21 @@ fn fileset_parse_error_hint(err: &FilesetParseError) -> Option<String> {
 
 fn opset_resolution_error_hint(err: &OpsetResolutionError) -> Option<String> {
     match err {
-        OpsetResolutionError::MultipleOperations {
-            expr: _,
-            candidates,
-        } => Some(format!(
-            "Try specifying one of the operations by ID: {}",
-            candidates.iter().map(short_operation_hash).join(", ")
-        )),
+        OpsetResolutionError::MultipleOperations { expr, candidates } => {
+            let ids = candidates.iter().map(short_operation_hash).join(", ");
+            if let Some(op_str) = expr.strip_suffix('-') {
+                // The candidates are the parents in order.
+                let parents = (1..=candidates.len())
+                    .map(|n| format!("{op_str}-({n})"))
+                    .join(", ");
+                Some(format!(
+                    "Try specifying one of the operations by ID: {ids}, or by parent index: \
+                     {parents}"
+                ))
+            } else {
+                Some(format!("Try specifying one of the operations by ID: {ids}"))
+            }
+        }
         OpsetResolutionError::EmptyOperations(_)
         | OpsetResolutionError::InvalidIdPrefix(_)
         | OpsetResolutionError::NoSuchOperation(_)
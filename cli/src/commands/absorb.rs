@@ -62,6 +62,9 @@ pub(crate) struct AbsorbArgs {
     /// Move only changes to these paths (instead of all paths)
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Record a note explaining the absorb, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -94,6 +97,9 @@ pub(crate) fn cmd_absorb(
     workspace_command.check_rewritable(selected_trees.target_commits.keys())?;
 
     let mut tx = workspace_command.start_transaction();
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
     let stats = absorb_hunks(tx.repo_mut(), &source, selected_trees.target_commits)?;
 
     if let Some(mut formatter) = ui.status_formatter() {
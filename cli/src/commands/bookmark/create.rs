@@ -12,15 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::ref_name::RefNameBuf;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::ref_name::RemoteNameBuf;
+use jj_lib::repo::Repo as _;
+use jj_lib::store::Store;
 
 use crate::cli_util::has_tracked_remote_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::complete;
@@ -45,6 +51,19 @@ pub struct BookmarkCreateArgs {
     )]
     revision: Option<RevisionArg>,
 
+    /// Track the bookmarks of the same names on the given remote
+    ///
+    /// The remote must be configured in the Git repository, but the remote
+    /// bookmarks don't have to exist yet. They will be created by the next
+    /// `jj git push` without `--allow-new`.
+    #[arg(
+        long,
+        alias = "remote",
+        value_name = "REMOTE",
+        add = ArgValueCandidates::new(complete::git_remotes),
+    )]
+    tracked: Option<RemoteNameBuf>,
+
     /// The bookmarks to create
     #[arg(required = true, value_parser = revset_util::parse_bookmark_name)]
     names: Vec<RefNameBuf>,
@@ -55,6 +74,14 @@ pub fn cmd_bookmark_create(
     command: &CommandHelper,
     args: &BookmarkCreateArgs,
 ) -> Result<(), CommandError> {
+    if let Some(remote) = &args.tracked {
+        if jj_lib::git::is_special_git_remote(remote) {
+            return Err(user_error(format!(
+                "Cannot track bookmarks on the Git-tracking remote: {remote}",
+                remote = remote.as_symbol()
+            )));
+        }
+    }
     let mut workspace_command = command.workspace_helper(ui)?;
     if args.revision.is_none() {
         writeln!(
@@ -63,17 +90,42 @@ pub fn cmd_bookmark_create(
              future it will be required to explicitly specify target revision."
         )?;
     }
+    if let Some(remote) = &args.tracked {
+        if !has_git_remote(workspace_command.repo().store(), remote) {
+            return Err(user_error(format!(
+                "No git remote named '{remote}'",
+                remote = remote.as_symbol()
+            )));
+        }
+    }
     let target_commit = workspace_command
         .resolve_single_rev(ui, args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
     let view = workspace_command.repo().view();
-    let bookmark_names = &args.names;
-    for name in bookmark_names {
-        if view.get_local_bookmark(name).is_present() {
+    let bookmark_names = args.names.iter().unique().collect_vec();
+    // Report all existing bookmarks at once, as none of them will be created.
+    let existing_names = bookmark_names
+        .iter()
+        .filter(|name| view.get_local_bookmark(name).is_present())
+        .collect_vec();
+    match &existing_names[..] {
+        [] => {}
+        [name] => {
             return Err(user_error_with_hint(
                 format!("Bookmark already exists: {name}", name = name.as_symbol()),
                 "Use `jj bookmark set` to update it.",
             ));
         }
+        names => {
+            return Err(user_error_with_hint(
+                format!(
+                    "Bookmarks already exist: {names}",
+                    names = names.iter().map(|name| name.as_symbol()).join(", ")
+                ),
+                "Use `jj bookmark set` to update them.",
+            ));
+        }
+    }
+    for name in &bookmark_names {
         if has_tracked_remote_bookmarks(view, name) {
             return Err(user_error_with_hint(
                 format!(
@@ -87,12 +139,35 @@ pub fn cmd_bookmark_create(
                 ),
             ));
         }
+        if let Some(remote) = &args.tracked {
+            let symbol = name.to_remote_symbol(remote);
+            let remote_ref = view.get_remote_bookmark(symbol);
+            if remote_ref.is_present()
+                && remote_ref.target != RefTarget::normal(target_commit.id().clone())
+            {
+                return Err(user_error_with_hint(
+                    format!("Remote bookmark already exists at a different revision: {symbol}"),
+                    format!(
+                        "Use `jj bookmark track {symbol}` to track it, and `jj bookmark set` to \
+                         move the local bookmark."
+                    ),
+                ));
+            }
+        }
     }
 
     let mut tx = workspace_command.start_transaction();
-    for name in bookmark_names {
+    for name in &bookmark_names {
         tx.repo_mut()
             .set_local_bookmark_target(name, RefTarget::normal(target_commit.id().clone()));
+        if let Some(remote) = &args.tracked {
+            let symbol = name.to_remote_symbol(remote);
+            if tx.repo().get_remote_bookmark(symbol).is_present() {
+                tx.repo_mut().track_remote_bookmark(symbol);
+            } else {
+                tx.repo_mut().set_remote_bookmark_pending(symbol, true);
+            }
+        }
     }
 
     if let Some(mut formatter) = ui.status_formatter() {
@@ -114,3 +189,15 @@ pub fn cmd_bookmark_create(
     )?;
     Ok(())
 }
+
+/// Returns true if the remote is configured in the backing Git repository.
+#[cfg(feature = "git")]
+fn has_git_remote(store: &Store, remote: &RemoteName) -> bool {
+    jj_lib::git::get_all_remote_names(store)
+        .is_ok_and(|names| names.iter().any(|name| name == remote))
+}
+
+#[cfg(not(feature = "git"))]
+fn has_git_remote(_store: &Store, _remote: &RemoteName) -> bool {
+    false
+}
@@ -20,10 +20,14 @@ use std::sync::Arc;
 
 use clap::ValueEnum;
 use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::backend;
 use jj_lib::backend::CommitId;
 use jj_lib::config::ConfigValue;
+use jj_lib::op_store::RefTarget;
+use jj_lib::op_store::RemoteRef;
+use jj_lib::op_store::RemoteRefState;
 use jj_lib::ref_name::RefName;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
@@ -102,6 +106,19 @@ pub struct BookmarkListArgs {
     #[arg(long, short, value_name = "REVSETS")]
     revisions: Option<Vec<RevisionArg>>,
 
+    /// Show bookmarks whose targets are in the given revisions
+    ///
+    /// Unlike `--revisions`, this filters the bookmarks selected by the other
+    /// options. Conflicted bookmarks match if any of their targets is in the
+    /// revisions. If remote bookmarks are listed (e.g. by `--all-remotes`),
+    /// remote bookmarks whose targets are in the revisions also match.
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    points_at: Option<Vec<RevisionArg>>,
+
     /// Render each bookmark using the given template
     ///
     /// All 0-argument methods of the [`CommitRef` type] are available as
@@ -116,6 +133,26 @@ pub struct BookmarkListArgs {
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
 
+    /// Show how many commits each local bookmark is ahead of (↑) and behind
+    /// (↓) its tracked remote bookmarks
+    ///
+    /// Tracked remote bookmarks are listed even if they are in sync with the
+    /// local bookmarks. The counts replace the
+    /// `format_tracked_remote_ref_distances()` template alias, which is used by
+    /// the default template.
+    #[arg(long)]
+    ahead_behind: bool,
+
+    /// Don't show whether remote bookmarks are tracked and in sync with the
+    /// local bookmarks
+    ///
+    /// By default, each remote bookmark is marked as `[tracked]`,
+    /// `[tracked, synced]`, or `[untracked]`. This empties the
+    /// `format_remote_ref_tracking_info()` template alias, which is used by the
+    /// default template.
+    #[arg(long)]
+    no_tracking_info: bool,
+
     /// Sort bookmarks based on the given key (or multiple keys)
     ///
     /// Suffix the key with `-` to sort in descending order of the value (e.g.
@@ -171,6 +208,30 @@ pub fn cmd_bookmark_list(
         None
     };
 
+    let points_at_ids: Option<HashSet<CommitId>> = if let Some(revisions) = &args.points_at {
+        let mut expression = workspace_command.parse_union_revsets(ui, revisions)?;
+        // Intersects with the set of local and remote bookmark targets to
+        // minimize the lookup space.
+        expression.intersect_with(
+            &RevsetExpression::bookmarks(StringPattern::everything()).union(
+                &RevsetExpression::remote_bookmarks(
+                    StringPattern::everything(),
+                    StringPattern::everything(),
+                    None,
+                ),
+            ),
+        );
+        Some(expression.evaluate_to_commit_ids()?.try_collect()?)
+    } else {
+        None
+    };
+    let points_at = |target: &RefTarget| {
+        points_at_ids
+            .as_ref()
+            .is_none_or(|ids| target.added_ids().any(|id| ids.contains(id)))
+    };
+    let list_remotes = args.all_remotes || args.remotes.is_some() || args.tracked;
+
     let template: TemplateRenderer<Rc<CommitRef>> = {
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
@@ -179,24 +240,47 @@ pub fn cmd_bookmark_list(
                 .settings()
                 .get("templates.bookmark_list")?,
         };
+        let mut alias_overrides = vec![];
+        if args.ahead_behind {
+            alias_overrides.push((
+                "format_tracked_remote_ref_distances(ref)",
+                "format_tracked_remote_ref_ahead_behind(ref)",
+            ));
+        }
+        if args.no_tracking_info {
+            alias_overrides.push(("format_remote_ref_tracking_info(ref)", r#""""#));
+        }
         workspace_command
-            .parse_template(ui, &language, &text)?
+            .env()
+            .parse_template_with_alias_overrides(ui, &language, &text, &alias_overrides)?
             .labeled(["bookmark_list"])
     };
 
+    // Pending remote bookmarks are listed as tracked remote bookmarks which
+    // don't exist yet.
+    let pending_remote_ref = RemoteRef {
+        target: RefTarget::absent(),
+        state: RemoteRefState::Tracked,
+    };
     let mut bookmark_list_items: Vec<RefListItem> = Vec::new();
     let bookmarks_to_list = view.bookmarks().filter(|(name, target)| {
         bookmark_names_to_list
             .as_ref()
             .is_none_or(|bookmark_names| bookmark_names.contains(name))
             && (!args.conflicted || target.local_target.has_conflict())
+            && (list_remotes || points_at(target.local_target))
     });
     for (name, bookmark_target) in bookmarks_to_list {
         let local_target = bookmark_target.local_target;
         let remote_refs = bookmark_target.remote_refs;
-        let (mut tracked_remote_refs, untracked_remote_refs) = remote_refs
+        let pending_remote_refs = view
+            .pending_remote_bookmarks()
+            .filter(|symbol| symbol.name == name)
+            .map(|symbol| (symbol.remote, &pending_remote_ref));
+        let (mut tracked_remote_refs, mut untracked_remote_refs) = remote_refs
             .iter()
             .copied()
+            .chain(pending_remote_refs)
             .filter(|(remote_name, _)| {
                 args.remotes.as_ref().is_none_or(|patterns| {
                     patterns
@@ -206,13 +290,18 @@ pub fn cmd_bookmark_list(
             })
             .partition::<Vec<_>, _>(|&(_, remote_ref)| remote_ref.is_tracked());
 
-        if args.tracked {
+        if args.tracked || args.ahead_behind {
             tracked_remote_refs.retain(|&(remote, _)| !jj_lib::git::is_special_git_remote(remote));
         } else if !args.all_remotes && args.remotes.is_none() {
             tracked_remote_refs.retain(|&(_, remote_ref)| remote_ref.target != *local_target);
         }
 
-        let include_local_only = !args.tracked && args.remotes.is_none();
+        if list_remotes {
+            tracked_remote_refs.retain(|&(_, remote_ref)| points_at(&remote_ref.target));
+            untracked_remote_refs.retain(|&(_, remote_ref)| points_at(&remote_ref.target));
+        }
+
+        let include_local_only = !args.tracked && args.remotes.is_none() && points_at(local_target);
         if include_local_only && local_target.is_present() || !tracked_remote_refs.is_empty() {
             let primary = CommitRef::local(
                 name,
@@ -22,6 +22,9 @@ mod set;
 mod track;
 mod untrack;
 
+use std::io;
+use std::io::Write as _;
+
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::op_store::RefTarget;
@@ -174,6 +177,39 @@ fn find_remote_bookmarks<'a>(
     }
 }
 
+/// Prints the tracking state of each remote bookmark, and how it relates to the
+/// local bookmark of the same name.
+fn write_tracking_summary(ui: &Ui, view: &View, symbols: &[RemoteRefSymbol<'_>]) -> io::Result<()> {
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    for &symbol in symbols {
+        let remote_ref = view.get_remote_bookmark(symbol);
+        let local_target = view.get_local_bookmark(symbol.name);
+        let (old_state, new_state) = if remote_ref.is_tracked() {
+            ("untracked", "tracked")
+        } else {
+            ("tracked", "untracked")
+        };
+        let sync_state = if remote_ref.is_absent() {
+            "not pushed yet"
+        } else if local_target.is_absent() {
+            "no local bookmark"
+        } else if local_target.has_conflict() {
+            "diverged from local bookmark"
+        } else if *local_target == remote_ref.target {
+            "in sync with local bookmark"
+        } else {
+            "local bookmark points to a different commit"
+        };
+        writeln!(
+            formatter,
+            "  {symbol}: {old_state} -> {new_state}, {sync_state}"
+        )?;
+    }
+    Ok(())
+}
+
 fn is_fast_forward(repo: &dyn Repo, old_target: &RefTarget, new_target_id: &CommitId) -> bool {
     if old_target.is_present() {
         // Strictly speaking, "all" old targets should be ancestors, but we allow
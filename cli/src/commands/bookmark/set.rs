@@ -18,11 +18,13 @@ use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::ref_name::RefNameBuf;
+use jj_lib::repo::Repo as _;
 
 use super::is_fast_forward;
 use crate::cli_util::has_tracked_remote_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::complete;
@@ -49,6 +51,19 @@ pub struct BookmarkSetArgs {
     #[arg(long, short = 'B')]
     allow_backwards: bool,
 
+    /// Only update the bookmarks if they currently point to this revision
+    ///
+    /// If any bookmark points elsewhere, no bookmark is updated and the command
+    /// fails without creating an operation. The bookmarks are compared with
+    /// the repo state loaded by this command, which includes any concurrent
+    /// operations that have already finished.
+    #[arg(
+        long,
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    expect_at: Option<RevisionArg>,
+
     /// The bookmarks to update
     #[arg(
         required = true,
@@ -73,8 +88,34 @@ pub fn cmd_bookmark_set(
     }
     let target_commit = workspace_command
         .resolve_single_rev(ui, args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
+    let expected_commit = args
+        .expect_at
+        .as_ref()
+        .map(|revision| workspace_command.resolve_single_rev(ui, revision))
+        .transpose()?;
     let repo = workspace_command.repo().as_ref();
     let bookmark_names = &args.names;
+    if let Some(expected_commit) = &expected_commit {
+        for name in bookmark_names {
+            let old_target = repo.view().get_local_bookmark(name);
+            if old_target.as_normal() == Some(expected_commit.id()) {
+                continue;
+            }
+            let actual = if let Some(id) = old_target.as_normal() {
+                let commit = repo.store().get_commit(id)?;
+                workspace_command.format_commit_summary(&commit)
+            } else if old_target.is_absent() {
+                "nothing".to_owned()
+            } else {
+                "conflicting targets".to_owned()
+            };
+            return Err(user_error(format!(
+                "Bookmark {name} points to {actual}, not {expected}",
+                name = name.as_symbol(),
+                expected = workspace_command.format_commit_summary(expected_commit),
+            )));
+        }
+    }
     let mut new_bookmark_count = 0;
     let mut moved_bookmark_count = 0;
     for name in bookmark_names {
@@ -17,8 +17,10 @@ use std::rc::Rc;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
 
 use super::find_remote_bookmarks;
+use super::write_tracking_summary;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RemoteBookmarkNamePattern;
 use crate::command_error::CommandError;
@@ -49,6 +51,10 @@ pub struct BookmarkTrackArgs {
         add = ArgValueCandidates::new(complete::untracked_bookmarks),
     )]
     names: Vec<RemoteBookmarkNamePattern>,
+    /// Print the tracking state of each bookmark, and whether the local and
+    /// remote bookmarks are in sync
+    #[arg(long)]
+    verbose: bool,
 }
 
 pub fn cmd_bookmark_track(
@@ -79,6 +85,9 @@ pub fn cmd_bookmark_track(
             "Started tracking {} remote bookmarks.",
             symbols.len()
         )?;
+        if args.verbose {
+            write_tracking_summary(ui, tx.repo().view(), &symbols)?;
+        }
     }
     tx.finish(
         ui,
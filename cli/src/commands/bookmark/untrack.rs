@@ -14,8 +14,11 @@
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::ref_name::RemoteRefSymbol;
+use jj_lib::repo::Repo as _;
 
 use super::find_remote_bookmarks;
+use super::write_tracking_summary;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RemoteBookmarkNamePattern;
 use crate::command_error::CommandError;
@@ -27,6 +30,10 @@ use crate::ui::Ui;
 /// A non-tracking remote bookmark is just a pointer to the last-fetched remote
 /// bookmark. It won't be imported as a local bookmark on future pulls.
 ///
+/// Remote bookmarks which don't exist yet, but are tracked by `jj bookmark
+/// create --tracked`, can also be untracked. They won't be created by the next
+/// `jj git push --tracked`.
+///
 /// If you want to forget a local bookmark while also untracking the
 /// corresponding remote bookmarks, use `jj bookmark forget` instead.
 #[derive(clap::Args, Clone, Debug)]
@@ -46,6 +53,10 @@ pub struct BookmarkUntrackArgs {
         add = ArgValueCandidates::new(complete::tracked_bookmarks)
     )]
     names: Vec<RemoteBookmarkNamePattern>,
+    /// Print the tracking state of each bookmark, and whether the local and
+    /// remote bookmarks are in sync
+    #[arg(long)]
+    verbose: bool,
 }
 
 pub fn cmd_bookmark_untrack(
@@ -55,8 +66,30 @@ pub fn cmd_bookmark_untrack(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
-    let mut symbols = Vec::new();
-    for (symbol, remote_ref) in find_remote_bookmarks(repo.view(), &args.names)? {
+    let view = repo.view();
+    // Pending remote bookmarks don't exist on the remote yet, but are tracked.
+    let is_match = |pattern: &RemoteBookmarkNamePattern, symbol: RemoteRefSymbol<'_>| {
+        pattern.bookmark.matches(symbol.name.as_str())
+            && pattern.remote.matches(symbol.remote.as_str())
+    };
+    let pending_symbols = view
+        .pending_remote_bookmarks()
+        .filter(|&symbol| args.names.iter().any(|pattern| is_match(pattern, symbol)))
+        .collect_vec();
+    let (pending_patterns, remote_patterns): (Vec<_>, Vec<_>) =
+        args.names.iter().cloned().partition(|pattern| {
+            pending_symbols
+                .iter()
+                .any(|&symbol| is_match(pattern, symbol))
+        });
+    let mut remote_bookmarks = find_remote_bookmarks(view, &remote_patterns)?;
+    for pattern in &pending_patterns {
+        remote_bookmarks.extend(view.remote_bookmarks_matching(&pattern.bookmark, &pattern.remote));
+    }
+    remote_bookmarks.sort_unstable_by_key(|&(symbol, _)| symbol);
+    remote_bookmarks.dedup_by_key(|&mut (symbol, _)| symbol);
+    let mut symbols = pending_symbols;
+    for (symbol, remote_ref) in remote_bookmarks {
         if jj_lib::git::is_special_git_remote(symbol.remote) {
             // This restriction can be lifted if we want to support untracked @git
             // bookmarks.
@@ -73,6 +106,7 @@ pub fn cmd_bookmark_untrack(
             symbols.push(symbol);
         }
     }
+    symbols.sort_unstable();
     let mut tx = workspace_command.start_transaction();
     for &symbol in &symbols {
         tx.repo_mut().untrack_remote_bookmark(symbol);
@@ -83,6 +117,9 @@ pub fn cmd_bookmark_untrack(
             "Stopped tracking {} remote bookmarks.",
             symbols.len()
         )?;
+        if args.verbose {
+            write_tracking_summary(ui, tx.repo().view(), &symbols)?;
+        }
     }
     tx.finish(
         ui,
@@ -69,6 +69,9 @@ pub(crate) struct CommitArgs {
         value_parser = parse_author
     )]
     author: Option<(String, String)>,
+    /// Record a note explaining the commit, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -91,6 +94,9 @@ pub(crate) fn cmd_commit(
         workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let text_editor = workspace_command.text_editor()?;
     let mut tx = workspace_command.start_transaction();
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
     let base_tree = commit.parent_tree(tx.repo())?;
     let format_instructions = || {
         format!(
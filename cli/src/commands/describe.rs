@@ -107,6 +107,9 @@ pub(crate) struct DescribeArgs {
         value_parser = parse_author
     )]
     author: Option<(String, String)>,
+    /// Record a note explaining the description change, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -132,6 +135,9 @@ pub(crate) fn cmd_describe(
     let text_editor = workspace_command.text_editor()?;
 
     let mut tx = workspace_command.start_transaction();
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
     let tx_description = match commits.as_slice() {
         [] => unreachable!(),
         [commit] => format!("describe commit {}", commit.id().hex()),
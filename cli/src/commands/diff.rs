@@ -200,7 +200,9 @@ pub(crate) fn cmd_diff(
             .parse_template(ui, &language, text)?
             .labeled(["diff"]);
         maybe_template = Some(template);
-        diff_renderer = workspace_command.diff_renderer(vec![]);
+        diff_renderer = workspace_command
+            .diff_renderer(vec![])
+            .with_change_types(args.format.diff_type.clone());
     } else {
         maybe_template = None;
         diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
@@ -208,7 +210,7 @@ pub(crate) fn cmd_diff(
 
     ui.request_pager();
     if let Some(template) = &maybe_template {
-        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+        let tree_diff = diff_renderer.diff_stream(&from_tree, &to_tree, &matcher, &copy_records);
         show_templated(ui.stdout_formatter().as_mut(), tree_diff, template).block_on()?;
     }
     diff_renderer.show_diff(
@@ -87,6 +87,9 @@ pub(crate) struct DiffeditArgs {
     /// is preserved instead of preserving the diff.
     #[arg(long)]
     restore_descendants: bool,
+    /// Record a note explaining the edit, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -117,6 +120,9 @@ pub(crate) fn cmd_diffedit(
 
     let diff_editor = workspace_command.diff_editor(ui, args.tool.as_deref())?;
     let mut tx = workspace_command.start_transaction();
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
     let format_instructions = || {
         format!(
             "\
@@ -23,15 +23,18 @@ use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::matchers::EverythingMatcher;
+use jj_lib::operation::Operation;
 use tracing::instrument;
 
 use crate::cli_util::format_template;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::EVOLOG_NOTE_TAG;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
+use crate::formatter::Formatter;
 use crate::graphlog::get_graphlog;
 use crate::graphlog::GraphStyle;
 use crate::templater::TemplateRenderer;
@@ -79,6 +82,16 @@ pub(crate) struct EvologArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    /// Render each revision using the template loaded from the given file
+    ///
+    /// Template aliases defined in the config can be used in the file.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "template",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    template_file: Option<String>,
     /// Show patch compared to the previous version of this change
     ///
     /// If the previous version has different parents, it will be temporarily
@@ -111,13 +124,16 @@ pub(crate) fn cmd_evolog(
     let node_template: TemplateRenderer<Option<Commit>>;
     {
         let language = workspace_command.commit_template_language();
-        let template_string = match &args.template {
-            Some(value) => value.to_string(),
-            None => workspace_command.settings().get_string("templates.log")?,
-        };
-        template = workspace_command
-            .parse_template(ui, &language, &template_string)?
-            .labeled(["log", "commit"]);
+        template = if let Some(path) = &args.template_file {
+            workspace_command.parse_template_file(ui, &language, path)?
+        } else {
+            let template_string = match &args.template {
+                Some(value) => value.to_string(),
+                None => workspace_command.settings().get_string("templates.log")?,
+            };
+            workspace_command.parse_template(ui, &language, &template_string)?
+        }
+        .labeled(["log", "commit"]);
         node_template = workspace_command
             .parse_template(
                 ui,
@@ -182,6 +198,7 @@ pub(crate) fn cmd_evolog(
                     write!(formatter, " operation ")?;
                     op_summary_template.format(op, formatter)?;
                     writeln!(formatter)?;
+                    write_evolog_note(formatter, op)?;
                 }
                 io::Result::Ok(())
             })?;
@@ -225,6 +242,7 @@ pub(crate) fn cmd_evolog(
                     write!(formatter, "-- operation ")?;
                     op_summary_template.format(op, formatter)?;
                     writeln!(formatter)?;
+                    write_evolog_note(formatter, op)?;
                 }
                 io::Result::Ok(())
             })?;
@@ -245,3 +263,11 @@ pub(crate) fn cmd_evolog(
 
     Ok(())
 }
+
+/// Writes the note recorded by `--evolog-note` in the operation, if any.
+fn write_evolog_note(formatter: &mut dyn Formatter, op: &Operation) -> io::Result<()> {
+    if let Some(note) = op.metadata().tags.get(EVOLOG_NOTE_TAG) {
+        writeln!(formatter.labeled("evolog_note"), "-- note: {note}")?;
+    }
+    Ok(())
+}
@@ -248,16 +248,13 @@ pub fn cmd_git_push(
         );
     } else if args.tracked {
         for (name, targets) in view.local_remote_bookmarks(remote) {
-            if !targets.remote_ref.is_tracked() {
+            let remote_symbol = name.to_remote_symbol(remote);
+            let is_pending = view.is_remote_bookmark_pending(remote_symbol);
+            if !targets.remote_ref.is_tracked() && !is_pending {
                 continue;
             }
-            let allow_new = false; // doesn't matter
-            match classify_bookmark_update(
-                name.to_remote_symbol(remote),
-                targets,
-                allow_new,
-                args.deleted,
-            ) {
+            let allow_new = is_pending; // tracked bookmarks exist on the remote
+            match classify_bookmark_update(remote_symbol, targets, allow_new, args.deleted) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
@@ -339,6 +336,8 @@ pub fn cmd_git_push(
                 continue;
             }
             let remote_symbol = name.to_remote_symbol(remote);
+            // Pending remote bookmarks can be created without --allow-new.
+            let allow_new = allow_new || view.is_remote_bookmark_pending(remote_symbol);
             let allow_delete = true; // named explicitly, allow delete without --delete
             match classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
@@ -366,13 +365,10 @@ pub fn cmd_git_push(
             if !seen_bookmarks.insert(name) {
                 continue;
             }
+            let remote_symbol = name.to_remote_symbol(remote);
+            let allow_new = allow_new || view.is_remote_bookmark_pending(remote_symbol);
             let allow_delete = false;
-            match classify_bookmark_update(
-                name.to_remote_symbol(remote),
-                targets,
-                allow_new,
-                allow_delete,
-            ) {
+            match classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
@@ -12,32 +12,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::io::Write as _;
+use std::sync::Arc;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::MillisSinceEpoch;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::dag_walk;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
+use jj_lib::graph::GraphNode;
 use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::matchers::FilesMatcher;
+use jj_lib::matchers::IntersectionMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::store::Store;
+use jj_lib::str_util::StringPattern;
 use tracing::instrument;
 
 use crate::cli_util::format_template;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::diff_util;
 use crate::diff_util::DiffFormatArgs;
+use crate::formatter::FormatRecorder;
+use crate::formatter::Formatter;
 use crate::graphlog::get_graphlog;
 use crate::graphlog::GraphStyle;
 use crate::templater::TemplateRenderer;
+use crate::text_util;
 use crate::ui::Ui;
 
 /// Show revision history
@@ -75,6 +98,20 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     revisions: Vec<RevisionArg>,
+    /// How to combine the revsets given by multiple `-r` options
+    #[arg(long, value_name = "MODE", value_enum, default_value_t = RevsetCombination::Union)]
+    combine: RevsetCombination,
+    /// Hide the given revisions
+    ///
+    /// This is equivalent to `-r '(<REVSETS>) ~ (<EXCLUDE>)'`, and also applies
+    /// to the default revset. Gaps left in the graph are shown as elided
+    /// revisions.
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    exclude: Vec<RevisionArg>,
     /// Show revisions modifying the given paths
     #[arg(
         value_name = "FILESETS",
@@ -88,12 +125,107 @@ pub(crate) struct LogArgs {
     /// before being reversed.
     #[arg(long, short = 'n')]
     limit: Option<usize>,
+    /// Limit number of revisions to show from each root of the graph
+    ///
+    /// Each root's descendants are counted separately, so one long line of
+    /// development doesn't push the others out of view. Revisions that are
+    /// omitted this way are shown as elided ancestors.
+    #[arg(long, value_name = "LIMIT")]
+    limit_per_root: Option<usize>,
     /// Show revisions in the opposite order (older revisions first)
+    ///
+    /// Only the ids (and graph edges) of the revisions to show are collected
+    /// before being reversed. The commits are then loaded and rendered one by
+    /// one. Since `--limit` is applied first, it also bounds the number of
+    /// revisions collected.
     #[arg(long)]
     reversed: bool,
     /// Don't show the graph, show a flat list of revisions
     #[arg(long)]
     no_graph: bool,
+    /// Order revisions by author date where the graph allows it
+    ///
+    /// Children are still shown before their parents. Revisions which aren't
+    /// constrained by the graph are shown newest first. Revisions with the
+    /// same date are ordered by commit id, so the order is stable. The
+    /// branches selected by `revsets.log-graph-prioritize` aren't shown
+    /// first.
+    #[arg(long, conflicts_with = "committer_date_order")]
+    author_date_order: bool,
+    /// Order revisions by committer date where the graph allows it
+    ///
+    /// Children are still shown before their parents. Revisions which aren't
+    /// constrained by the graph are shown newest first. Revisions with the
+    /// same date are ordered by commit id, so the order is stable. The
+    /// branches selected by `revsets.log-graph-prioritize` aren't shown
+    /// first.
+    #[arg(long)]
+    committer_date_order: bool,
+    /// Insert a blank line between revisions in the flat list
+    #[arg(long, requires = "no_graph")]
+    separator: bool,
+    /// Write the given string after each revision in the flat list
+    ///
+    /// The separator is written in addition to the rendered template, so the
+    /// content of each revision is left as is.
+    #[arg(
+        long,
+        value_name = "SEPARATOR",
+        requires = "no_graph",
+        conflicts_with_all = ["separator", "columns"],
+    )]
+    record_separator: Option<String>,
+    /// Don't write `--record-separator` after the last revision
+    #[arg(long, requires = "record_separator")]
+    no_trailing_separator: bool,
+    /// Render the given revisions as roots of the graph
+    ///
+    /// Edges from these revisions to ancestors that aren't shown are omitted,
+    /// so they are drawn as root nodes instead of with a dangling `~`. Edges to
+    /// shown ancestors are kept.
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        conflicts_with = "no_graph",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    graph_from: Vec<RevisionArg>,
+    /// Render only the given revisions using the template
+    ///
+    /// The other revisions are drawn as bare nodes, so the focused revisions
+    /// are shown along with how they are connected in the graph.
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        conflicts_with_all = ["no_graph", "graph_lines_only"],
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    focus: Vec<RevisionArg>,
+    /// Emphasize the nodes of the given revisions in the graph
+    ///
+    /// This is typically used with a `A::B` range to trace the ancestry path
+    /// from `A` to `B` in a broader graph. The nodes are rendered with the
+    /// `ancestry_path` label and distinct symbols, so the path can be told
+    /// apart without colors. The working-copy, immutable, and conflicted
+    /// nodes on the path are shown as `◉`, `◈`, and `⊗` respectively (`&`,
+    /// `#`, and `X` in ASCII graph styles), and the others as `●` (`*`).
+    #[arg(
+        long,
+        value_name = "REVSET",
+        conflicts_with = "no_graph",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    highlight_path: Option<RevisionArg>,
+    /// Render only the graph and the full commit id of each revision
+    ///
+    /// The revisions aren't rendered using templates, and no diffs are shown,
+    /// so the output can be annotated by other tools. The graph is drawn in the
+    /// configured `ui.graph.style`.
+    #[arg(
+        long,
+        conflicts_with_all = ["no_graph", "template", "template_file", "patch", "highlight"],
+    )]
+    graph_lines_only: bool,
     /// Render each revision using the given template
     ///
     /// Run `jj log -T` to list the built-in templates.
@@ -111,20 +243,145 @@ pub(crate) struct LogArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    /// Render each revision using the template loaded from the given file
+    ///
+    /// Template aliases defined in the config can be used in the file.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "template",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    template_file: Option<String>,
+    /// Show timestamps in ISO 8601 format in the default template
+    ///
+    /// This overrides the `format_timestamp()` and `format_time_range()`
+    /// template aliases, so the output doesn't depend on the configured time
+    /// format. Templates specified by `-T` or `--template-file` are
+    /// unaffected.
+    #[arg(long)]
+    iso_time: bool,
+    /// Render each revision as a row of fields, one for each template
+    ///
+    /// This can be repeated to add more fields. Fields are joined by
+    /// `--column-sep`. Backslashes, newlines, and occurrences of the separator
+    /// within a field are escaped with a backslash, so each revision is
+    /// printed on a single line. Colors are not applied to fields.
+    ///
+    /// For example, `--column change_id.short() --column
+    /// description.first_line()`
+    #[arg(
+        long = "column",
+        value_name = "TEMPLATE",
+        requires = "no_graph",
+        conflicts_with_all = ["template", "template_file"],
+    )]
+    columns: Vec<String>,
+    /// Separator between fields of `--column` [default: tab]
+    #[arg(long, value_name = "SEPARATOR", requires = "columns")]
+    column_sep: Option<String>,
+    /// Terminate `--column` rows with NUL instead of newline
+    ///
+    /// Newlines within fields are not escaped.
+    #[arg(long, requires = "columns")]
+    null: bool,
+    /// Highlight matches of the given pattern in the rendered revisions
+    ///
+    /// The pattern is matched as a substring by default. Use a prefix like
+    /// `regex:` or `glob:` to specify other [string pattern] kinds. Exact and
+    /// glob patterns match whole whitespace-separated words. Matches are
+    /// styled with the `highlight` label on top of the existing colors. Graph
+    /// and diff output isn't highlighted.
+    ///
+    /// [string pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(long, value_name = "PATTERN", value_parser = StringPattern::parse_default_substring)]
+    highlight: Option<StringPattern>,
+    /// Don't disambiguate ids within the `revsets.short-prefixes` revset
+    ///
+    /// Ids are shortened to unique prefixes within the whole repo instead. The
+    /// configured revset isn't loaded, so it doesn't have to be valid.
+    #[arg(long)]
+    no_short_prefixes: bool,
+    /// Only show bookmarks matching the given name patterns
+    ///
+    /// This affects the bookmarks rendered by the `bookmarks`,
+    /// `local_bookmarks`, and `remote_bookmarks` template keywords. The
+    /// revisions to show aren't affected. By default, the specified name
+    /// matches exactly. Use `glob:` prefix to select bookmarks by [wildcard
+    /// pattern].
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::bookmarks),
+    )]
+    decorate_refs: Vec<StringPattern>,
+    /// Don't show bookmarks matching the given name patterns
+    ///
+    /// Takes precedence over `--decorate-refs`. The excluded bookmarks still
+    /// exist and can be referenced in revsets.
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::bookmarks),
+    )]
+    decorate_refs_exclude: Vec<StringPattern>,
     /// Show patch
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Only show diffs of the paths changed in the given revisions
+    ///
+    /// The shown revisions aren't affected. This can be used to review
+    /// changes to the files which were also changed in another line of
+    /// development, e.g. `--diff-paths-from 'trunk()..main'`.
+    #[arg(
+        long,
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    diff_paths_from: Option<RevisionArg>,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
 
+/// Loads the workspace with an empty `revsets.short-prefixes` revset, so ids
+/// are shortened within the whole repo and the configured revset isn't parsed.
+fn workspace_helper_without_short_prefixes(
+    ui: &Ui,
+    command: &CommandHelper,
+) -> Result<WorkspaceCommandHelper, CommandError> {
+    let mut config = command.settings().config().clone();
+    let mut layer = ConfigLayer::empty(ConfigSource::CommandArg);
+    layer.set_value("revsets.short-prefixes", "").unwrap();
+    config.add_layer(layer);
+    let settings = command.settings().with_new_config(config)?;
+    let workspace_root = command.workspace_loader()?.workspace_root().to_owned();
+    let workspace = command.load_workspace_at(&workspace_root, &settings)?;
+    let op_head = command.resolve_operation(ui, workspace.repo_loader())?;
+    let repo = workspace.repo_loader().load_at(&op_head)?;
+    let mut workspace_command = command.for_workable_repo(ui, workspace, repo)?;
+    if command.is_working_copy_writable() {
+        workspace_command.maybe_snapshot(ui)?;
+    }
+    Ok(workspace_command)
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_log(
     ui: &mut Ui,
     command: &CommandHelper,
     args: &LogArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_command = if args.no_short_prefixes {
+        workspace_helper_without_short_prefixes(ui, command)?
+    } else {
+        command.workspace_helper(ui)?
+    };
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
@@ -133,8 +390,20 @@ pub(crate) fn cmd_log(
         let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
             let revset_string = settings.get_string("revsets.log")?;
             workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
-        } else if !args.revisions.is_empty() {
-            workspace_command.parse_union_revsets(ui, &args.revisions)?
+        } else if let Some((first, rest)) = args.revisions.split_first() {
+            match args.combine {
+                RevsetCombination::Union => {
+                    workspace_command.parse_union_revsets(ui, &args.revisions)?
+                }
+                RevsetCombination::Intersect => {
+                    let mut expression = workspace_command.parse_revset(ui, first)?;
+                    for arg in rest {
+                        expression
+                            .intersect_with(workspace_command.parse_revset(ui, arg)?.expression());
+                    }
+                    expression
+                }
+            }
         } else {
             // a path was specified so we use all() and add path filter later
             workspace_command.attach_revset_evaluator(RevsetExpression::all())
@@ -145,17 +414,69 @@ pub(crate) fn cmd_log(
             let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
             expression.intersect_with(&RevsetExpression::filter(predicate));
         }
+        if !args.exclude.is_empty() {
+            let excluded = workspace_command.parse_union_revsets(ui, &args.exclude)?;
+            expression.subtract(excluded.expression());
+        }
         expression
     };
     let prio_revset = settings.get_string("revsets.log-graph-prioritize")?;
     let prio_revset = workspace_command.parse_revset(ui, &RevisionArg::from(prio_revset))?;
+    let graph_from_revset = if args.graph_from.is_empty() {
+        None
+    } else {
+        Some(
+            workspace_command
+                .parse_union_revsets(ui, &args.graph_from)?
+                .evaluate()?,
+        )
+    };
+    let focus_revset = if args.focus.is_empty() {
+        None
+    } else {
+        Some(
+            workspace_command
+                .parse_union_revsets(ui, &args.focus)?
+                .evaluate()?,
+        )
+    };
+    let path_revset = if let Some(revision_arg) = &args.highlight_path {
+        Some(
+            workspace_command
+                .parse_revset(ui, revision_arg)?
+                .evaluate()?,
+        )
+    } else {
+        None
+    };
 
     let repo = workspace_command.repo();
     let matcher = fileset_expression.to_matcher();
+    let matcher: Box<dyn Matcher> = if let Some(revision_arg) = &args.diff_paths_from {
+        let commits = workspace_command
+            .parse_revset(ui, revision_arg)?
+            .evaluate_to_commits()?;
+        let mut paths = HashSet::new();
+        for commit in commits {
+            let commit = commit?;
+            paths.extend(diff_util::changed_paths(
+                &commit.parent_tree(repo.as_ref())?,
+                &commit.tree()?,
+            )?);
+        }
+        Box::new(IntersectionMatcher::new(matcher, FilesMatcher::new(paths)))
+    } else {
+        matcher
+    };
     let revset = revset_expression.evaluate()?;
 
     let store = repo.store();
-    let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
+    // Only the graph structure is rendered with --graph-lines-only.
+    let diff_renderer = if args.graph_lines_only {
+        None
+    } else {
+        workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?
+    };
     let graph_style = GraphStyle::from_settings(settings)?;
 
     let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
@@ -163,20 +484,79 @@ pub(crate) fn cmd_log(
 
     let template: TemplateRenderer<Commit>;
     let node_template: TemplateRenderer<Option<Commit>>;
+    let path_node_template: Option<TemplateRenderer<Option<Commit>>>;
+    let column_templates: Vec<TemplateRenderer<Commit>>;
     {
-        let language = workspace_command.commit_template_language();
-        let template_string = match &args.template {
-            Some(value) => value.to_string(),
-            None => settings.get_string("templates.log")?,
-        };
-        template = workspace_command
-            .parse_template(ui, &language, &template_string)?
-            .labeled(["log", "commit"]);
+        let mut language = workspace_command.commit_template_language();
+        if !args.decorate_refs.is_empty() || !args.decorate_refs_exclude.is_empty() {
+            let includes = &args.decorate_refs;
+            let excludes = &args.decorate_refs_exclude;
+            language.set_bookmark_filter(move |name| {
+                let name = name.as_str();
+                (includes.is_empty() || includes.iter().any(|pattern| pattern.matches(name)))
+                    && !excludes.iter().any(|pattern| pattern.matches(name))
+            });
+        }
+        column_templates = args
+            .columns
+            .iter()
+            .map(|text| workspace_command.parse_template(ui, &language, text))
+            .try_collect()?;
+        template = if let Some(path) = &args.template_file {
+            workspace_command.parse_template_file(ui, &language, path)?
+        } else if let Some(value) = &args.template {
+            workspace_command.parse_template(ui, &language, value)?
+        } else {
+            let text = settings.get_string("templates.log")?;
+            if args.iso_time {
+                workspace_command
+                    .env()
+                    .parse_template_with_iso_time(ui, &language, &text)?
+            } else {
+                workspace_command.parse_template(ui, &language, &text)?
+            }
+        }
+        .labeled(["log", "commit"]);
         node_template = workspace_command
             .parse_template(ui, &language, &settings.get_string("templates.log_node")?)?
             .labeled(["log", "commit", "node"]);
+        path_node_template = if path_revset.is_some() {
+            let text = if graph_style.is_ascii() {
+                "builtin_log_node_ancestry_path_ascii"
+            } else {
+                "builtin_log_node_ancestry_path"
+            };
+            let template = workspace_command
+                .parse_template(ui, &language, text)?
+                .labeled(["log", "commit", "node"]);
+            Some(template)
+        } else {
+            None
+        };
     }
 
+    let format_commit = |formatter: &mut dyn Formatter, commit: &Commit| -> io::Result<()> {
+        if let Some(pattern) = &args.highlight {
+            let mut recorder = FormatRecorder::new();
+            template.format(commit, &mut recorder)?;
+            // Non-UTF-8 output isn't highlighted
+            let ranges = std::str::from_utf8(recorder.data())
+                .map(|text| pattern.match_ranges(text))
+                .unwrap_or_default();
+            text_util::write_highlighted(formatter, &recorder, &ranges, "highlight")
+        } else {
+            template.format(commit, formatter)
+        }
+    };
+
+    let date_order = if args.author_date_order {
+        Some(DateOrder::Author)
+    } else if args.committer_date_order {
+        Some(DateOrder::Committer)
+    } else {
+        None
+    };
+
     {
         ui.request_pager();
         let mut formatter = ui.stdout_formatter();
@@ -186,28 +566,66 @@ pub(crate) fn cmd_log(
             let mut raw_output = formatter.raw()?;
             let mut graph = get_graphlog(graph_style, raw_output.as_mut());
             let iter: Box<dyn Iterator<Item = _>> = {
-                let mut forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
+                let forward_iter: Box<dyn Iterator<Item = _>> = if let Some(date_order) = date_order
+                {
+                    let nodes = sort_graph_by_date(store, revset.iter_graph(), date_order)?;
+                    Box::new(nodes.into_iter().map(Ok))
+                } else {
+                    let mut forward_iter =
+                        TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
 
-                let has_commit = revset.containing_fn();
+                    let has_commit = revset.containing_fn();
 
-                for prio in prio_revset.evaluate_to_commit_ids()? {
-                    let prio = prio?;
-                    if has_commit(&prio)? {
-                        forward_iter.prioritize_branch(prio);
+                    for prio in prio_revset.evaluate_to_commit_ids()? {
+                        let prio = prio?;
+                        if has_commit(&prio)? {
+                            forward_iter.prioritize_branch(prio);
+                        }
                     }
-                }
+                    Box::new(forward_iter)
+                };
+
+                let forward_iter: Box<dyn Iterator<Item = _>> =
+                    if let Some(limit) = args.limit_per_root {
+                        let nodes = limit_graph_per_root(forward_iter.try_collect()?, limit);
+                        Box::new(nodes.into_iter().map(Ok))
+                    } else {
+                        forward_iter
+                    };
 
                 // The input to TopoGroupedGraphIterator shouldn't be truncated
                 // because the prioritized commit must exist in the input set.
                 let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
+
+                // Graph roots have no edges to ancestors that aren't shown. This
+                // has to be done before reversing the graph.
+                let is_graph_root = graph_from_revset
+                    .as_ref()
+                    .map(|revset| revset.containing_fn());
+                let forward_iter =
+                    forward_iter.map(move |node| -> Result<_, RevsetEvaluationError> {
+                        let (commit_id, mut edges) = node?;
+                        if let Some(is_graph_root) = &is_graph_root {
+                            if is_graph_root(&commit_id)? {
+                                edges.retain(|edge| edge.edge_type != GraphEdgeType::Missing);
+                            }
+                        }
+                        Ok((commit_id, edges))
+                    });
                 if args.reversed {
                     Box::new(reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok))
                 } else {
                     Box::new(forward_iter)
                 }
             };
+            let is_focused = focus_revset.as_ref().map(|revset| revset.containing_fn());
+            let is_on_path = path_revset.as_ref().map(|revset| revset.containing_fn());
             for node in iter {
                 let (commit_id, edges) = node?;
+                let show_content = match &is_focused {
+                    Some(is_focused) => is_focused(&commit_id)?,
+                    None => true,
+                };
 
                 // The graph is keyed by (CommitId, is_synthetic)
                 let mut graphlog_edges = vec![];
@@ -242,13 +660,17 @@ pub(crate) fn cmd_log(
                 let commit = store.get_commit(&key.0)?;
                 let within_graph =
                     with_content_format.sub_width(graph.width(&key, &graphlog_edges));
-                within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
-                    template.format(&commit, formatter)
-                })?;
+                if args.graph_lines_only {
+                    writeln!(buffer, "{}", commit.id().hex())?;
+                } else if show_content {
+                    within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+                        format_commit(formatter, &commit)
+                    })?;
+                }
                 if !buffer.ends_with(b"\n") {
                     buffer.push(b'\n');
                 }
-                if let Some(renderer) = &diff_renderer {
+                if let Some(renderer) = diff_renderer.as_ref().filter(|_| show_content) {
                     let mut formatter = ui.new_formatter(&mut buffer);
                     renderer.show_patch(
                         ui,
@@ -259,7 +681,11 @@ pub(crate) fn cmd_log(
                     )?;
                 }
 
-                let node_symbol = format_template(ui, &Some(commit), &node_template);
+                let commit_node_template = match (&path_node_template, &is_on_path) {
+                    (Some(template), Some(is_on_path)) if is_on_path(&key.0)? => template,
+                    _ => &node_template,
+                };
+                let node_symbol = format_template(ui, &Some(commit), commit_node_template);
                 graph.add_node(
                     &key,
                     &graphlog_edges,
@@ -273,9 +699,14 @@ pub(crate) fn cmd_log(
                     let mut buffer = vec![];
                     let within_graph =
                         with_content_format.sub_width(graph.width(&elided_key, &edges));
-                    within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
-                        writeln!(formatter.labeled("elided"), "(elided revisions)")
-                    })?;
+                    if args.graph_lines_only {
+                        buffer.push(b'\n');
+                    } else {
+                        within_graph
+                            .write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+                                writeln!(formatter.labeled("elided"), "(elided revisions)")
+                            })?;
+                    }
                     let node_symbol = format_template(ui, &None, &node_template);
                     graph.add_node(
                         &elided_key,
@@ -287,7 +718,20 @@ pub(crate) fn cmd_log(
             }
         } else {
             let iter: Box<dyn Iterator<Item = Result<CommitId, RevsetEvaluationError>>> = {
-                let forward_iter = revset.iter().take(args.limit.unwrap_or(usize::MAX));
+                let forward_iter: Box<dyn Iterator<Item = _>> = if let Some(date_order) = date_order
+                {
+                    let mut nodes = sort_graph_by_date(store, revset.iter_graph(), date_order)?;
+                    if let Some(limit) = args.limit_per_root {
+                        nodes = limit_graph_per_root(nodes, limit);
+                    }
+                    Box::new(nodes.into_iter().map(|(id, _)| Ok(id)))
+                } else if let Some(limit) = args.limit_per_root {
+                    let nodes = limit_graph_per_root(revset.iter_graph().try_collect()?, limit);
+                    Box::new(nodes.into_iter().map(|(id, _)| Ok(id)))
+                } else {
+                    Box::new(revset.iter())
+                };
+                let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
                 if args.reversed {
                     let entries: Vec<_> = forward_iter.try_collect()?;
                     Box::new(entries.into_iter().rev().map(Ok))
@@ -295,15 +739,40 @@ pub(crate) fn cmd_log(
                     Box::new(forward_iter)
                 }
             };
-            for commit_or_error in iter.commits(store) {
+            let mut has_records = false;
+            for (i, commit_or_error) in iter.commits(store).enumerate() {
                 let commit = commit_or_error?;
-                with_content_format
-                    .write(formatter, |formatter| template.format(&commit, formatter))?;
+                if args.separator && i > 0 {
+                    writeln!(formatter)?;
+                }
+                if let Some(separator) = &args.record_separator {
+                    if i > 0 {
+                        write!(formatter, "{separator}")?;
+                    }
+                }
+                has_records = true;
+                if !column_templates.is_empty() {
+                    let separator = args.column_sep.as_deref().unwrap_or("\t");
+                    let fields = column_templates.iter().map(|template| {
+                        let field = template.format_plain_text(&commit);
+                        escape_column_field(&field, separator.as_bytes(), args.null)
+                    });
+                    formatter.write_all(&fields.collect_vec().join(separator.as_bytes()))?;
+                    formatter.write_all(if args.null { b"\0" } else { b"\n" })?;
+                } else {
+                    with_content_format
+                        .write(formatter, |formatter| format_commit(formatter, &commit))?;
+                }
                 if let Some(renderer) = &diff_renderer {
                     let width = ui.term_width();
                     renderer.show_patch(ui, formatter, &commit, matcher.as_ref(), width)?;
                 }
             }
+            if let Some(separator) = &args.record_separator {
+                if has_records && !args.no_trailing_separator {
+                    write!(formatter, "{separator}")?;
+                }
+            }
         }
     }
 
@@ -333,3 +802,171 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+enum RevsetCombination {
+    /// Show revisions in any of the revsets
+    Union,
+    /// Show revisions in all of the revsets
+    Intersect,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum DateOrder {
+    Author,
+    Committer,
+}
+
+/// Sorts graph nodes topologically, showing newer commits first where the
+/// graph doesn't constrain the order. Prioritized branches aren't taken into
+/// account.
+fn sort_graph_by_date(
+    store: &Arc<Store>,
+    nodes: impl Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>>,
+    date_order: DateOrder,
+) -> Result<Vec<GraphNode<CommitId>>, CommandError> {
+    let mut edges_map: HashMap<CommitId, Vec<GraphEdge<CommitId>>> = nodes.try_collect()?;
+    let timestamps: HashMap<&CommitId, MillisSinceEpoch> = edges_map
+        .keys()
+        .map(|id| {
+            let commit = store.get_commit(id)?;
+            let signature = match date_order {
+                DateOrder::Author => commit.author(),
+                DateOrder::Committer => commit.committer(),
+            };
+            Ok((id, signature.timestamp.timestamp))
+        })
+        .try_collect::<_, _, BackendError>()?;
+    let to_key = |id: &CommitId| (timestamps[id], id.clone());
+    let sorted_ids = dag_walk::topo_order_reverse_ord(
+        edges_map.keys().map(to_key),
+        |(_, id)| id.clone(),
+        |(_, id)| {
+            edges_map[id]
+                .iter()
+                .filter(|edge| edges_map.contains_key(&edge.target))
+                .map(|edge| to_key(&edge.target))
+                .collect_vec()
+        },
+    )
+    .into_iter()
+    .map(|(_, id)| id)
+    .collect_vec();
+    drop(timestamps);
+    Ok(sorted_ids
+        .into_iter()
+        .map(|id| {
+            let edges = edges_map.remove(&id).unwrap();
+            (id, edges)
+        })
+        .collect())
+}
+
+/// Keeps the first `limit` nodes descending from each root of the graph.
+///
+/// A node is kept if any of the roots it descends from still has room. Edges
+/// to the dropped nodes are replaced by indirect edges to their nearest kept
+/// ancestors, or by missing edges if there are none.
+fn limit_graph_per_root(nodes: Vec<GraphNode<CommitId>>, limit: usize) -> Vec<GraphNode<CommitId>> {
+    // Roots each node descends from, computed from the ancestors first
+    let mut roots_map: HashMap<&CommitId, Vec<usize>> = HashMap::new();
+    let mut root_count = 0;
+    for (id, edges) in nodes.iter().rev() {
+        let mut roots = edges
+            .iter()
+            .filter_map(|edge| roots_map.get(&edge.target))
+            .flatten()
+            .copied()
+            .collect_vec();
+        if roots.is_empty() {
+            roots.push(root_count);
+            root_count += 1;
+        } else {
+            roots.sort_unstable();
+            roots.dedup();
+        }
+        roots_map.insert(id, roots);
+    }
+
+    let mut counts = vec![0; root_count];
+    let mut is_kept = HashMap::new();
+    for (id, _) in &nodes {
+        let roots = &roots_map[id];
+        let kept = roots.iter().any(|&root| counts[root] < limit);
+        if kept {
+            for &root in roots {
+                counts[root] += 1;
+            }
+        }
+        is_kept.insert(id.clone(), kept);
+    }
+    drop(roots_map);
+
+    // Edges to be inherited by the children of each dropped node
+    let mut edges_via: HashMap<CommitId, Vec<GraphEdge<CommitId>>> = HashMap::new();
+    let mut kept_nodes = vec![];
+    for (id, edges) in nodes.into_iter().rev() {
+        let mut new_edges: Vec<GraphEdge<CommitId>> = vec![];
+        for edge in edges {
+            let inherited = edges_via
+                .get(&edge.target)
+                .map_or_else(|| vec![edge], Clone::clone);
+            for edge in inherited {
+                if !new_edges.contains(&edge) {
+                    new_edges.push(edge);
+                }
+            }
+        }
+        if is_kept[&id] {
+            kept_nodes.push((id, new_edges));
+        } else if new_edges.is_empty() {
+            edges_via.insert(id.clone(), vec![GraphEdge::missing(id)]);
+        } else {
+            let new_edges = new_edges
+                .into_iter()
+                .map(|edge| match edge.edge_type {
+                    GraphEdgeType::Missing => edge,
+                    _ => GraphEdge::indirect(edge.target),
+                })
+                .collect();
+            edges_via.insert(id, new_edges);
+        }
+    }
+    kept_nodes.reverse();
+    kept_nodes
+}
+
+/// Escapes backslashes, newlines (unless rows are NUL-terminated), and
+/// occurrences of the `separator` in a `--column` field. Every byte of a
+/// separator occurrence is escaped, so no part of it is left in the field.
+fn escape_column_field(field: &[u8], separator: &[u8], null: bool) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(field.len());
+    let mut rest = field;
+    while let Some((&b, tail)) = rest.split_first() {
+        if !separator.is_empty() && rest.starts_with(separator) {
+            for &b in separator {
+                escape_column_byte(&mut escaped, b);
+            }
+            rest = &rest[separator.len()..];
+            continue;
+        }
+        match b {
+            b'\\' => escape_column_byte(&mut escaped, b),
+            b'\n' if !null => escape_column_byte(&mut escaped, b),
+            b'\0' if null => escape_column_byte(&mut escaped, b),
+            _ => escaped.push(b),
+        }
+        rest = tail;
+    }
+    escaped
+}
+
+fn escape_column_byte(escaped: &mut Vec<u8>, b: u8) {
+    match b {
+        b'\\' => escaped.extend_from_slice(b"\\\\"),
+        b'\n' => escaped.extend_from_slice(b"\\n"),
+        b'\t' => escaped.extend_from_slice(b"\\t"),
+        b'\0' => escaped.extend_from_slice(b"\\0"),
+        _ => escaped.extend_from_slice(format!("\\x{b:02x}").as_bytes()),
+    }
+}
@@ -115,7 +115,7 @@ enum Command {
     Git(git::GitCommand),
     Help(help::HelpArgs),
     Interdiff(interdiff::InterdiffArgs),
-    Log(log::LogArgs),
+    Log(Box<log::LogArgs>),
     New(new::NewArgs),
     Next(next::NextArgs),
     #[command(subcommand)]
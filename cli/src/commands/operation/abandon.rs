@@ -18,7 +18,15 @@ use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::op_store::OpStore;
+use jj_lib::op_store::OpStoreObjectStats;
+use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
+use jj_lib::op_walk::ReparentStats;
+use jj_lib::operation::Operation;
+use jj_lib::repo::RepoLoader;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::str_util::StringPattern;
 
 use crate::cli_util::short_operation_hash;
 use crate::cli_util::CommandHelper;
@@ -37,13 +45,38 @@ use crate::ui::Ui;
 /// To discard recent operations, use `jj op restore <operation ID>` followed
 /// by `jj op abandon <operation ID>..@-`.
 ///
+/// To discard operations by description, use `jj op abandon --match
+/// <PATTERN>`. The current operation and merge operations are never abandoned
+/// this way.
+///
 /// The abandoned operations, commits, and other unreachable objects can later
 /// be garbage collected by using `jj util gc` command.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationAbandonArgs {
     /// The operation or operation range to abandon
-    #[arg(add = ArgValueCandidates::new(complete::operations))]
-    operation: String,
+    #[arg(
+        required_unless_present = "match_description",
+        add = ArgValueCandidates::new(complete::operations),
+    )]
+    operation: Option<String>,
+    /// Abandon all operations whose description matches the given pattern
+    ///
+    /// By default, the specified pattern matches the whole description. Use
+    /// `substring:` or `glob:` prefix to match part of it.
+    #[arg(
+        long = "match",
+        value_name = "PATTERN",
+        conflicts_with = "operation",
+        value_parser = StringPattern::parse,
+    )]
+    match_description: Option<StringPattern>,
+    /// Report the number and size of operation objects that became
+    /// unreachable
+    ///
+    /// These objects can be removed by `jj util gc`. Computing the sizes
+    /// requires scanning the operation store.
+    #[arg(long)]
+    report_freed: bool,
 }
 
 pub fn cmd_op_abandon(
@@ -63,52 +96,39 @@ pub fn cmd_op_abandon(
         return Err(cli_error("--at-op is not respected"));
     }
     let current_head_ops = op_walk::get_current_head_ops(op_store, op_heads_store.as_ref())?;
-    let resolve_op = |op_str| op_walk::resolve_op_at(op_store, &current_head_ops, op_str);
-    let (abandon_root_op, abandon_head_ops) =
-        if let Some((root_op_str, head_op_str)) = args.operation.split_once("..") {
-            let root_op = if root_op_str.is_empty() {
-                repo_loader.root_operation()
-            } else {
-                resolve_op(root_op_str)?
-            };
-            let head_ops = if head_op_str.is_empty() {
-                current_head_ops.clone()
-            } else {
-                vec![resolve_op(head_op_str)?]
-            };
-            (root_op, head_ops)
-        } else {
-            let op = resolve_op(&args.operation)?;
-            let parent_ops: Vec<_> = op.parents().try_collect()?;
-            let parent_op = match parent_ops.len() {
-                0 => return Err(user_error("Cannot abandon the root operation")),
-                1 => parent_ops.into_iter().next().unwrap(),
-                _ => return Err(user_error("Cannot abandon a merge operation")),
-            };
-            (parent_op, vec![op])
+    let old_unreachable_stats = if args.report_freed {
+        let head_ids = current_head_ops
+            .iter()
+            .map(|op| op.id().clone())
+            .collect_vec();
+        unreachable_object_stats(ui, op_store.as_ref(), &head_ids)?
+    } else {
+        None
+    };
+    let stats = if let Some(pattern) = &args.match_description {
+        let is_abandoned = |op: &Operation| {
+            op.parent_ids().len() == 1 && pattern.matches(&op.metadata().description)
         };
-
-    if let Some(op) = abandon_head_ops
-        .iter()
-        .find(|op| current_head_ops.contains(op))
-    {
-        let mut err = user_error(format!(
-            "Cannot abandon the current operation {}",
-            short_operation_hash(op.id())
-        ));
-        if current_head_ops.len() == 1 {
-            err.add_hint("Run `jj undo` to revert the current operation, then use `jj op abandon`");
+        // The current operations can't be abandoned, and merge operations
+        // can't be reparented.
+        let skipped_count = op_walk::walk_ancestors(&current_head_ops)
+            .filter_ok(|op| {
+                pattern.matches(&op.metadata().description)
+                    && (current_head_ops.contains(op) || op.parent_ids().len() > 1)
+            })
+            .process_results(|iter| iter.count())?;
+        if skipped_count > 0 {
+            writeln!(
+                ui.warning_default(),
+                "Skipped {skipped_count} matching operations which are current or merge \
+                 operations."
+            )?;
         }
-        return Err(err);
-    }
-
-    // Reparent descendants, count the number of abandoned operations.
-    let stats = op_walk::reparent_range(
-        op_store.as_ref(),
-        &abandon_head_ops,
-        &current_head_ops,
-        &abandon_root_op,
-    )?;
+        op_walk::remove_matching(op_store.as_ref(), &current_head_ops, is_abandoned)?
+    } else {
+        let operation = args.operation.as_ref().unwrap();
+        reparent_operations(repo_loader, &current_head_ops, operation)?
+    };
     assert_eq!(
         current_head_ops.len(),
         stats.new_head_ids.len(),
@@ -125,6 +145,23 @@ pub fn cmd_op_abandon(
         stats.unreachable_count,
         stats.rewritten_count,
     )?;
+    let new_unreachable_stats = if old_unreachable_stats.is_some() {
+        unreachable_object_stats(ui, op_store.as_ref(), &stats.new_head_ids)?
+    } else {
+        None
+    };
+    if let Some((old_stats, new_stats)) = old_unreachable_stats.zip(new_unreachable_stats) {
+        let freed_bytes = new_stats.total_bytes.saturating_sub(old_stats.total_bytes);
+        writeln!(
+            ui.status(),
+            "Unreferenced {} operations and {} views ({}), which can be freed by `jj util gc`.",
+            new_stats
+                .operation_count
+                .saturating_sub(old_stats.operation_count),
+            new_stats.view_count.saturating_sub(old_stats.view_count),
+            HumanByteSize(freed_bytes),
+        )?;
+    }
     for (old, new_id) in reparented_head_ops().filter(|&(old, new_id)| old.id() != new_id) {
         op_heads_store.update_op_heads(slice::from_ref(old.id()), new_id)?;
     }
@@ -149,3 +186,77 @@ pub fn cmd_op_abandon(
     }
     Ok(())
 }
+
+/// Counts unreachable operation objects, or prints a warning if the operation
+/// store can't count them.
+fn unreachable_object_stats(
+    ui: &Ui,
+    op_store: &dyn OpStore,
+    head_ids: &[OperationId],
+) -> Result<Option<OpStoreObjectStats>, CommandError> {
+    match op_store.unreachable_object_stats(head_ids) {
+        Ok(stats) => Ok(Some(stats)),
+        Err(err) => {
+            writeln!(
+                ui.warning_default(),
+                "Cannot report freed operation objects: {err}"
+            )?;
+            Ok(None)
+        }
+    }
+}
+
+fn reparent_operations(
+    repo_loader: &RepoLoader,
+    current_head_ops: &[Operation],
+    operation: &str,
+) -> Result<ReparentStats, CommandError> {
+    let op_store = repo_loader.op_store();
+    let resolve_op = |op_str| op_walk::resolve_op_at(op_store, current_head_ops, op_str);
+    let (abandon_root_op, abandon_head_ops) =
+        if let Some((root_op_str, head_op_str)) = operation.split_once("..") {
+            let root_op = if root_op_str.is_empty() {
+                repo_loader.root_operation()
+            } else {
+                resolve_op(root_op_str)?
+            };
+            let head_ops = if head_op_str.is_empty() {
+                current_head_ops.to_vec()
+            } else {
+                vec![resolve_op(head_op_str)?]
+            };
+            (root_op, head_ops)
+        } else {
+            let op = resolve_op(operation)?;
+            let parent_ops: Vec<_> = op.parents().try_collect()?;
+            let parent_op = match parent_ops.len() {
+                0 => return Err(user_error("Cannot abandon the root operation")),
+                1 => parent_ops.into_iter().next().unwrap(),
+                _ => return Err(user_error("Cannot abandon a merge operation")),
+            };
+            (parent_op, vec![op])
+        };
+
+    if let Some(op) = abandon_head_ops
+        .iter()
+        .find(|op| current_head_ops.contains(op))
+    {
+        let mut err = user_error(format!(
+            "Cannot abandon the current operation {}",
+            short_operation_hash(op.id())
+        ));
+        if current_head_ops.len() == 1 {
+            err.add_hint("Run `jj undo` to revert the current operation, then use `jj op abandon`");
+        }
+        return Err(err);
+    }
+
+    // Reparent descendants, count the number of abandoned operations.
+    let stats = op_walk::reparent_range(
+        op_store.as_ref(),
+        &abandon_head_ops,
+        current_head_ops,
+        &abandon_root_op,
+    )?;
+    Ok(stats)
+}
@@ -18,34 +18,49 @@ use std::slice;
 use std::sync::Arc;
 
 use clap_complete::ArgValueCandidates;
+use futures::StreamExt as _;
+use itertools::EitherOrBoth;
 use itertools::Itertools as _;
-use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::copies::CopyRecords;
 use jj_lib::evolution::accumulate_predecessors;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::op_store::RemoteRefState;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
 use jj_lib::refs::diff_named_commit_ids;
 use jj_lib::refs::diff_named_ref_targets;
 use jj_lib::refs::diff_named_remote_refs;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::rewrite::rebase_to_dest_parent;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::diff_formats_for_log;
+use crate::diff_util::set_max_patch_bytes;
+use crate::diff_util::show_diff_stats_summary;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
+use crate::diff_util::DiffStatOptions;
+use crate::diff_util::DiffStats;
 use crate::formatter::Formatter;
 use crate::graphlog::get_graphlog;
 use crate::graphlog::GraphStyle;
+use crate::operation_templater::diff_operation_commit_ids;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
@@ -73,6 +88,19 @@ pub struct OperationDiffArgs {
         add = ArgValueCandidates::new(complete::operations),
     )]
     to: Option<String>,
+    /// Show repository changes in the first operation, compared to the
+    /// closest common ancestor of the two operations
+    ///
+    /// This shows what one side of concurrent operations contributed relative
+    /// to the operation both sides were based on.
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OPERATION", "OTHER"],
+        conflicts_with_all = ["operation", "from", "to"],
+        add = ArgValueCandidates::new(complete::operations),
+    )]
+    merge_base: Vec<String>,
     /// Don't show the graph, show a flat list of modified changes
     #[arg(long)]
     no_graph: bool,
@@ -83,6 +111,24 @@ pub struct OperationDiffArgs {
     /// contaminated by unrelated changes.
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Truncate the patch of each file beyond the given number of bytes
+    ///
+    /// Overrides the `ui.op-diff.max-patch-bytes` setting.
+    #[arg(long, value_name = "BYTES")]
+    max_patch_bytes: Option<usize>,
+    /// Omit changes made only by working-copy snapshot operations
+    ///
+    /// Commits which were rewritten only by snapshot operations between the
+    /// two operations (along with their working-copy updates) are not shown.
+    /// Changes made by other operations are still shown in full.
+    #[arg(long)]
+    skip_snapshots: bool,
+    /// Show renamed local bookmarks as a deletion and an addition
+    ///
+    /// By default, a local bookmark deleted and another one added at the same
+    /// target are shown as a rename.
+    #[arg(long)]
+    no_rename_detection: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -98,7 +144,14 @@ pub fn cmd_op_diff(
     let settings = workspace_command.settings();
     let from_ops;
     let to_op;
-    if args.from.is_some() || args.to.is_some() {
+    if let [op_str, other_op_str] = args.merge_base.as_slice() {
+        to_op = workspace_command.resolve_single_op(op_str)?;
+        let other_op = workspace_command.resolve_single_op(other_op_str)?;
+        let base_op =
+            op_walk::closest_common_ancestor(slice::from_ref(&to_op), slice::from_ref(&other_op))?
+                .expect("operations should share the root operation");
+        from_ops = vec![base_op];
+    } else if args.from.is_some() || args.to.is_some() {
         from_ops = vec![workspace_command.resolve_single_op(args.from.as_deref().unwrap_or("@"))?];
         to_op = workspace_command.resolve_single_op(args.to.as_deref().unwrap_or("@"))?;
     } else {
@@ -120,11 +173,20 @@ pub fn cmd_op_diff(
     let merged_repo = tx.repo();
 
     let diff_renderer = {
-        let formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
+        let mut formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
+        let max_patch_bytes = match args.max_patch_bytes {
+            Some(max_bytes) => Some(max_bytes),
+            None => settings.get("ui.op-diff.max-patch-bytes").optional()?,
+        };
+        if let Some(max_bytes) = max_patch_bytes {
+            set_max_patch_bytes(&mut formats, max_bytes);
+        }
         let path_converter = workspace_env.path_converter();
         let conflict_marker_style = workspace_env.conflict_marker_style();
-        (!formats.is_empty())
-            .then(|| DiffRenderer::new(merged_repo, path_converter, conflict_marker_style, formats))
+        (!formats.is_empty()).then(|| {
+            DiffRenderer::new(merged_repo, path_converter, conflict_marker_style, formats)
+                .with_change_types(args.diff_format.diff_type.clone())
+        })
     };
     let id_prefix_context = workspace_env.new_id_prefix_context();
     let commit_summary_template = {
@@ -160,9 +222,39 @@ pub fn cmd_op_diff(
         (!args.no_graph).then_some(graph_style),
         &with_content_format,
         diff_renderer.as_ref(),
+        &OpDiffOptions {
+            skip_snapshots: args.skip_snapshots,
+            detect_renames: !args.no_rename_detection,
+            ..Default::default()
+        },
     )
 }
 
+/// Options to filter and annotate the differences shown by [`show_op_diff()`].
+#[derive(Clone, Copy, Debug)]
+pub struct OpDiffOptions<'a> {
+    /// Patches are restricted to the paths matched by this matcher.
+    pub matcher: &'a dyn Matcher,
+    /// Omit changed commits which don't touch any of the matched paths.
+    pub only_matching: bool,
+    /// Omit commits rewritten only by snapshot operations.
+    pub skip_snapshots: bool,
+    /// Show local bookmarks which were deleted and re-added at the same target
+    /// as renames.
+    pub detect_renames: bool,
+}
+
+impl Default for OpDiffOptions<'_> {
+    fn default() -> Self {
+        Self {
+            matcher: &EverythingMatcher,
+            only_matching: false,
+            skip_snapshots: false,
+            detect_renames: true,
+        }
+    }
+}
+
 /// Computes and shows the differences between two operations, using the given
 /// `ReadonlyRepo`s for the operations.
 /// `current_repo` should contain a `Repo` with the indices of both repos merged
@@ -178,8 +270,30 @@ pub fn show_op_diff(
     graph_style: Option<GraphStyle>,
     with_content_format: &LogContentFormat,
     diff_renderer: Option<&DiffRenderer>,
+    options: &OpDiffOptions,
 ) -> Result<(), CommandError> {
-    let changes = compute_operation_commits_diff(current_repo, from_repo, to_repo)?;
+    let OpDiffOptions {
+        matcher,
+        only_matching,
+        skip_snapshots,
+        detect_renames,
+    } = *options;
+    let snapshot_commits = if skip_snapshots {
+        collect_snapshot_only_commits(from_repo.operation(), to_repo.operation())?
+    } else {
+        HashSet::new()
+    };
+    let mut changes = compute_operation_commits_diff(current_repo, from_repo, to_repo)?;
+    changes.retain(|commit_id, _| !snapshot_commits.contains(commit_id));
+    if only_matching {
+        let mut matching_changes = HashMap::new();
+        for (commit_id, change) in changes {
+            if change_touches_paths(current_repo, &change, matcher)? {
+                matching_changes.insert(commit_id, change);
+            }
+        }
+        changes = matching_changes;
+    }
     if !changes.is_empty() {
         let revset =
             RevsetExpression::commits(changes.keys().cloned().collect()).evaluate(current_repo)?;
@@ -216,6 +330,7 @@ pub fn show_op_diff(
                         formatter.as_mut(),
                         diff_renderer,
                         modified_change,
+                        matcher,
                         within_graph.width(),
                     )?;
                 }
@@ -242,7 +357,14 @@ pub fn show_op_diff(
                 })?;
                 if let Some(diff_renderer) = &diff_renderer {
                     let width = with_content_format.width();
-                    show_change_diff(ui, formatter, diff_renderer, modified_change, width)?;
+                    show_change_diff(
+                        ui,
+                        formatter,
+                        diff_renderer,
+                        modified_change,
+                        matcher,
+                        width,
+                    )?;
                 }
             }
         }
@@ -252,6 +374,7 @@ pub fn show_op_diff(
         from_repo.view().wc_commit_ids(),
         to_repo.view().wc_commit_ids(),
     )
+    .filter(|(_, (_, to_commit))| !to_commit.is_some_and(|id| snapshot_commits.contains(id)))
     .collect_vec();
     if !changed_working_copies.is_empty() {
         writeln!(formatter)?;
@@ -282,11 +405,37 @@ pub fn show_op_diff(
         }
     }
 
-    let changed_local_bookmarks = diff_named_ref_targets(
+    let mut changed_local_bookmarks = diff_named_ref_targets(
         from_repo.view().local_bookmarks(),
         to_repo.view().local_bookmarks(),
     )
     .collect_vec();
+    let renamed_local_bookmarks = if detect_renames {
+        take_renamed_bookmarks(&mut changed_local_bookmarks)
+    } else {
+        vec![]
+    };
+    if !renamed_local_bookmarks.is_empty() {
+        writeln!(formatter)?;
+        with_content_format.write(formatter, |formatter| {
+            writeln!(formatter, "Renamed local bookmarks:")
+        })?;
+        for (old_name, new_name, commit_id) in renamed_local_bookmarks {
+            with_content_format.write(formatter, |formatter| {
+                writeln!(
+                    formatter,
+                    "{old_name} -> {new_name}:",
+                    old_name = old_name.as_symbol(),
+                    new_name = new_name.as_symbol()
+                )?;
+                write!(formatter, "  ")?;
+                let commit = current_repo.store().get_commit(commit_id)?;
+                commit_summary_template.format(&commit, formatter)?;
+                writeln!(formatter)?;
+                Ok::<_, CommandError>(())
+            })?;
+        }
+    }
     if !changed_local_bookmarks.is_empty() {
         writeln!(formatter)?;
         with_content_format.write(formatter, |formatter| {
@@ -384,9 +533,115 @@ pub fn show_op_diff(
         }
     }
 
+    let changed_pending_bookmarks = from_repo
+        .view()
+        .pending_remote_bookmarks()
+        .merge_join_by(to_repo.view().pending_remote_bookmarks(), |a, b| a.cmp(b))
+        .filter(|entry| !entry.is_both())
+        .collect_vec();
+    if !changed_pending_bookmarks.is_empty() {
+        writeln!(formatter)?;
+        with_content_format.write(formatter, |formatter| {
+            writeln!(formatter, "Changed pending remote bookmarks:")
+        })?;
+        for entry in changed_pending_bookmarks {
+            let (symbol, label, sign) = match entry {
+                EitherOrBoth::Left(symbol) => (symbol, "removed", "-"),
+                EitherOrBoth::Right(symbol) => (symbol, "added", "+"),
+                EitherOrBoth::Both(..) => unreachable!(),
+            };
+            with_content_format.write(formatter, |formatter| {
+                formatter.with_label("diff", |formatter| {
+                    write!(formatter.labeled(label), "{sign}")
+                })?;
+                writeln!(formatter, " {symbol}")
+            })?;
+        }
+    }
+
     Ok(())
 }
 
+/// Computes and shows a single diffstat summary of all commits changed between
+/// two operations.
+///
+/// Each changed commit contributes its diff as it would be displayed by
+/// [`show_change_diff()`]. Files are counted once even if they were changed in
+/// multiple commits.
+pub fn show_op_diff_stat(
+    formatter: &mut dyn Formatter,
+    current_repo: &dyn Repo,
+    from_repo: &Arc<ReadonlyRepo>,
+    to_repo: &Arc<ReadonlyRepo>,
+    matcher: &dyn Matcher,
+    conflict_marker_style: ConflictMarkerStyle,
+) -> Result<(), CommandError> {
+    let changes = compute_operation_commits_diff(current_repo, from_repo, to_repo)?;
+    let store = current_repo.store();
+    let options = DiffStatOptions::default();
+    let copy_records = CopyRecords::default();
+    let mut paths = HashSet::new();
+    let mut total_added = 0;
+    let mut total_removed = 0;
+    for change in changes.values() {
+        let (from_tree, to_tree) = change_trees(current_repo, change)?;
+        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, matcher, &copy_records);
+        let stats =
+            DiffStats::calculate(store, tree_diff, &options, conflict_marker_style).block_on()?;
+        paths.extend(
+            stats
+                .entries()
+                .iter()
+                .map(|stat| stat.path.target().to_owned()),
+        );
+        total_added += stats.count_total_added();
+        total_removed += stats.count_total_removed();
+    }
+    show_diff_stats_summary(formatter, paths.len(), total_added, total_removed)?;
+    Ok(())
+}
+
+/// Removes pairs of deleted and added bookmarks pointing to the same commit
+/// from `changed_bookmarks`, and returns them as `(old_name, new_name,
+/// commit_id)`.
+///
+/// Bookmarks are paired only if exactly one bookmark was deleted from and
+/// exactly one bookmark was added to the commit.
+fn take_renamed_bookmarks<'a, K: Copy + Ord>(
+    changed_bookmarks: &mut Vec<(K, (&RefTarget, &'a RefTarget))>,
+) -> Vec<(K, K, &'a CommitId)> {
+    let mut deleted_by_target: HashMap<&CommitId, Vec<K>> = HashMap::new();
+    let mut added_by_target: HashMap<&CommitId, Vec<K>> = HashMap::new();
+    for (name, (from_target, to_target)) in changed_bookmarks.iter() {
+        if let (Some(id), true) = (from_target.as_normal(), to_target.is_absent()) {
+            deleted_by_target.entry(id).or_default().push(*name);
+        } else if let (true, Some(id)) = (from_target.is_absent(), to_target.as_normal()) {
+            added_by_target.entry(id).or_default().push(*name);
+        }
+    }
+    let mut renamed = vec![];
+    for (name, (_, to_target)) in changed_bookmarks.iter() {
+        let Some(id) = to_target.as_normal() else {
+            continue;
+        };
+        if let (Some([old_name]), Some([new_name])) = (
+            deleted_by_target.get(id).map(Vec::as_slice),
+            added_by_target.get(id).map(Vec::as_slice),
+        ) {
+            if new_name == name {
+                renamed.push((*old_name, *new_name, id));
+            }
+        }
+    }
+    changed_bookmarks.retain(|(name, _)| {
+        !renamed
+            .iter()
+            .any(|(old_name, new_name, _)| name == old_name || name == new_name)
+    });
+    renamed.sort_by_key(|&(old_name, _, _)| old_name);
+    renamed
+}
+
 /// Writes a summary for the given `ModifiedChange`.
 fn write_modified_change_summary(
     formatter: &mut dyn Formatter,
@@ -407,6 +662,12 @@ fn write_modified_change_summary(
         commit_summary_template.format(commit, formatter)?;
         writeln!(formatter)?;
     }
+    if modified_change.is_description_only() {
+        writeln!(
+            formatter.labeled("description_changed"),
+            "  (description changed)"
+        )?;
+    }
     Ok(())
 }
 
@@ -489,6 +750,24 @@ impl ModifiedChange {
             Self::Abandoned { .. } => &[],
         }
     }
+
+    /// Returns true if a commit was rewritten only to change its description.
+    fn is_description_only(&self) -> bool {
+        match self {
+            Self::Existing {
+                commit,
+                predecessors,
+            } => match predecessors.as_slice() {
+                [predecessor] => {
+                    predecessor.parent_ids() == commit.parent_ids()
+                        && predecessor.tree_id() == commit.tree_id()
+                        && predecessor.description() != commit.description()
+                }
+                _ => false,
+            },
+            Self::Abandoned { .. } => false,
+        }
+    }
 }
 
 /// Computes created/rewritten/abandoned commits between two operations.
@@ -504,43 +783,14 @@ fn compute_operation_commits_diff(
     let store = repo.store();
     let from_heads = from_repo.view().heads().iter().cloned().collect_vec();
     let to_heads = to_repo.view().heads().iter().cloned().collect_vec();
-    let from_expr = RevsetExpression::commits(from_heads);
-    let to_expr = RevsetExpression::commits(to_heads);
-
     let predecessor_commits = accumulate_predecessors(
         slice::from_ref(to_repo.operation()),
         slice::from_ref(from_repo.operation()),
     )?;
+    let diff = diff_operation_commit_ids(repo, from_heads, to_heads, &predecessor_commits)?;
 
-    // Collect hidden commits to find abandoned/rewritten changes.
-    let mut hidden_commits_by_change: HashMap<ChangeId, CommitId> = HashMap::new();
-    let mut abandoned_commits: HashSet<CommitId> = HashSet::new();
-    let newly_hidden = to_expr.range(&from_expr).evaluate(repo)?;
-    for item in newly_hidden.commit_change_ids() {
-        let (commit_id, change_id) = item?;
-        // Just pick one if diverged. Divergent commits shouldn't be considered
-        // "squashed" into the new commit.
-        hidden_commits_by_change
-            .entry(change_id)
-            .or_insert_with(|| commit_id.clone());
-        abandoned_commits.insert(commit_id);
-    }
-
-    // For each new commit, copy/deduce predecessors based on change id.
     let mut changes: HashMap<CommitId, ModifiedChange> = HashMap::new();
-    let newly_visible = from_expr.range(&to_expr).evaluate(repo)?;
-    for item in newly_visible.commit_change_ids() {
-        let (commit_id, change_id) = item?;
-        let predecessor_ids = if let Some(ids) = predecessor_commits.get(&commit_id) {
-            ids // including visible predecessors
-        } else if let Some(id) = hidden_commits_by_change.get(&change_id) {
-            slice::from_ref(id)
-        } else {
-            &[]
-        };
-        for id in predecessor_ids {
-            abandoned_commits.remove(id);
-        }
+    for (commit_id, predecessor_ids) in diff.existing {
         let change = ModifiedChange::Existing {
             commit: store.get_commit(&commit_id)?,
             predecessors: predecessor_ids
@@ -550,9 +800,7 @@ fn compute_operation_commits_diff(
         };
         changes.insert(commit_id, change);
     }
-
-    // Record remainders as abandoned.
-    for commit_id in abandoned_commits {
+    for commit_id in diff.abandoned {
         let change = ModifiedChange::Abandoned {
             commit: store.get_commit(&commit_id)?,
         };
@@ -562,37 +810,113 @@ fn compute_operation_commits_diff(
     Ok(changes)
 }
 
+/// Collects commits which were rewritten only by snapshot operations between
+/// `from_op` and `to_op`.
+///
+/// A commit is included if it was created by a snapshot operation, and all of
+/// its predecessors created in the range were also created by snapshot
+/// operations.
+fn collect_snapshot_only_commits(
+    from_op: &Operation,
+    to_op: &Operation,
+) -> Result<HashSet<CommitId>, CommandError> {
+    let ops: Vec<Operation> =
+        op_walk::walk_ancestors_range(slice::from_ref(to_op), slice::from_ref(from_op))
+            .try_collect()?;
+    let mut created_commits: HashSet<CommitId> = HashSet::new();
+    let mut snapshot_commits: HashSet<CommitId> = HashSet::new();
+    // Visit operations from oldest to newest so predecessors are classified
+    // first.
+    for op in ops.iter().rev() {
+        let Some(commit_predecessors) = &op.store_operation().commit_predecessors else {
+            // Predecessors aren't recorded, so nothing can be classified.
+            return Ok(HashSet::new());
+        };
+        let is_snapshot = op.metadata().is_snapshot;
+        for (commit_id, predecessor_ids) in commit_predecessors {
+            let snapshot_only = is_snapshot
+                && !predecessor_ids.is_empty()
+                && predecessor_ids
+                    .iter()
+                    .all(|id| !created_commits.contains(id) || snapshot_commits.contains(id));
+            if snapshot_only {
+                snapshot_commits.insert(commit_id.clone());
+            }
+        }
+        created_commits.extend(commit_predecessors.keys().cloned());
+    }
+    Ok(snapshot_commits)
+}
+
 /// Displays the diffs of a modified change.
 ///
 /// For created/rewritten commits, the diff is shown between the old (or
 /// predecessor) commits and the new commit. The old commits are temporarily
-/// rebased onto the new commit's parents. For abandoned commits, the diff is
-/// shown of that commit's contents.
+/// rebased onto the new commit's parents. If only the description was
+/// changed, the diff of the descriptions is shown instead. For abandoned
+/// commits, the diff is shown of that commit's contents.
 fn show_change_diff(
     ui: &Ui,
     formatter: &mut dyn Formatter,
     diff_renderer: &DiffRenderer,
     change: &ModifiedChange,
+    matcher: &dyn Matcher,
     width: usize,
 ) -> Result<(), CommandError> {
     match change {
         ModifiedChange::Existing {
             commit,
             predecessors,
-        } => {
-            diff_renderer.show_inter_diff(
-                ui,
+        } if change.is_description_only() => {
+            // The trees are the same, so show the descriptions instead.
+            diff_renderer.show_description_diff(
                 formatter,
-                predecessors,
-                commit,
-                &EverythingMatcher,
-                width,
+                predecessors[0].description(),
+                commit.description(),
             )?;
         }
+        ModifiedChange::Existing {
+            commit,
+            predecessors,
+        } => {
+            diff_renderer.show_inter_diff(ui, formatter, predecessors, commit, matcher, width)?;
+        }
         ModifiedChange::Abandoned { commit } => {
             // TODO: Should we show a reverse diff?
-            diff_renderer.show_patch(ui, formatter, commit, &EverythingMatcher, width)?;
+            diff_renderer.show_patch(ui, formatter, commit, matcher, width)?;
         }
     }
     Ok(())
 }
+
+/// Returns true if the diff of the modified change (as it would be displayed
+/// by [`show_change_diff()`]) contains any of the paths matched by the
+/// `matcher`.
+fn change_touches_paths(
+    repo: &dyn Repo,
+    change: &ModifiedChange,
+    matcher: &dyn Matcher,
+) -> Result<bool, CommandError> {
+    let (from_tree, to_tree) = change_trees(repo, change)?;
+    let mut tree_diff = from_tree.diff_stream(&to_tree, matcher);
+    Ok(tree_diff.next().block_on().is_some())
+}
+
+/// Returns the trees to compare for the modified change, as it would be
+/// displayed by [`show_change_diff()`].
+fn change_trees(
+    repo: &dyn Repo,
+    change: &ModifiedChange,
+) -> Result<(MergedTree, MergedTree), CommandError> {
+    let trees = match change {
+        ModifiedChange::Existing {
+            commit,
+            predecessors,
+        } => (
+            rebase_to_dest_parent(repo, predecessors, commit)?,
+            commit.tree()?,
+        ),
+        ModifiedChange::Abandoned { commit } => (commit.parent_tree(repo)?, commit.tree()?),
+    };
+    Ok(trees)
+}
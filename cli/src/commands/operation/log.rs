@@ -12,18 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
+use jj_lib::graph::GraphEdgeType;
 use jj_lib::op_store::OpStoreError;
+use jj_lib::op_store::OpStoreResult;
+use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 use jj_lib::repo::RepoLoader;
+use jj_lib::str_util::StringPattern;
 
 use super::diff::show_op_diff;
+use super::diff::show_op_diff_stat;
+use super::diff::OpDiffOptions;
 use crate::cli_util::format_template;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
@@ -36,6 +45,7 @@ use crate::diff_util::DiffRenderer;
 use crate::formatter::Formatter;
 use crate::graphlog::get_graphlog;
 use crate::graphlog::GraphStyle;
+use crate::operation_templater::changed_bookmark_names;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
@@ -56,9 +66,92 @@ pub struct OperationLogArgs {
     /// Show operations in the opposite order (older operations first)
     #[arg(long)]
     reversed: bool,
+    /// Show only the given operation and its ancestors
+    ///
+    /// Unlike `--at-op`, this doesn't change the operation the repo is loaded
+    /// at.
+    #[arg(
+        long,
+        value_name = "OPERATION",
+        add = ArgValueCandidates::new(complete::operations),
+    )]
+    ancestors_of: Option<String>,
+    /// Show only operations newer than the given operation
+    ///
+    /// Only descendants of the operation are shown, excluding the operation
+    /// itself. Concurrent operations which aren't descendants of it are
+    /// omitted.
+    #[arg(
+        long,
+        value_name = "OPERATION",
+        add = ArgValueCandidates::new(complete::operations),
+    )]
+    after: Option<String>,
+    /// Show only operations older than the given operation
+    ///
+    /// Only ancestors of the operation are shown, excluding the operation
+    /// itself.
+    #[arg(
+        long,
+        value_name = "OPERATION",
+        conflicts_with = "ancestors_of",
+        add = ArgValueCandidates::new(complete::operations),
+    )]
+    before: Option<String>,
     /// Don't show the graph, show a flat list of operations
     #[arg(long)]
     no_graph: bool,
+    /// Style of the graph connectors and node symbols
+    ///
+    /// Overrides the `ui.op-log.graph.style` and `ui.graph.style` settings.
+    #[arg(long, value_name = "STYLE", value_enum, conflicts_with = "no_graph")]
+    graph_style: Option<GraphStyle>,
+    /// Insert a blank line between operations in the flat list
+    #[arg(long, requires = "no_graph")]
+    separator: bool,
+    /// Show only operations that merge concurrent operations
+    ///
+    /// In the graph, the other operations are elided.
+    #[arg(long)]
+    merges_only: bool,
+    /// Hide operations that merge concurrent operations
+    #[arg(long, conflicts_with = "merges_only")]
+    no_merges: bool,
+    /// Show only operations that changed bookmarks matching the given patterns
+    ///
+    /// An operation matches if a local or remote bookmark differs from the
+    /// parent operation. Changes merged from concurrent operations are
+    /// attributed to those operations. In the graph, the other operations are
+    /// elided.
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select bookmarks by [wildcard pattern].
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::bookmarks),
+    )]
+    touching_bookmark: Vec<StringPattern>,
+    /// Show only operations whose description or command-line arguments
+    /// match the given pattern
+    ///
+    /// The pattern is matched as a substring by default. Use `regex:` for a
+    /// regular expression, or other [string pattern] prefixes. In the graph,
+    /// the other operations are elided.
+    ///
+    /// [string pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(long, value_name = "PATTERN", value_parser = StringPattern::parse_default_substring)]
+    grep: Option<StringPattern>,
+    /// Don't show the command-line arguments recorded in operations
+    ///
+    /// This hides the `args` tag from `tags()` in the operation template.
+    #[arg(long)]
+    no_args: bool,
     /// Render each operation using the given template
     ///
     /// You can specify arbitrary template expressions using the
@@ -72,6 +165,42 @@ pub struct OperationLogArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    /// Render each operation using the template loaded from the given file
+    ///
+    /// Template aliases defined in the config can be used in the file.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "template",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    template_file: Option<String>,
+    /// Show timestamps in ISO 8601 format in the default template
+    ///
+    /// This overrides the `format_timestamp()` and `format_time_range()`
+    /// template aliases, so the output doesn't depend on the configured time
+    /// format. Templates specified by `-T` or `--template-file` are
+    /// unaffected.
+    #[arg(long)]
+    iso_time: bool,
+    /// Show the given fields of each operation separated by tabs
+    ///
+    /// This is a shorthand for a template which lists the fields. Multi-line
+    /// descriptions are flattened to their first line.
+    #[arg(
+        long,
+        value_name = "FIELDS",
+        value_enum,
+        value_delimiter = ',',
+        requires = "no_graph",
+        conflicts_with_all = ["template", "template_file", "separator"],
+    )]
+    fields: Vec<OperationLogField>,
+    /// Terminate each operation listed by `--fields` with a NUL character
+    ///
+    /// Descriptions are shown in full.
+    #[arg(long, requires = "fields")]
+    null: bool,
     /// Show changes to the repository at each operation
     #[arg(long, short = 'd')]
     op_diff: bool,
@@ -82,6 +211,21 @@ pub struct OperationLogArgs {
     /// contaminated by unrelated changes.
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Show a single diffstat summary of all changes at each operation
+    ///
+    /// Unlike `--stat`, which shows a diffstat per changed commit, this shows
+    /// the total number of files, insertions, and deletions.
+    #[arg(long, conflicts_with_all = ["op_diff", "patch", "short-format", "long-format", "tool"])]
+    op_stat: bool,
+    /// Show patches of changes to these paths only (implies --op-diff)
+    ///
+    /// Changed commits are still listed even if they don't touch any of the
+    /// given paths, unless `--only-matching` is set.
+    #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    paths: Vec<String>,
+    /// Omit changed commits which don't touch any of the given paths
+    #[arg(long, requires = "paths")]
+    only_matching: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -116,101 +260,214 @@ fn do_op_log(
     args: &OperationLogArgs,
 ) -> Result<(), CommandError> {
     let settings = repo_loader.settings();
-    let graph_style = GraphStyle::from_settings(settings)?;
+    // The op-log specific style also selects the builtin node symbols, as the
+    // default node template only looks at `ui.graph.style`. The symbols can be
+    // customized by overriding the builtin_op_log_node[_ascii] aliases.
+    let graph_style_override = match args.graph_style {
+        Some(style) => Some(style),
+        None => settings.get("ui.op-log.graph.style").optional()?,
+    };
+    let graph_style = match graph_style_override {
+        Some(style) => style,
+        None => GraphStyle::from_settings(settings)?,
+    };
     let with_content_format = LogContentFormat::new(ui, settings)?;
 
     let template: TemplateRenderer<Operation>;
     let op_node_template: TemplateRenderer<Operation>;
     {
-        let language = OperationTemplateLanguage::new(
+        let mut language = OperationTemplateLanguage::new(
             repo_loader,
             Some(current_op.id()),
             workspace_env.operation_template_extensions(),
         );
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => settings.get_string("templates.op_log")?,
+        if args.no_args {
+            language.hide_tag("args");
+        }
+        template = if let Some(path) = &args.template_file {
+            workspace_env.parse_template_file(ui, &language, path)?
+        } else if !args.fields.is_empty() {
+            let text = fields_template_text(&args.fields, args.null);
+            workspace_env.parse_template(ui, &language, &text)?
+        } else if let Some(value) = &args.template {
+            workspace_env.parse_template(ui, &language, value)?
+        } else {
+            let text = settings.get_string("templates.op_log")?;
+            if args.iso_time {
+                workspace_env.parse_template_with_iso_time(ui, &language, &text)?
+            } else {
+                workspace_env.parse_template(ui, &language, &text)?
+            }
+        }
+        .labeled(["op_log", "operation"]);
+        let node_text = match graph_style_override {
+            Some(style) if style.is_ascii() => "builtin_op_log_node_ascii".to_owned(),
+            Some(_) => "builtin_op_log_node".to_owned(),
+            None => settings.get_string("templates.op_log_node")?,
         };
-        template = workspace_env
-            .parse_template(ui, &language, &text)?
-            .labeled(["op_log", "operation"]);
         op_node_template = workspace_env
-            .parse_template(
-                ui,
-                &language,
-                &settings.get_string("templates.op_log_node")?,
-            )?
+            .parse_template(ui, &language, &node_text)?
             .labeled(["op_log", "operation", "node"]);
     }
 
     let diff_formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
-    let maybe_show_op_diff = if args.op_diff || !diff_formats.is_empty() {
-        let template_text = settings.get_string("templates.commit_summary")?;
-        let show = move |ui: &Ui,
-                         formatter: &mut dyn Formatter,
-                         op: &Operation,
-                         with_content_format: &LogContentFormat| {
-            let parent_ops: Vec<_> = op.parents().try_collect()?;
-            let merged_parent_op = repo_loader.merge_operations(parent_ops.clone(), None)?;
-            let parent_repo = repo_loader.load_at(&merged_parent_op)?;
-            let repo = repo_loader.load_at(op)?;
+    let maybe_show_op_diff =
+        if args.op_diff || args.op_stat || !diff_formats.is_empty() || !args.paths.is_empty() {
+            let template_text = settings.get_string("templates.commit_summary")?;
+            let matcher = workspace_env
+                .parse_file_patterns(ui, &args.paths)?
+                .to_matcher();
+            let show = move |ui: &Ui,
+                             formatter: &mut dyn Formatter,
+                             op: &Operation,
+                             with_content_format: &LogContentFormat| {
+                let parent_ops: Vec<_> = op.parents().try_collect()?;
+                let merged_parent_op = repo_loader.merge_operations(parent_ops.clone(), None)?;
+                let parent_repo = repo_loader.load_at(&merged_parent_op)?;
+                let repo = repo_loader.load_at(op)?;
 
-            let id_prefix_context = workspace_env.new_id_prefix_context();
-            let commit_summary_template = {
-                let language =
-                    workspace_env.commit_template_language(repo.as_ref(), &id_prefix_context);
-                workspace_env
-                    .parse_template(ui, &language, &template_text)?
-                    .labeled(["op_log", "commit"])
-            };
-            let path_converter = workspace_env.path_converter();
-            let conflict_marker_style = workspace_env.conflict_marker_style();
-            let diff_renderer = (!diff_formats.is_empty()).then(|| {
-                DiffRenderer::new(
+                let id_prefix_context = workspace_env.new_id_prefix_context();
+                let commit_summary_template = {
+                    let language =
+                        workspace_env.commit_template_language(repo.as_ref(), &id_prefix_context);
+                    workspace_env
+                        .parse_template(ui, &language, &template_text)?
+                        .labeled(["op_log", "commit"])
+                };
+                let path_converter = workspace_env.path_converter();
+                let conflict_marker_style = workspace_env.conflict_marker_style();
+                let diff_renderer = (!diff_formats.is_empty()).then(|| {
+                    DiffRenderer::new(
+                        repo.as_ref(),
+                        path_converter,
+                        conflict_marker_style,
+                        diff_formats.clone(),
+                    )
+                    .with_change_types(args.diff_format.diff_type.clone())
+                });
+
+                // TODO: Merged repo may have newly rebased commits, which wouldn't
+                // exist in the index. (#4465)
+                if parent_ops.len() > 1 {
+                    return Ok(());
+                }
+                if args.op_stat {
+                    return with_content_format.write(formatter, |formatter| {
+                        show_op_diff_stat(
+                            formatter,
+                            repo.as_ref(),
+                            &parent_repo,
+                            &repo,
+                            matcher.as_ref(),
+                            conflict_marker_style,
+                        )
+                    });
+                }
+                show_op_diff(
+                    ui,
+                    formatter,
                     repo.as_ref(),
-                    path_converter,
-                    conflict_marker_style,
-                    diff_formats.clone(),
+                    &parent_repo,
+                    &repo,
+                    &commit_summary_template,
+                    (!args.no_graph).then_some(graph_style),
+                    with_content_format,
+                    diff_renderer.as_ref(),
+                    &OpDiffOptions {
+                        matcher: matcher.as_ref(),
+                        only_matching: args.only_matching,
+                        ..Default::default()
+                    },
                 )
-            });
-
-            // TODO: Merged repo may have newly rebased commits, which wouldn't
-            // exist in the index. (#4465)
-            if parent_ops.len() > 1 {
-                return Ok(());
-            }
-            show_op_diff(
-                ui,
-                formatter,
-                repo.as_ref(),
-                &parent_repo,
-                &repo,
-                &commit_summary_template,
-                (!args.no_graph).then_some(graph_style),
-                with_content_format,
-                diff_renderer.as_ref(),
-            )
+            };
+            Some(show)
+        } else {
+            None
         };
-        Some(show)
-    } else {
-        None
-    };
 
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    let iter =
-        op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
+    let resolve_op = |op_str: &str| {
+        op_walk::resolve_op_at(repo_loader.op_store(), slice::from_ref(current_op), op_str)
+    };
+    let head_ops: Vec<Operation> = if let Some(op_str) = &args.before {
+        resolve_op(op_str)?.parents().try_collect()?
+    } else if let Some(op_str) = &args.ancestors_of {
+        vec![resolve_op(op_str)?]
+    } else {
+        vec![current_op.clone()]
+    };
+    let after_ids = if let Some(op_str) = &args.after {
+        Some(descendant_op_ids(&head_ops, &resolve_op(op_str)?)?)
+    } else {
+        None
+    };
+    let merges_filter = if args.merges_only {
+        Some(true)
+    } else if args.no_merges {
+        Some(false)
+    } else {
+        None
+    };
+    let is_shown = |op: &Operation| -> OpStoreResult<bool> {
+        if after_ids.as_ref().is_some_and(|ids| !ids.contains(op.id())) {
+            return Ok(false);
+        }
+        if merges_filter.is_some_and(|merges| (op.parent_ids().len() > 1) != merges) {
+            return Ok(false);
+        }
+        if let Some(pattern) = &args.grep {
+            let metadata = op.metadata();
+            let args_tag = metadata.tags.get("args").map_or("", String::as_str);
+            if !pattern.matches(&metadata.description) && !pattern.matches(args_tag) {
+                return Ok(false);
+            }
+        }
+        if args.touching_bookmark.is_empty() {
+            return Ok(true);
+        }
+        let bookmarks = changed_bookmark_names(op)?;
+        Ok(bookmarks.iter().any(|name| {
+            args.touching_bookmark
+                .iter()
+                .any(|pattern| pattern.matches(name.as_str()))
+        }))
+    };
+    let is_filtered = merges_filter.is_some()
+        || args.grep.is_some()
+        || !args.touching_bookmark.is_empty()
+        || after_ids.is_some();
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let iter = op_walk::walk_ancestors(&head_ops);
 
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
         let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-        let iter = iter.map(|op| -> Result<_, OpStoreError> {
-            let op = op?;
-            let ids = op.parent_ids();
-            let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
-            Ok((op, edges))
-        });
+        let iter: Box<dyn Iterator<Item = Result<_, OpStoreError>>> = if is_filtered {
+            let ops: Vec<_> = iter.try_collect()?;
+            let shown_ids: HashSet<OperationId> = ops
+                .iter()
+                .filter_map(|op| match is_shown(op) {
+                    Ok(true) => Some(Ok(op.id().clone())),
+                    Ok(false) => None,
+                    Err(err) => Some(Err(err)),
+                })
+                .try_collect()?;
+            Box::new(
+                elide_op_graph(ops, |op| shown_ids.contains(op.id()))
+                    .into_iter()
+                    .take(limit)
+                    .map(Ok),
+            )
+        } else {
+            Box::new(iter.take(limit).map(|op| {
+                let op = op?;
+                let ids = op.parent_ids();
+                let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
+                Ok((op, edges))
+            }))
+        };
         let iter_nodes: Box<dyn Iterator<Item = _>> = if args.reversed {
             Box::new(reverse_graph(iter, Operation::id)?.into_iter().map(Ok))
         } else {
@@ -239,13 +496,23 @@ fn do_op_log(
             )?;
         }
     } else {
+        let iter = iter
+            .filter_map(|op| match op.and_then(|op| Ok((is_shown(&op)?, op))) {
+                Ok((true, op)) => Some(Ok(op)),
+                Ok((false, _)) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .take(limit);
         let iter: Box<dyn Iterator<Item = _>> = if args.reversed {
             Box::new(iter.collect_vec().into_iter().rev())
         } else {
             Box::new(iter)
         };
-        for op in iter {
+        for (i, op) in iter.enumerate() {
             let op = op?;
+            if args.separator && i > 0 {
+                writeln!(formatter)?;
+            }
             with_content_format.write(formatter, |formatter| template.format(&op, formatter))?;
             if let Some(show) = &maybe_show_op_diff {
                 show(ui, formatter, &op, &with_content_format)?;
@@ -255,3 +522,106 @@ fn do_op_log(
 
     Ok(())
 }
+
+/// Collects the ids of the operations reachable from `head_ops` which are
+/// descendants of the `base_op`, excluding the `base_op` itself.
+fn descendant_op_ids(
+    head_ops: &[Operation],
+    base_op: &Operation,
+) -> OpStoreResult<HashSet<OperationId>> {
+    let ops: Vec<_> =
+        op_walk::walk_ancestors_range(head_ops, slice::from_ref(base_op)).try_collect()?;
+    let mut ids = HashSet::from([base_op.id().clone()]);
+    for op in ops.iter().rev() {
+        if op.parent_ids().iter().any(|id| ids.contains(id)) {
+            ids.insert(op.id().clone());
+        }
+    }
+    ids.remove(base_op.id());
+    Ok(ids)
+}
+
+/// Operation field which can be listed by `--fields`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum OperationLogField {
+    /// The full operation id
+    Id,
+    /// The shortened operation id
+    #[value(name = "id.short")]
+    IdShort,
+    /// The user name and host name
+    User,
+    /// The time the operation started
+    #[value(name = "time.start")]
+    TimeStart,
+    /// The time the operation ended
+    #[value(name = "time.end")]
+    TimeEnd,
+    /// The operation description
+    Description,
+    /// Whether the operation is a snapshot operation
+    Snapshot,
+}
+
+impl OperationLogField {
+    fn template_text(self, null: bool) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::IdShort => "id.short()",
+            Self::User => "user",
+            Self::TimeStart => "time.start()",
+            Self::TimeEnd => "time.end()",
+            Self::Description if null => "description",
+            Self::Description => "description.first_line()",
+            Self::Snapshot => "snapshot",
+        }
+    }
+}
+
+/// Builds a template which lists the `fields` separated by tabs.
+fn fields_template_text(fields: &[OperationLogField], null: bool) -> String {
+    let terminator = if null { r#""\0""# } else { r#""\n""# };
+    let fields = fields.iter().map(|field| field.template_text(null));
+    format!(r#"{} ++ {terminator}"#, fields.format(r#" ++ "\t" ++ "#))
+}
+
+/// Builds graph nodes of the operations matching `is_shown`. Each node is
+/// connected to its nearest shown ancestors, by indirect edges if operations
+/// in between are elided.
+///
+/// The `ops` should be sorted topologically, descendants first.
+fn elide_op_graph(
+    ops: Vec<Operation>,
+    is_shown: impl Fn(&Operation) -> bool,
+) -> Vec<(Operation, Vec<GraphEdge<OperationId>>)> {
+    // Edges to be inherited by the children of each operation
+    let mut edges_via: HashMap<OperationId, Vec<GraphEdge<OperationId>>> = HashMap::new();
+    let mut nodes = vec![];
+    for op in ops.into_iter().rev() {
+        let mut edges: Vec<GraphEdge<OperationId>> = vec![];
+        for parent_id in op.parent_ids() {
+            for edge in edges_via.get(parent_id).into_iter().flatten() {
+                if !edges.contains(edge) {
+                    edges.push(edge.clone());
+                }
+            }
+        }
+        if is_shown(&op) {
+            edges_via.insert(op.id().clone(), vec![GraphEdge::direct(op.id().clone())]);
+            nodes.push((op, edges));
+        } else if op.parent_ids().is_empty() {
+            edges_via.insert(op.id().clone(), vec![GraphEdge::missing(op.id().clone())]);
+        } else {
+            let edges = edges
+                .into_iter()
+                .map(|edge| match edge.edge_type {
+                    GraphEdgeType::Missing => edge,
+                    _ => GraphEdge::indirect(edge.target),
+                })
+                .collect();
+            edges_via.insert(op.id().clone(), edges);
+        }
+    }
+    nodes.reverse();
+    nodes
+}
@@ -24,6 +24,8 @@ use abandon::OperationAbandonArgs;
 use clap::Subcommand;
 use diff::cmd_op_diff;
 use diff::OperationDiffArgs;
+use itertools::Itertools as _;
+use jj_lib::view::View;
 use log::cmd_op_log;
 use log::OperationLogArgs;
 use restore::cmd_op_restore;
@@ -106,3 +108,45 @@ fn view_with_desired_portions_restored(
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
     }
 }
+
+/// Warns about local bookmarks whose tracked remote bookmarks were removed by
+/// restoring only the remote-tracking portion of the view.
+///
+/// Such local bookmarks are left without their remote counterparts, so they
+/// would be pushed as new bookmarks.
+fn warn_about_lost_tracked_remote_bookmarks(
+    ui: &Ui,
+    old_view: &View,
+    new_view: &View,
+    what: &[UndoWhatToRestore],
+) -> Result<(), CommandError> {
+    if what.contains(&UndoWhatToRestore::Repo) {
+        return Ok(());
+    }
+    let lost_symbols = old_view
+        .bookmarks()
+        .filter(|(name, _)| new_view.get_local_bookmark(name).is_present())
+        .flat_map(|(name, targets)| {
+            targets
+                .remote_refs
+                .into_iter()
+                .filter(|(_, remote_ref)| remote_ref.is_tracked() && remote_ref.is_present())
+                .map(move |(remote, _)| name.to_remote_symbol(remote))
+        })
+        .filter(|symbol| new_view.get_remote_bookmark(*symbol).is_absent())
+        .collect_vec();
+    for symbol in &lost_symbols {
+        writeln!(
+            ui.warning_default(),
+            "Local bookmark {name} no longer has tracked remote bookmark {symbol}.",
+            name = symbol.name.as_symbol(),
+        )?;
+    }
+    if !lost_symbols.is_empty() {
+        writeln!(
+            ui.hint_default(),
+            "Run `jj git fetch` to update the remote-tracking bookmarks."
+        )?;
+    }
+    Ok(())
+}
@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use clap_complete::ArgValueCandidates;
+use jj_lib::commit::Commit;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
 
 use super::view_with_desired_portions_restored;
+use super::warn_about_lost_tracked_remote_bookmarks;
 use super::UndoWhatToRestore;
 use super::DEFAULT_UNDO_WHAT;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::ui::Ui;
@@ -42,6 +47,14 @@ pub struct OperationRestoreArgs {
     /// This option is EXPERIMENTAL.
     #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
     what: Vec<UndoWhatToRestore>,
+
+    /// Carry the current working-copy changes over to the restored state
+    ///
+    /// The changes in the current working-copy commit are applied to a new
+    /// commit on top of the restored working-copy commit. If the changes
+    /// conflict with the restored state, the new commit will have conflicts.
+    #[arg(long)]
+    preserve_working_copy: bool,
 }
 
 pub fn cmd_op_restore(
@@ -51,6 +64,14 @@ pub fn cmd_op_restore(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let target_op = workspace_command.resolve_single_op(&args.operation)?;
+    let old_wc_commit = if args.preserve_working_copy {
+        let wc_commit_id = workspace_command
+            .get_wc_commit_id()
+            .ok_or_else(|| user_error("This command requires a working copy"))?;
+        Some(workspace_command.repo().store().get_commit(wc_commit_id)?)
+    } else {
+        None
+    };
     let mut tx = workspace_command.start_transaction();
     let new_view = view_with_desired_portions_restored(
         target_op.view()?.store_view(),
@@ -64,7 +85,55 @@ pub fn cmd_op_restore(
         template.format(&target_op, formatter.as_mut())?;
         writeln!(formatter)?;
     }
+    warn_about_lost_tracked_remote_bookmarks(
+        ui,
+        tx.base_repo().view(),
+        tx.repo().view(),
+        &args.what,
+    )?;
+    if let Some(old_wc_commit) = &old_wc_commit {
+        preserve_working_copy_changes(ui, &mut tx, old_wc_commit)?;
+    }
     tx.finish(ui, format!("restore to operation {}", target_op.id().hex()))?;
 
     Ok(())
 }
+
+/// Applies the changes in `old_wc_commit` to a new working-copy commit on top
+/// of the working-copy commit in the restored view.
+fn preserve_working_copy_changes(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    old_wc_commit: &Commit,
+) -> Result<(), CommandError> {
+    let workspace_name = tx.base_workspace_helper().workspace_name().to_owned();
+    let Some(restored_wc_commit_id) = tx.repo().view().get_wc_commit_id(&workspace_name) else {
+        return Err(user_error(format!(
+            "Workspace {} doesn't exist at the restored operation",
+            workspace_name.as_symbol()
+        )));
+    };
+    if restored_wc_commit_id == old_wc_commit.id() {
+        return Ok(());
+    }
+    let old_parent_tree = old_wc_commit.parent_tree(tx.repo())?;
+    let old_tree = old_wc_commit.tree()?;
+    if old_tree.id() == old_parent_tree.id() {
+        return Ok(());
+    }
+    let restored_wc_commit = tx.repo().store().get_commit(restored_wc_commit_id)?;
+    let new_tree = restored_wc_commit
+        .tree()?
+        .merge(&old_parent_tree, &old_tree)?;
+    let new_commit = tx
+        .repo_mut()
+        .new_commit(vec![restored_wc_commit.id().clone()], new_tree.id())
+        .write()?;
+    tx.edit(&new_commit)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Preserved working-copy changes in commit ")?;
+        tx.write_commit_summary(formatter.as_mut(), &new_commit)?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
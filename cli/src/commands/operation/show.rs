@@ -12,16 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::num::NonZeroUsize;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::operation::Operation;
 
 use super::diff::show_op_diff;
+use super::diff::OpDiffOptions;
+use crate::cli_util::short_operation_hash;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::diff_formats_for_log;
+use crate::diff_util::set_max_patch_bytes;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
 use crate::graphlog::GraphStyle;
@@ -34,6 +41,12 @@ pub struct OperationShowArgs {
     /// Show repository changes in this operation, compared to its parent(s)
     #[arg(default_value = "@", add = ArgValueCandidates::new(complete::operations))]
     operation: String,
+    /// Show repository changes compared to the given parent of the operation
+    ///
+    /// Parents are numbered from 1 in the order they are recorded. Without
+    /// this option, changes of merge operations aren't shown.
+    #[arg(long, value_name = "INDEX")]
+    against_parent: Option<NonZeroUsize>,
     /// Don't show the graph, show a flat list of modified changes
     #[arg(long)]
     no_graph: bool,
@@ -44,6 +57,16 @@ pub struct OperationShowArgs {
     /// contaminated by unrelated changes.
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Truncate the patch of each file beyond the given number of bytes
+    ///
+    /// Overrides the `ui.op-diff.max-patch-bytes` setting.
+    #[arg(long, value_name = "BYTES")]
+    max_patch_bytes: Option<usize>,
+    /// Don't show the command-line arguments recorded in the operation
+    ///
+    /// This hides the `args` tag from `tags()` in the operation template.
+    #[arg(long)]
+    no_args: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -58,7 +81,17 @@ pub fn cmd_op_show(
     let repo_loader = workspace_command.workspace().repo_loader();
     let settings = workspace_command.settings();
     let op = workspace_command.resolve_single_op(&args.operation)?;
-    let parent_ops: Vec<_> = op.parents().try_collect()?;
+    let mut parent_ops: Vec<_> = op.parents().try_collect()?;
+    if let Some(index) = args.against_parent {
+        if index.get() > parent_ops.len() {
+            return Err(user_error(format!(
+                "Operation {} has only {} parent(s)",
+                short_operation_hash(op.id()),
+                parent_ops.len()
+            )));
+        }
+        parent_ops = vec![parent_ops.swap_remove(index.get() - 1)];
+    }
     let merged_parent_op = repo_loader.merge_operations(parent_ops.clone(), None)?;
     let parent_repo = repo_loader.load_at(&merged_parent_op)?;
     let repo = repo_loader.load_at(&op)?;
@@ -75,7 +108,14 @@ pub fn cmd_op_show(
     let graph_style = GraphStyle::from_settings(settings)?;
     let with_content_format = LogContentFormat::new(ui, settings)?;
     let diff_renderer = {
-        let formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
+        let mut formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
+        let max_patch_bytes = match args.max_patch_bytes {
+            Some(max_bytes) => Some(max_bytes),
+            None => settings.get("ui.op-diff.max-patch-bytes").optional()?,
+        };
+        if let Some(max_bytes) = max_patch_bytes {
+            set_max_patch_bytes(&mut formats, max_bytes);
+        }
         let path_converter = workspace_env.path_converter();
         let conflict_marker_style = workspace_env.conflict_marker_style();
         (!formats.is_empty()).then(|| {
@@ -85,14 +125,19 @@ pub fn cmd_op_show(
                 conflict_marker_style,
                 formats,
             )
+            .with_change_types(args.diff_format.diff_type.clone())
         })
     };
 
     // TODO: Should we make this customizable via clap arg?
     let template: TemplateRenderer<Operation> = {
+        let mut language = workspace_command.operation_template_language();
+        if args.no_args {
+            language.hide_tag("args");
+        }
         let text = settings.get_string("templates.op_log")?;
-        workspace_command
-            .parse_operation_template(ui, &text)?
+        workspace_env
+            .parse_template(ui, &language, &text)?
             .labeled(["op_show", "operation"])
     };
 
@@ -115,5 +160,6 @@ pub fn cmd_op_show(
         (!args.no_graph).then_some(graph_style),
         &with_content_format,
         diff_renderer.as_ref(),
+        &OpDiffOptions::default(),
     )
 }
@@ -20,6 +20,7 @@ use jj_lib::operation::Operation;
 use jj_lib::repo::Repo as _;
 
 use super::view_with_desired_portions_restored;
+use super::warn_about_lost_tracked_remote_bookmarks;
 use super::UndoWhatToRestore;
 use super::DEFAULT_UNDO_WHAT;
 use crate::cli_util::CommandHelper;
@@ -94,6 +95,12 @@ pub fn cmd_op_undo(
         template.format(&bad_op, formatter.as_mut())?;
         writeln!(formatter)?;
     }
+    warn_about_lost_tracked_remote_bookmarks(
+        ui,
+        tx.base_repo().view(),
+        tx.repo().view(),
+        &args.what,
+    )?;
     tx.finish(ui, tx_description(&bad_op))?;
 
     // Check if the user performed a "double undo", i.e. the current `undo` (C)
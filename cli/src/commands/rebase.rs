@@ -329,6 +329,9 @@ pub(crate) struct RebaseArgs {
     /// destination with identical changes.
     #[arg(long)]
     keep_divergent: bool,
+    /// Record a note explaining the rebase, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -393,6 +396,9 @@ pub(crate) fn cmd_rebase(
     };
 
     let mut tx = workspace_command.start_transaction();
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
     let mut computed_move = compute_move_commits(tx.repo(), &loc)?;
     if !args.keep_divergent {
         let abandoned_divergent =
@@ -101,6 +101,9 @@ pub(crate) struct RestoreArgs {
     /// Preserve the content (not the diff) when rebasing descendants
     #[arg(long)]
     restore_descendants: bool,
+    /// Record a note explaining the restore, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -160,6 +163,9 @@ pub(crate) fn cmd_restore(
         writeln!(ui.status(), "Nothing changed.")?;
     } else {
         let mut tx = workspace_command.start_transaction();
+        if let Some(note) = &args.evolog_note {
+            tx.set_evolog_note(note);
+        }
         tx.repo_mut()
             .rewrite_commit(&to_commit)
             .set_tree_id(new_tree_id)
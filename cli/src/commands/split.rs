@@ -131,6 +131,9 @@ pub(crate) struct SplitArgs {
         add = ArgValueCompleter::new(complete::modified_revision_files),
     )]
     paths: Vec<String>,
+    /// Record a note explaining the split, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
 }
 
 impl SplitArgs {
@@ -215,6 +218,9 @@ pub(crate) fn cmd_split(
     } = args.resolve(ui, &workspace_command)?;
     let text_editor = workspace_command.text_editor()?;
     let mut tx = workspace_command.start_transaction();
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
 
     // Prompt the user to select the changes they want for the first commit.
     let target = select_diff(ui, &tx, &target_commit, &matcher, &diff_selector)?;
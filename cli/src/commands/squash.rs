@@ -12,18 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
 use indoc::formatdoc;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::commit::CommitIteratorExt as _;
 use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTreeBuilder;
 use jj_lib::object_id::ObjectId as _;
-use jj_lib::repo::Repo as _;
+use jj_lib::ref_name::RefName;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite;
+use jj_lib::rewrite::rebase_commit;
 use jj_lib::rewrite::CommitWithSelection;
+use jj_lib::rewrite::RebaseOptions;
+use jj_lib::rewrite::RebasedCommit;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
+use crate::cli_util::print_updated_commits;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::DiffSelector;
 use crate::cli_util::RevisionArg;
@@ -55,12 +69,21 @@ use crate::ui::Ui;
 /// parent(s), and `--keep-emptied` is not set, it will be abandoned. Without
 /// `--interactive` or paths, the source revision will always be empty.
 ///
+/// With `--interactive` and several source revisions, the changes of all
+/// sources are presented as a single diff. A file changed by several sources
+/// can only be selected or deselected as a whole: its changes are moved from
+/// all of these sources, or left in all of them.
+///
 /// If the source was abandoned and both the source and destination had a
 /// non-empty description, you will be asked for the combined description. If
 /// either was empty, then the other one will be used.
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// With `--dry-run`, the squash is planned but not recorded: the resulting
+/// description, the commits that would be abandoned or kept, and the number of
+/// descendants that would be rebased are reported instead.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct SquashArgs {
     /// Revision to squash into its parent (default: @)
@@ -88,6 +111,25 @@ pub(crate) struct SquashArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable),
     )]
     into: Option<RevisionArg>,
+    /// Squash into a new revision inserted before the given revision
+    ///
+    /// The new revision is created on top of the parents of the given
+    /// revision, which is rebased onto the new revision along with its
+    /// descendants.
+    #[arg(
+        long, short = 'B',
+        conflicts_with_all = ["revision", "into", "use_destination_message"],
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    insert_before: Option<RevisionArg>,
+    /// Exclude the destination from the source revisions
+    ///
+    /// Without this option, it is an error if the source revisions include
+    /// the destination. This is useful with a source revset such as
+    /// `--from 'x::' --into x`.
+    #[arg(long, conflicts_with_all = ["revision", "insert_before"])]
+    exclude_destination: bool,
     /// The description to use for squashed revision (don't open editor)
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
@@ -111,6 +153,27 @@ pub(crate) struct SquashArgs {
     /// The source revision will not be abandoned
     #[arg(long, short)]
     keep_emptied: bool,
+    /// Preserve the content (not the diff) when rebasing descendants
+    ///
+    /// When rebasing a descendant on top of the rewritten revisions, its diff
+    /// compared to its parent(s) is normally preserved, i.e. the same way that
+    /// descendants are always rebased. This flag makes it so the content/state
+    /// is preserved instead of preserving the diff.
+    #[arg(long)]
+    restore_descendants: bool,
+    /// List the descendants rebased by `--restore-descendants`, marking the
+    /// ones whose diff changed in order to preserve their content
+    #[arg(long, requires = "restore_descendants")]
+    verbose: bool,
+    /// Record a note explaining the squash, shown in `jj evolog`
+    #[arg(long, value_name = "TEXT")]
+    evolog_note: Option<String>,
+    /// Only report what the squash would do, without rewriting any commits
+    ///
+    /// If the descriptions would have to be combined in an editor, the editor
+    /// is not opened.
+    #[arg(long, conflicts_with_all = ["interactive", "tool", "insert_before"])]
+    dry_run: bool,
 }
 
 #[instrument(skip_all)]
@@ -122,8 +185,8 @@ pub(crate) fn cmd_squash(
     let mut workspace_command = command.workspace_helper(ui)?;
 
     let mut sources: Vec<Commit>;
-    let destination;
-    if !args.from.is_empty() || args.into.is_some() {
+    let mut destination;
+    if !args.from.is_empty() || args.into.is_some() || args.insert_before.is_some() {
         sources = if args.from.is_empty() {
             workspace_command.parse_revset(ui, &RevisionArg::AT)?
         } else {
@@ -131,10 +194,43 @@ pub(crate) fn cmd_squash(
         }
         .evaluate_to_commits()?
         .try_collect()?;
-        destination = workspace_command
-            .resolve_single_rev(ui, args.into.as_ref().unwrap_or(&RevisionArg::AT))?;
-        if sources.iter().any(|source| source.id() == destination.id()) {
-            return Err(user_error("Source and destination cannot be the same"));
+        if let Some(revision_arg) = &args.insert_before {
+            // The new destination will be inserted before this revision.
+            destination = workspace_command.resolve_single_rev(ui, revision_arg)?;
+            if destination.id() == destination.store().root_commit_id() {
+                return Err(user_error("Cannot insert a commit before the root commit"));
+            }
+        } else {
+            destination = workspace_command
+                .resolve_single_rev(ui, args.into.as_ref().unwrap_or(&RevisionArg::AT))?;
+        }
+        let root_commit_id = destination.store().root_commit_id();
+        if sources.iter().any(|source| source.id() == root_commit_id) {
+            return Err(user_error("Cannot squash the root commit"));
+        }
+        if destination.id() == root_commit_id {
+            return Err(user_error("Cannot squash into the root commit"));
+        }
+        if args.insert_before.is_none()
+            && sources.iter().any(|source| source.id() == destination.id())
+        {
+            if !args.exclude_destination {
+                let message = "Source and destination cannot be the same";
+                return Err(if sources.len() > 1 {
+                    user_error_with_hint(
+                        message,
+                        "Use `--exclude-destination` to squash the other source revisions",
+                    )
+                } else {
+                    user_error(message)
+                });
+            }
+            sources.retain(|source| source.id() != destination.id());
+            writeln!(
+                ui.status(),
+                "Excluded the destination {} from the source revisions",
+                workspace_command.format_commit_summary(&destination)
+            )?;
         }
         // Reverse the set so we apply the oldest commits first. It shouldn't affect the
         // result, but it avoids creating transient conflicts and is therefore probably
@@ -143,6 +239,9 @@ pub(crate) fn cmd_squash(
     } else {
         let source = workspace_command
             .resolve_single_rev(ui, args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
+        if source.id() == source.store().root_commit_id() {
+            return Err(user_error("Cannot squash the root commit"));
+        }
         let mut parents: Vec<_> = source.parents().try_collect()?;
         if parents.len() != 1 {
             return Err(user_error_with_hint(
@@ -152,6 +251,9 @@ pub(crate) fn cmd_squash(
         }
         sources = vec![source];
         destination = parents.pop().unwrap();
+        if destination.id() == destination.store().root_commit_id() {
+            return Err(user_error("Cannot squash into the root commit"));
+        }
     }
 
     let matcher = workspace_command
@@ -164,16 +266,47 @@ pub(crate) fn cmd_squash(
     workspace_command
         .check_rewritable(sources.iter().chain(std::iter::once(&destination)).ids())?;
 
+    let old_repo = workspace_command.repo().clone();
     let mut tx = workspace_command.start_transaction();
-    let tx_description = format!("squash commits into {}", destination.id().hex());
+    let tx_description;
+    if args.insert_before.is_some() {
+        tx_description = format!(
+            "squash commits into new commit before {}",
+            destination.id().hex()
+        );
+        (destination, sources) = insert_empty_commit_before(&mut tx, &destination, &sources)?;
+    } else {
+        tx_description = format!("squash commits into {}", destination.id().hex());
+    }
+    if let Some(note) = &args.evolog_note {
+        tx.set_evolog_note(note);
+    }
     let source_commits = select_diff(&tx, &sources, &destination, &matcher, &diff_selector)?;
+    let no_file_changes = source_commits
+        .iter()
+        .all(|source| source.is_empty_selection());
     if let Some(squashed) = rewrite::squash_commits(
         tx.repo_mut(),
         &source_commits,
         &destination,
         args.keep_emptied,
     )? {
+        if no_file_changes
+            && !matches!(description, SquashedDescription::UseDestination)
+            && squashed
+                .abandoned_commits
+                .iter()
+                .any(|commit| !commit.description().is_empty())
+        {
+            writeln!(
+                ui.warning_default(),
+                "No file changes to move; only combining descriptions"
+            )?;
+        }
         let mut commit_builder = squashed.commit_builder.detach();
+        let abandoned_ids: HashSet<CommitId> =
+            squashed.abandoned_commits.iter().ids().cloned().collect();
+        let mut needs_editor = false;
         let new_description = match description {
             SquashedDescription::Exact(description) => {
                 if description.is_empty() {
@@ -200,6 +333,9 @@ pub(crate) fn cmd_squash(
                         commit_builder.set_description(description);
                         add_trailers(ui, &tx, &commit_builder)?
                     }
+                } else if args.dry_run {
+                    needs_editor = true;
+                    destination.description().to_owned()
                 } else {
                     let combined = combine_messages_for_editing(
                         ui,
@@ -218,7 +354,28 @@ pub(crate) fn cmd_squash(
             }
         };
         commit_builder.set_description(new_description);
-        commit_builder.write(tx.repo_mut())?;
+        let new_commit = commit_builder.write(tx.repo_mut())?;
+        if args.dry_run {
+            let num_rebased = if args.restore_descendants {
+                tx.repo_mut().reparent_descendants()?
+            } else {
+                tx.repo_mut().rebase_descendants()?
+            };
+            let description = (!needs_editor).then(|| new_commit.description());
+            print_dry_run_report(
+                ui,
+                &tx,
+                &sources,
+                &destination,
+                &abandoned_ids,
+                description,
+                num_rebased,
+            )?;
+            return Ok(());
+        }
+        if args.restore_descendants {
+            reparent_descendants(ui, &mut tx, args.verbose)?;
+        }
     } else {
         if diff_selector.is_interactive() {
             return Err(user_error("No changes selected"));
@@ -241,6 +398,162 @@ pub(crate) fn cmd_squash(
         }
     }
     tx.finish(ui, tx_description)?;
+    warn_collapsed_bookmarks(ui, old_repo.as_ref(), workspace_command.repo().as_ref())?;
+    Ok(())
+}
+
+/// Creates an empty commit between the `target` and its parents, and rebases
+/// the `target` and its descendants onto it.
+///
+/// Returns the new commit and the `sources` as rebased.
+fn insert_empty_commit_before(
+    tx: &mut WorkspaceCommandTransaction,
+    target: &Commit,
+    sources: &[Commit],
+) -> Result<(Commit, Vec<Commit>), CommandError> {
+    let parent_tree = target.parent_tree(tx.repo())?;
+    let new_commit = tx
+        .repo_mut()
+        .new_commit(target.parent_ids().to_vec(), parent_tree.id())
+        .write()?;
+    let rebased_target =
+        rebase_commit(tx.repo_mut(), target.clone(), vec![new_commit.id().clone()])?;
+    let mut rebased_commits = HashMap::from([(target.id().clone(), rebased_target)]);
+    tx.repo_mut().rebase_descendants_with_options(
+        &RebaseOptions::default(),
+        |old_commit, rebased_commit| {
+            if let RebasedCommit::Rewritten(new_commit) = rebased_commit {
+                rebased_commits.insert(old_commit.id().clone(), new_commit);
+            }
+        },
+    )?;
+    let sources = sources
+        .iter()
+        .map(|source| rebased_commits.get(source.id()).unwrap_or(source).clone())
+        .collect();
+    Ok((new_commit, sources))
+}
+
+/// Reports what a squash would do without recording it. The `description` is
+/// `None` if an editor would be opened to combine the descriptions.
+fn print_dry_run_report(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    sources: &[Commit],
+    destination: &Commit,
+    abandoned_ids: &HashSet<CommitId>,
+    description: Option<&str>,
+    num_rebased: usize,
+) -> Result<(), CommandError> {
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    // Commits are summarized as they are before the squash.
+    let template = tx.base_workspace_helper().commit_summary_template();
+    writeln!(formatter, "Would squash into commit:")?;
+    print_updated_commits(formatter.as_mut(), &template, [destination])?;
+    let (abandoned, kept): (Vec<_>, Vec<_>) = sources
+        .iter()
+        .partition(|source| abandoned_ids.contains(source.id()));
+    if !abandoned.is_empty() {
+        writeln!(formatter, "Would abandon these commits:")?;
+        print_updated_commits(formatter.as_mut(), &template, abandoned)?;
+    }
+    if !kept.is_empty() {
+        writeln!(formatter, "Would keep these commits:")?;
+        print_updated_commits(formatter.as_mut(), &template, kept)?;
+    }
+    match description {
+        Some("") => writeln!(formatter, "The resulting description would be empty")?,
+        Some(description) => {
+            writeln!(formatter, "The resulting description would be:")?;
+            for line in description.lines() {
+                writeln!(formatter, "  {line}")?;
+            }
+        }
+        None => writeln!(formatter, "An editor would open to combine descriptions")?,
+    }
+    if num_rebased > 0 {
+        writeln!(formatter, "Would rebase {num_rebased} descendant commits")?;
+    }
+    writeln!(formatter, "Dry-run requested, not squashing.")?;
+    Ok(())
+}
+
+/// Warns about local bookmarks which pointed to different commits, but now
+/// point to the same commit.
+fn warn_collapsed_bookmarks(
+    ui: &Ui,
+    old_repo: &dyn Repo,
+    new_repo: &dyn Repo,
+) -> Result<(), CommandError> {
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    let old_view = old_repo.view();
+    let mut names_by_target: HashMap<&CommitId, Vec<&RefName>> = HashMap::new();
+    for (name, target) in new_repo.view().local_bookmarks() {
+        if let Some(id) = target.as_normal() {
+            names_by_target.entry(id).or_default().push(name);
+        }
+    }
+    for names in names_by_target.into_values().sorted() {
+        let old_targets: HashSet<_> = names
+            .iter()
+            .map(|&name| old_view.get_local_bookmark(name))
+            .collect();
+        if old_targets.len() < 2 {
+            continue;
+        }
+        let (last_name, other_names) = names.split_last().unwrap();
+        writeln!(
+            formatter.labeled("warning").with_heading("Warning: "),
+            "Bookmarks {} and {} now point to the same commit",
+            other_names.iter().map(|name| name.as_symbol()).join(", "),
+            last_name.as_symbol(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Reparents descendants of the rewritten commits. If `verbose` is set, lists
+/// them and marks the ones whose diff changed in order to preserve their
+/// content.
+fn reparent_descendants(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    verbose: bool,
+) -> Result<(), CommandError> {
+    let mut reparented = vec![];
+    tx.repo_mut()
+        .reparent_descendants_with(|old_commit, new_commit| {
+            reparented.push((old_commit, new_commit));
+        })?;
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    if reparented.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        formatter,
+        "Rebased {} descendant commits (while preserving their content)",
+        reparented.len()
+    )?;
+    if !verbose {
+        return Ok(());
+    }
+    let template = tx.commit_summary_template();
+    for (old_commit, new_commit) in &reparented {
+        let old_parent_tree = old_commit.parent_tree(tx.repo())?;
+        let new_parent_tree = new_commit.parent_tree(tx.repo())?;
+        write!(formatter, "  ")?;
+        template.format(new_commit, formatter.as_mut())?;
+        if old_parent_tree.id() != new_parent_tree.id() {
+            write!(formatter.labeled("warning"), " (diff changed)")?;
+        }
+        writeln!(formatter)?;
+    }
     Ok(())
 }
 
@@ -277,6 +590,9 @@ fn select_diff(
     matcher: &dyn Matcher,
     diff_selector: &DiffSelector,
 ) -> Result<Vec<CommitWithSelection>, CommandError> {
+    if diff_selector.is_interactive() && sources.len() > 1 {
+        return select_combined_diff(tx, sources, destination, matcher, diff_selector);
+    }
     let mut source_commits = vec![];
     for source in sources {
         let parent_tree = source.parent_tree(tx.repo())?;
@@ -309,3 +625,114 @@ fn select_diff(
     }
     Ok(source_commits)
 }
+
+/// Selects changes to move from all `sources` in one diff, and distributes the
+/// selected changes back to the sources they came from.
+///
+/// A path changed by several sources shows the contents of the last source
+/// that changed it, and must be selected entirely or not at all, since a
+/// partial selection can't be applied to the other sources' parent trees.
+fn select_combined_diff(
+    tx: &WorkspaceCommandTransaction,
+    sources: &[Commit],
+    destination: &Commit,
+    matcher: &dyn Matcher,
+    diff_selector: &DiffSelector,
+) -> Result<Vec<CommitWithSelection>, CommandError> {
+    let store = tx.repo().store();
+    let mut source_diffs = vec![];
+    // Indices of the first and the last sources changing each path
+    let mut first_sources: HashMap<RepoPathBuf, usize> = HashMap::new();
+    let mut last_sources: HashMap<RepoPathBuf, usize> = HashMap::new();
+    for (i, source) in sources.iter().enumerate() {
+        let parent_tree = source.parent_tree(tx.repo())?;
+        let source_tree = source.tree()?;
+        let paths: Vec<RepoPathBuf> = parent_tree
+            .diff_stream(&source_tree, matcher)
+            .map(|entry| entry.values.map(|_| entry.path))
+            .try_collect()
+            .block_on()?;
+        for path in &paths {
+            first_sources.entry(path.clone()).or_insert(i);
+            last_sources.insert(path.clone(), i);
+        }
+        source_diffs.push((parent_tree, source_tree, paths));
+    }
+
+    // Unchanged paths are taken from an arbitrary base tree, which doesn't
+    // matter as they aren't part of the diff.
+    let base_tree_id = source_diffs[0].0.id();
+    let mut left_builder = MergedTreeBuilder::new(base_tree_id.clone());
+    for (path, &i) in &first_sources {
+        let (parent_tree, _, _) = &source_diffs[i];
+        left_builder.set_or_remove(path.clone(), parent_tree.path_value(path)?);
+    }
+    let mut right_builder = MergedTreeBuilder::new(base_tree_id);
+    for (path, &i) in &last_sources {
+        let (_, source_tree, _) = &source_diffs[i];
+        right_builder.set_or_remove(path.clone(), source_tree.path_value(path)?);
+    }
+    let left_tree = store.get_root_tree(&left_builder.write_tree(store)?)?;
+    let right_tree = store.get_root_tree(&right_builder.write_tree(store)?)?;
+
+    let format_instructions = || {
+        let source_summaries = sources
+            .iter()
+            .map(|source| format!("  {}", tx.format_commit_summary(source)))
+            .join("\n");
+        formatdoc! {"
+            You are moving changes from:
+            {source_summaries}
+            into commit: {destination}
+
+            The left side of the diff shows the contents of the parent commits. The
+            right side initially shows the combined contents of the commits you're
+            moving changes from. If several commits changed the same file, the
+            contents of the last commit are shown, and the file can only be
+            selected as a whole.
+
+            Adjust the right side until the diff shows the changes you want to move
+            to the destination. If you don't make any changes, then all the changes
+            from the sources will be moved into the destination.
+            ",
+            destination = tx.format_commit_summary(destination),
+        }
+    };
+    let selected_tree_id =
+        diff_selector.select(&left_tree, &right_tree, matcher, format_instructions)?;
+    let selected_tree = store.get_root_tree(&selected_tree_id)?;
+
+    let mut source_commits = vec![];
+    for (source, (parent_tree, source_tree, paths)) in sources.iter().zip(source_diffs) {
+        let mut builder = MergedTreeBuilder::new(parent_tree.id());
+        for path in paths {
+            let selected_value = selected_tree.path_value(&path)?;
+            if selected_value == left_tree.path_value(&path)? {
+                continue;
+            }
+            let value = if first_sources[&path] == last_sources[&path] {
+                selected_value
+            } else if selected_value == right_tree.path_value(&path)? {
+                source_tree.path_value(&path)?
+            } else {
+                return Err(user_error_with_hint(
+                    format!(
+                        "Cannot select part of the changes to {} made by several source \
+                         revisions",
+                        tx.base_workspace_helper().format_file_path(&path)
+                    ),
+                    "Select all or none of the changes to this file, or squash the sources \
+                     one at a time.",
+                ));
+            };
+            builder.set_or_remove(path, value);
+        }
+        let selected_tree = store.get_root_tree(&builder.write_tree(store)?)?;
+        source_commits.push(CommitWithSelection {
+            commit: source.clone(),
+            selected_tree,
+            parent_tree,
+        });
+    }
+    Ok(source_commits)
+}
@@ -48,6 +48,7 @@ use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
+use jj_lib::ref_name::RefName;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo;
@@ -73,11 +74,13 @@ use pollster::FutureExt as _;
 use serde::Serialize as _;
 
 use crate::diff_util;
+use crate::diff_util::DiffStatStyle;
 use crate::diff_util::DiffStats;
 use crate::formatter::Formatter;
 use crate::revset_util;
 use crate::template_builder;
 use crate::template_builder::expect_plain_text_expression;
+use crate::template_builder::expect_usize_expression;
 use crate::template_builder::merge_fn_map;
 use crate::template_builder::BuildContext;
 use crate::template_builder::CoreTemplateBuildFnTable;
@@ -163,6 +166,15 @@ impl<'repo> CommitTemplateLanguage<'repo> {
             cache_extensions,
         }
     }
+
+    /// Restricts the bookmarks rendered by the `bookmarks`,
+    /// `local_bookmarks`, and `remote_bookmarks` keywords to the names
+    /// accepted by the given predicate.
+    ///
+    /// This must be set before the template is evaluated.
+    pub fn set_bookmark_filter(&mut self, filter: impl Fn(&RefName) -> bool + 'repo) {
+        self.keyword_cache.bookmark_filter = Some(Box::new(filter));
+    }
 }
 
 impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
@@ -257,6 +269,11 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let inner_property = property.try_unwrap(type_name).into_dyn();
                 build(self, diagnostics, build_ctx, inner_property, function)
             }
+            CommitTemplatePropertyKind::RepoPathList(property) => {
+                let table = &self.build_fn_table.repo_path_list_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
             CommitTemplatePropertyKind::ChangeId(property) => {
                 let table = &self.build_fn_table.change_id_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -359,6 +376,7 @@ pub enum CommitTemplatePropertyKind<'repo> {
     RefSymbolOpt(BoxedTemplateProperty<'repo, Option<RefSymbolBuf>>),
     RepoPath(BoxedTemplateProperty<'repo, RepoPathBuf>),
     RepoPathOpt(BoxedTemplateProperty<'repo, Option<RepoPathBuf>>),
+    RepoPathList(BoxedTemplateProperty<'repo, Vec<RepoPathBuf>>),
     ChangeId(BoxedTemplateProperty<'repo, ChangeId>),
     CommitId(BoxedTemplateProperty<'repo, CommitId>),
     ShortestIdPrefix(BoxedTemplateProperty<'repo, ShortestIdPrefix>),
@@ -385,6 +403,7 @@ template_builder::impl_property_wrappers!(<'repo> CommitTemplatePropertyKind<'re
     RefSymbolOpt(Option<RefSymbolBuf>),
     RepoPath(RepoPathBuf),
     RepoPathOpt(Option<RepoPathBuf>),
+    RepoPathList(Vec<RepoPathBuf>),
     ChangeId(ChangeId),
     CommitId(CommitId),
     ShortestIdPrefix(ShortestIdPrefix),
@@ -421,6 +440,7 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::RefSymbolOpt(_) => "Option<RefSymbol>",
             Self::RepoPath(_) => "RepoPath",
             Self::RepoPathOpt(_) => "Option<RepoPath>",
+            Self::RepoPathList(_) => "List<RepoPath>",
             Self::ChangeId(_) => "ChangeId",
             Self::CommitId(_) => "CommitId",
             Self::ShortestIdPrefix(_) => "ShortestIdPrefix",
@@ -449,6 +469,7 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::RefSymbolOpt(property) => Some(property.map(|opt| opt.is_some()).into_dyn()),
             Self::RepoPath(_) => None,
             Self::RepoPathOpt(property) => Some(property.map(|opt| opt.is_some()).into_dyn()),
+            Self::RepoPathList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
             Self::ChangeId(_) => None,
             Self::CommitId(_) => None,
             Self::ShortestIdPrefix(_) => None,
@@ -505,6 +526,7 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::RefSymbolOpt(property) => Some(property.into_serialize()),
             Self::RepoPath(property) => Some(property.into_serialize()),
             Self::RepoPathOpt(property) => Some(property.into_serialize()),
+            Self::RepoPathList(property) => Some(property.into_serialize()),
             Self::ChangeId(property) => Some(property.into_serialize()),
             Self::CommitId(property) => Some(property.into_serialize()),
             Self::ShortestIdPrefix(property) => Some(property.into_serialize()),
@@ -533,6 +555,7 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::RefSymbolOpt(property) => Some(property.into_template()),
             Self::RepoPath(property) => Some(property.into_template()),
             Self::RepoPathOpt(property) => Some(property.into_template()),
+            Self::RepoPathList(property) => Some(property.into_template()),
             Self::ChangeId(property) => Some(property.into_template()),
             Self::CommitId(property) => Some(property.into_template()),
             Self::ShortestIdPrefix(property) => Some(property.into_template()),
@@ -591,6 +614,7 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::RefSymbolOpt(_), _) => None,
             (Self::RepoPath(_), _) => None,
             (Self::RepoPathOpt(_), _) => None,
+            (Self::RepoPathList(_), _) => None,
             (Self::ChangeId(_), _) => None,
             (Self::CommitId(_), _) => None,
             (Self::ShortestIdPrefix(_), _) => None,
@@ -620,6 +644,7 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::RefSymbolOpt(_), _) => None,
             (Self::RepoPath(_), _) => None,
             (Self::RepoPathOpt(_), _) => None,
+            (Self::RepoPathList(_), _) => None,
             (Self::ChangeId(_), _) => None,
             (Self::CommitId(_), _) => None,
             (Self::ShortestIdPrefix(_), _) => None,
@@ -648,6 +673,7 @@ pub struct CommitTemplateBuildFnTable<'repo> {
     pub commit_ref_methods: CommitTemplateBuildMethodFnMap<'repo, Rc<CommitRef>>,
     pub commit_ref_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<Rc<CommitRef>>>,
     pub repo_path_methods: CommitTemplateBuildMethodFnMap<'repo, RepoPathBuf>,
+    pub repo_path_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<RepoPathBuf>>,
     pub change_id_methods: CommitTemplateBuildMethodFnMap<'repo, ChangeId>,
     pub commit_id_methods: CommitTemplateBuildMethodFnMap<'repo, CommitId>,
     pub shortest_id_prefix_methods: CommitTemplateBuildMethodFnMap<'repo, ShortestIdPrefix>,
@@ -673,6 +699,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             commit_ref_methods: builtin_commit_ref_methods(),
             commit_ref_list_methods: template_builder::builtin_formattable_list_methods(),
             repo_path_methods: builtin_repo_path_methods(),
+            repo_path_list_methods: template_builder::builtin_formattable_list_methods(),
             change_id_methods: builtin_change_id_methods(),
             commit_id_methods: builtin_commit_id_methods(),
             shortest_id_prefix_methods: builtin_shortest_id_prefix_methods(),
@@ -696,6 +723,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             commit_ref_methods: HashMap::new(),
             commit_ref_list_methods: HashMap::new(),
             repo_path_methods: HashMap::new(),
+            repo_path_list_methods: HashMap::new(),
             change_id_methods: HashMap::new(),
             commit_id_methods: HashMap::new(),
             shortest_id_prefix_methods: HashMap::new(),
@@ -719,6 +747,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             commit_ref_methods,
             commit_ref_list_methods,
             repo_path_methods,
+            repo_path_list_methods,
             change_id_methods,
             commit_id_methods,
             shortest_id_prefix_methods,
@@ -739,6 +768,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
         merge_fn_map(&mut self.commit_ref_methods, commit_ref_methods);
         merge_fn_map(&mut self.commit_ref_list_methods, commit_ref_list_methods);
         merge_fn_map(&mut self.repo_path_methods, repo_path_methods);
+        merge_fn_map(&mut self.repo_path_list_methods, repo_path_list_methods);
         merge_fn_map(&mut self.change_id_methods, change_id_methods);
         merge_fn_map(&mut self.commit_id_methods, commit_id_methods);
         merge_fn_map(
@@ -763,19 +793,23 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
     }
 }
 
+type BookmarkFilterFn<'repo> = dyn Fn(&RefName) -> bool + 'repo;
+
 #[derive(Default)]
 pub struct CommitKeywordCache<'repo> {
     // Build index lazily, and Rc to get away from &self lifetime.
     bookmarks_index: OnceCell<Rc<CommitRefsIndex>>,
+    bookmark_filter: Option<Box<BookmarkFilterFn<'repo>>>,
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
+    working_copies_index: OnceCell<Rc<HashMap<CommitId, Vec<String>>>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
     pub fn bookmarks_index(&self, repo: &dyn Repo) -> &Rc<CommitRefsIndex> {
         self.bookmarks_index
-            .get_or_init(|| Rc::new(build_bookmarks_index(repo)))
+            .get_or_init(|| Rc::new(build_bookmarks_index(repo, self.bookmark_filter.as_deref())))
     }
 
     pub fn tags_index(&self, repo: &dyn Repo) -> &Rc<CommitRefsIndex> {
@@ -788,6 +822,11 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_commit_refs_index(repo.view().git_refs())))
     }
 
+    pub fn working_copies_index(&self, repo: &dyn Repo) -> &Rc<HashMap<CommitId, Vec<String>>> {
+        self.working_copies_index
+            .get_or_init(|| Rc::new(build_working_copies_index(repo)))
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -868,6 +907,35 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "author_committer_differ",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            let ([], [kind_node]) = function.expect_arguments()?;
+            let kind = if let Some(node) = kind_node {
+                template_parser::expect_string_literal_with(node, |text, span| match text {
+                    "identity" | "time" | "any" => Ok(text.to_owned()),
+                    _ => Err(TemplateParseError::expression(
+                        r#"Expected "identity", "time", or "any""#,
+                        span,
+                    )),
+                })?
+            } else {
+                "identity".to_owned()
+            };
+            let out_property = self_property.map(move |commit| {
+                let (author, committer) = (commit.author(), commit.committer());
+                let identity_differs =
+                    author.name != committer.name || author.email != committer.email;
+                let time_differs = author.timestamp.timestamp != committer.timestamp.timestamp;
+                match kind.as_str() {
+                    "identity" => identity_differs,
+                    "time" => time_differs,
+                    _ => identity_differs || time_differs,
+                }
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "mine",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -889,8 +957,12 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
         "working_copies",
         |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            let repo = language.repo;
-            let out_property = self_property.map(|commit| extract_working_copies(repo, &commit));
+            let index = language
+                .keyword_cache
+                .working_copies_index(language.repo)
+                .clone();
+            let out_property = self_property
+                .map(move |commit| index.get(commit.id()).cloned().unwrap_or_default());
             Ok(out_property.into_dyn_wrapped())
         },
     );
@@ -905,6 +977,18 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "any_working_copy",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let index = language
+                .keyword_cache
+                .working_copies_index(language.repo)
+                .clone();
+            let out_property = self_property.map(move |commit| index.contains_key(commit.id()));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "bookmarks",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1075,6 +1159,20 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "files",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let ([], [parent_node]) = function.expect_arguments()?;
+            let parent_property = parent_node
+                .map(|node| expect_usize_expression(language, diagnostics, build_ctx, node))
+                .transpose()?;
+            let repo = language.repo;
+            let out_property = (self_property, parent_property).and_then(move |(commit, index)| {
+                commit_changed_paths(repo, &commit, index.unwrap_or(0))
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "root",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1088,19 +1186,41 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
     map
 }
 
-// TODO: return Vec<String>
-fn extract_working_copies(repo: &dyn Repo, commit: &Commit) -> String {
+/// Lists the paths changed in the commit compared to its `index`-th parent.
+fn commit_changed_paths(
+    repo: &dyn Repo,
+    commit: &Commit,
+    index: usize,
+) -> Result<Vec<RepoPathBuf>, TemplatePropertyError> {
+    let from_tree = match commit.parent_ids().get(index) {
+        Some(parent_id) => repo.store().get_commit(parent_id)?.tree()?,
+        // The root commit has no parents, so compare it against the empty tree
+        None if index == 0 => commit.parent_tree(repo)?,
+        None => {
+            return Err(TemplatePropertyError(
+                format!("Commit has no parent at index {index}").into(),
+            ));
+        }
+    };
+    Ok(diff_util::changed_paths(&from_tree, &commit.tree()?)?)
+}
+
+/// Maps working-copy commits to the `<workspace name>@` symbols.
+///
+/// For single-workspace repository, the working-copy commit isn't indicated.
+fn build_working_copies_index(repo: &dyn Repo) -> HashMap<CommitId, Vec<String>> {
     let wc_commit_ids = repo.view().wc_commit_ids();
+    let mut index: HashMap<CommitId, Vec<String>> = HashMap::new();
     if wc_commit_ids.len() <= 1 {
-        return "".to_string();
+        return index;
     }
-    let mut names = vec![];
     for (name, wc_commit_id) in wc_commit_ids {
-        if wc_commit_id == commit.id() {
-            names.push(format!("{}@", name.as_symbol()));
-        }
+        index
+            .entry(wc_commit_id.clone())
+            .or_default()
+            .push(format!("{}@", name.as_symbol()));
     }
-    names.join(" ")
+    index
 }
 
 fn expect_fileset_literal(
@@ -1521,9 +1641,13 @@ impl CommitRefsIndex {
     }
 }
 
-fn build_bookmarks_index(repo: &dyn Repo) -> CommitRefsIndex {
+fn build_bookmarks_index(repo: &dyn Repo, filter: Option<&BookmarkFilterFn>) -> CommitRefsIndex {
     let mut index = CommitRefsIndex::default();
-    for (bookmark_name, bookmark_target) in repo.view().bookmarks() {
+    let bookmarks = repo
+        .view()
+        .bookmarks()
+        .filter(|(name, _)| filter.is_none_or(|f| f(name)));
+    for (bookmark_name, bookmark_target) in bookmarks {
         let local_target = bookmark_target.local_target;
         let remote_refs = bookmark_target.remote_refs;
         if local_target.is_present() {
@@ -1583,6 +1707,12 @@ impl Template for RepoPathBuf {
     }
 }
 
+impl Template for Vec<RepoPathBuf> {
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        templater::format_joined(formatter, self, " ")
+    }
+}
+
 fn builtin_repo_path_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, RepoPathBuf> {
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
@@ -2192,6 +2322,7 @@ impl Template for DiffStatsFormatted<'_> {
             &self.stats,
             self.path_converter,
             self.width,
+            DiffStatStyle::Bar,
         )
     }
 }
@@ -14,16 +14,26 @@
 
 use std::borrow::Borrow;
 use std::cmp::max;
+use std::collections::HashSet;
+use std::env;
 use std::io;
+use std::io::Write as _;
 use std::iter;
 use std::mem;
 use std::ops::Range;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt as _;
 use std::path::Path;
 use std::path::PathBuf;
 
 use bstr::BStr;
 use bstr::BString;
+use bstr::ByteSlice as _;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 use futures::executor::block_on_stream;
+use futures::future;
+use futures::stream;
 use futures::stream::BoxStream;
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
@@ -35,6 +45,7 @@ use jj_lib::backend::CopyRecord;
 use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::materialized_diff_stream;
 use jj_lib::conflicts::ConflictMarkerStyle;
@@ -57,6 +68,8 @@ use jj_lib::files::ConflictDiffHunk;
 use jj_lib::files::DiffLineHunkSide;
 use jj_lib::files::DiffLineIterator;
 use jj_lib::files::DiffLineNumber;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Merge;
 use jj_lib::merge::MergedTreeValue;
@@ -65,6 +78,7 @@ use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::InvalidRepoPathError;
 use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::rewrite::rebase_to_dest_parent;
 use jj_lib::settings::UserSettings;
@@ -75,12 +89,16 @@ use tracing::instrument;
 use unicode_width::UnicodeWidthStr as _;
 
 use crate::command_error::cli_error;
+use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::commit_templater;
 use crate::config::CommandNameAndArgs;
+use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
+use crate::formatter::PlainTextFormatter;
 use crate::merge_tools;
 use crate::merge_tools::generate_diff;
+use crate::merge_tools::invoke_diff_highlighter;
 use crate::merge_tools::invoke_external_diff;
 use crate::merge_tools::new_utf8_temp_dir;
 use crate::merge_tools::DiffGenerateError;
@@ -101,6 +119,9 @@ pub struct DiffFormatArgs {
     /// Show a histogram of the changes
     #[arg(long)]
     pub stat: bool,
+    /// How to show the changed lines of each path in `--stat` output
+    #[arg(long, value_name = "FORMAT", value_enum, requires = "stat")]
+    pub stat_format: Option<DiffStatStyle>,
     /// For each path, show only its type before and after
     ///
     /// The diff is shown as two letters. The first letter indicates the type
@@ -119,6 +140,23 @@ pub struct DiffFormatArgs {
     /// Show a Git-format diff
     #[arg(long)]
     pub git: bool,
+    /// Pipe Git-format diffs through the given command
+    ///
+    /// This overrides the `ui.diff-highlighter` setting.
+    #[arg(long, value_name = "COMMAND")]
+    pub highlighter: Option<String>,
+    /// Show only files of the given change types
+    ///
+    /// This applies to all diff formats. Renamed and copied files are only
+    /// matched by `renamed` and `copied` respectively.
+    #[arg(long, value_name = "TYPE", value_enum, value_delimiter = ',')]
+    pub diff_type: Vec<DiffChangeType>,
+    /// Include binary patch data in Git-format diffs
+    ///
+    /// Binary files are emitted as `GIT binary patch` blocks which can be
+    /// applied by `git apply`. Object ids are shown in full.
+    #[arg(long)]
+    pub binary: bool,
     /// Show a word-level diff with changes indicated only by color
     #[arg(long)]
     pub color_words: bool,
@@ -126,8 +164,17 @@ pub struct DiffFormatArgs {
     ///
     /// A builtin format can also be specified as `:<name>`. For example,
     /// `--tool=:git` is equivalent to `--git`.
+    ///
+    /// If the tool can't be found, the tools listed in `ui.diff-tools` are
+    /// tried in order.
     #[arg(long)]
     pub tool: Option<String>,
+    /// Show the diff in the builtin color-words format
+    ///
+    /// External tools specified by `--tool`, `ui.diff-tools`, or
+    /// `ui.diff-formatter` aren't used.
+    #[arg(long)]
+    pub builtin: bool,
     /// Number of lines of context to show
     #[arg(long)]
     context: Option<usize>,
@@ -139,6 +186,18 @@ pub struct DiffFormatArgs {
     /// Ignore changes in amount of whitespace when comparing lines.
     #[arg(long, conflicts_with = "ignore_all_space")] // short = 'b'
     ignore_space_change: bool,
+    /// Highlight lines moved within a file in the Git and color-words formats
+    ///
+    /// Lines removed from one place and added to another place of the same
+    /// file are labeled as `diff moved`.
+    #[arg(
+        long,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "plain"
+    )]
+    color_moved: Option<ColorMovedMode>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -291,47 +350,169 @@ fn diff_formats_from_args(
     let mut long_format = long_kind
         .map(|kind| kind.to_format(settings, args))
         .transpose()?;
-    if let Some(name) = &args.tool {
-        let ensure_new = |old_kind: Option<BuiltinFormatKind>| match old_kind {
-            Some(old) => Err(cli_error(format!(
-                "--tool={name} cannot be used with --{old}",
-                old = old.to_arg_name()
-            ))),
-            None => Ok(()),
-        };
-        if let Some(name) = name.strip_prefix(':') {
-            let kind = BuiltinFormatKind::from_name(name).map_err(cli_error)?;
-            let format = kind.to_format(settings, args)?;
-            if kind.is_short() {
-                ensure_new(short_kind)?;
-                short_format = Some(format);
-            } else {
-                ensure_new(long_kind)?;
-                long_format = Some(format);
+    if args.builtin {
+        // --builtin replaces the tool specified by --tool
+        if args.tool.is_some() && long_format.is_none() {
+            long_format = Some(BuiltinFormatKind::ColorWords.to_format(settings, args)?);
+        }
+    } else if let Some(name) = &args.tool {
+        let ensure_new = |name: &str, kind: Option<BuiltinFormatKind>| {
+            let old_kind = match kind {
+                Some(kind) if kind.is_short() => short_kind,
+                _ => long_kind,
+            };
+            match old_kind {
+                Some(old) => Err(cli_error(format!(
+                    "--tool={name} cannot be used with --{old}",
+                    old = old.to_arg_name()
+                ))),
+                None => Ok(()),
             }
+        };
+        // Report bad combination before looking up the fallback tools.
+        ensure_new(name, builtin_kind_from_tool_name(name).map_err(cli_error)?)?;
+        let tool_names = diff_tool_names(settings, Some(name))?;
+        let name = select_diff_tool_name(settings, &tool_names)?
+            .expect("tool names should include the specified tool");
+        let kind = builtin_kind_from_tool_name(name).map_err(cli_error)?;
+        ensure_new(name, kind)?;
+        let format = diff_format_for_tool(settings, args, name, kind)?;
+        if kind.is_some_and(|kind| kind.is_short()) {
+            short_format = Some(format);
         } else {
-            let tool = merge_tools::get_external_tool_config(settings, name)?
-                .unwrap_or_else(|| ExternalMergeTool::with_program(name));
-            ensure_new(long_kind)?;
-            long_format = Some(DiffFormat::Tool(Box::new(tool)));
+            long_format = Some(format);
         }
     }
     Ok([short_format, long_format])
 }
 
-fn default_diff_format(
+/// Parses the `:<name>` of a builtin format, or returns `None` if the tool
+/// name refers to an external tool.
+fn builtin_kind_from_tool_name(name: &str) -> Result<Option<BuiltinFormatKind>, String> {
+    name.strip_prefix(':')
+        .map(BuiltinFormatKind::from_name)
+        .transpose()
+}
+
+fn diff_format_for_tool(
     settings: &UserSettings,
     args: &DiffFormatArgs,
+    name: &str,
+    kind: Option<BuiltinFormatKind>,
 ) -> Result<DiffFormat, ConfigGetError> {
+    if let Some(kind) = kind {
+        kind.to_format(settings, args)
+    } else {
+        let tool = merge_tools::get_external_tool_config(settings, name)?
+            .unwrap_or_else(|| ExternalMergeTool::with_program(name));
+        Ok(DiffFormat::Tool(Box::new(tool)))
+    }
+}
+
+/// Returns the names of the diff tools to try in order: the explicitly
+/// specified `tool_name`, followed by the tools listed in `ui.diff-tools`.
+fn diff_tool_names(
+    settings: &UserSettings,
+    tool_name: Option<&str>,
+) -> Result<Vec<String>, ConfigGetError> {
+    let configured_names: Vec<String> = settings
+        .get("ui.diff-tools")
+        .optional()?
+        .unwrap_or_default();
+    Ok(tool_name
+        .map(str::to_owned)
+        .into_iter()
+        .chain(configured_names)
+        .collect())
+}
+
+/// Picks the first of the `tool_names` that can be run.
+///
+/// Builtin formats, whose names start with `:`, are always available. The
+/// program of an external tool is looked up in `[merge-tools.<name>]`, and
+/// must exist as an executable file or be found in `$PATH`. Returns `None` if
+/// no tool names are given.
+fn select_diff_tool_name<'a>(
+    settings: &UserSettings,
+    tool_names: &'a [String],
+) -> Result<Option<&'a str>, CommandError> {
+    if tool_names.is_empty() {
+        return Ok(None);
+    }
+    for name in tool_names {
+        if name.starts_with(':') {
+            return Ok(Some(name));
+        }
+        let program = merge_tools::get_external_tool_config(settings, name)?
+            .map_or_else(|| name.clone(), |tool| tool.program);
+        if is_program_available(Path::new(&program)) {
+            return Ok(Some(name));
+        }
+    }
+    Err(user_error_with_hint(
+        format!(
+            "None of the tools could be found: {}",
+            tool_names.join(", ")
+        ),
+        "Use --builtin to show the diff in the builtin format.",
+    ))
+}
+
+/// Returns true if the program exists as an executable file path or can be
+/// found in `$PATH`.
+///
+/// This follows the lookup of `std::process::Command`: on Windows, ".exe" is
+/// appended to the program name if it has no extension.
+fn is_program_available(program: &Path) -> bool {
+    let is_executable_file = |path: &Path| {
+        let path = if cfg!(windows) && path.extension().is_none() {
+            path.with_extension("exe")
+        } else {
+            path.to_owned()
+        };
+        let Ok(metadata) = path.metadata() else {
+            return false;
+        };
+        #[cfg(unix)]
+        let executable = metadata.permissions().mode() & 0o111 != 0;
+        #[cfg(not(unix))]
+        let executable = true;
+        metadata.is_file() && executable
+    };
+    if program.components().nth(1).is_some() {
+        return is_executable_file(program);
+    }
+    let Some(paths) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&paths).any(|dir| is_executable_file(&dir.join(program)))
+}
+
+fn default_diff_format(
+    settings: &UserSettings,
+    args: &DiffFormatArgs,
+) -> Result<DiffFormat, CommandError> {
+    if args.builtin {
+        return Ok(BuiltinFormatKind::ColorWords.to_format(settings, args)?);
+    }
+    let tool_names = diff_tool_names(settings, None)?;
+    if let Some(name) = select_diff_tool_name(settings, &tool_names)? {
+        let kind = builtin_kind_from_tool_name(name).map_err(|err| ConfigGetError::Type {
+            name: "ui.diff-tools".to_owned(),
+            error: err.into(),
+            source_path: None,
+        })?;
+        return Ok(diff_format_for_tool(settings, args, name, kind)?);
+    }
     let tool_args: CommandNameAndArgs = settings.get("ui.diff-formatter")?;
     if let Some(name) = tool_args.as_str().and_then(|s| s.strip_prefix(':')) {
-        BuiltinFormatKind::from_name(name)
+        Ok(BuiltinFormatKind::from_name(name)
             .map_err(|err| ConfigGetError::Type {
                 name: "ui.diff-formatter".to_owned(),
                 error: err.into(),
                 source_path: None,
             })?
-            .to_format(settings, args)
+            .to_format(settings, args)?)
     } else {
         let tool = if let Some(name) = tool_args.as_str() {
             merge_tools::get_external_tool_config(settings, name)?
@@ -366,6 +547,7 @@ pub struct DiffRenderer<'a> {
     path_converter: &'a RepoPathUiConverter,
     conflict_marker_style: ConflictMarkerStyle,
     formats: Vec<DiffFormat>,
+    change_types: Vec<DiffChangeType>,
 }
 
 impl<'a> DiffRenderer<'a> {
@@ -380,7 +562,44 @@ impl<'a> DiffRenderer<'a> {
             path_converter,
             conflict_marker_style,
             formats,
+            change_types: vec![],
+        }
+    }
+
+    /// Restricts the diff to files of the given `change_types`. All files are
+    /// shown if empty.
+    pub fn with_change_types(mut self, change_types: Vec<DiffChangeType>) -> Self {
+        self.change_types = change_types;
+        self
+    }
+
+    /// Computes the diff of files matching the `matcher` and the change types
+    /// of this renderer.
+    pub fn diff_stream<'b>(
+        &'b self,
+        from_tree: &MergedTree,
+        to_tree: &MergedTree,
+        matcher: &'b dyn Matcher,
+        copy_records: &'b CopyRecords,
+    ) -> BoxStream<'b, CopiesTreeDiffEntry> {
+        let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+        if self.change_types.is_empty() {
+            return tree_diff;
         }
+        let change_types = &self.change_types;
+        tree_diff
+            .filter(move |entry| {
+                let shown = match &entry.values {
+                    Ok((before, after)) => {
+                        let change_type = DiffChangeType::of(&entry.path, before, after);
+                        change_types.contains(&change_type)
+                    }
+                    // Let the error be reported by the formatter
+                    Err(_) => true,
+                };
+                future::ready(shown)
+            })
+            .boxed()
     }
 
     /// Generates diff between `from_tree` and `to_tree`.
@@ -425,43 +644,54 @@ impl<'a> DiffRenderer<'a> {
         for format in &self.formats {
             match format {
                 DiffFormat::Summary => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    let tree_diff = self.diff_stream(from_tree, to_tree, matcher, copy_records);
                     show_diff_summary(formatter, tree_diff, path_converter).await?;
                 }
                 DiffFormat::Stat(options) => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    let tree_diff = self.diff_stream(from_tree, to_tree, matcher, copy_records);
                     let stats =
                         DiffStats::calculate(store, tree_diff, options, self.conflict_marker_style)
                             .block_on()?;
-                    show_diff_stats(formatter, &stats, path_converter, width)?;
+                    show_diff_stats(formatter, &stats, path_converter, width, options.style)?;
                 }
                 DiffFormat::Types => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    let tree_diff = self.diff_stream(from_tree, to_tree, matcher, copy_records);
                     show_types(formatter, tree_diff, path_converter).await?;
                 }
                 DiffFormat::NameOnly => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    let tree_diff = self.diff_stream(from_tree, to_tree, matcher, copy_records);
                     show_names(formatter, tree_diff, path_converter).await?;
                 }
                 DiffFormat::Git(options) => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    show_git_diff(
-                        formatter,
-                        store,
-                        tree_diff,
-                        options,
-                        self.conflict_marker_style,
-                    )
-                    .await?;
+                    let tree_diff = self.diff_stream(from_tree, to_tree, matcher, copy_records);
+                    if let Some(highlighter) = &options.highlighter {
+                        let mut diff = vec![];
+                        show_git_diff(
+                            &mut PlainTextFormatter::new(&mut diff),
+                            store,
+                            tree_diff,
+                            options,
+                            self.conflict_marker_style,
+                        )
+                        .await?;
+                        if !diff.is_empty() {
+                            let mut writer = formatter.raw()?;
+                            invoke_diff_highlighter(ui, writer.as_mut(), highlighter, &diff)
+                                .map_err(DiffRenderError::DiffGenerate)?;
+                        }
+                    } else {
+                        show_git_diff(
+                            formatter,
+                            store,
+                            tree_diff,
+                            options,
+                            self.conflict_marker_style,
+                        )
+                        .await?;
+                    }
                 }
                 DiffFormat::ColorWords(options) => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    let tree_diff = self.diff_stream(from_tree, to_tree, matcher, copy_records);
                     show_color_words_diff(
                         formatter,
                         store,
@@ -476,7 +706,7 @@ impl<'a> DiffRenderer<'a> {
                     match tool.diff_invocation_mode {
                         DiffToolMode::FileByFile => {
                             let tree_diff =
-                                from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                                self.diff_stream(from_tree, to_tree, matcher, copy_records);
                             show_file_by_file_diff(
                                 ui,
                                 formatter,
@@ -489,6 +719,24 @@ impl<'a> DiffRenderer<'a> {
                             .await
                         }
                         DiffToolMode::Dir => {
+                            // The tool can only be restricted to the paths of
+                            // the files of the requested change types.
+                            let files_matcher;
+                            let matcher = if self.change_types.is_empty() {
+                                matcher
+                            } else {
+                                let paths: Vec<_> = self
+                                    .diff_stream(from_tree, to_tree, matcher, copy_records)
+                                    .flat_map(|entry| {
+                                        let paths = [entry.path.source(), entry.path.target()]
+                                            .map(|path| path.to_owned());
+                                        stream::iter(paths)
+                                    })
+                                    .collect()
+                                    .await;
+                                files_matcher = FilesMatcher::new(paths);
+                                &files_matcher
+                            };
                             let mut writer = formatter.raw()?;
                             generate_diff(
                                 ui,
@@ -560,6 +808,50 @@ impl<'a> DiffRenderer<'a> {
             width,
         )
     }
+
+    /// Generates line diff of the given commit descriptions if any of the
+    /// formats shows file contents.
+    pub fn show_description_diff(
+        &self,
+        formatter: &mut dyn Formatter,
+        from_description: &str,
+        to_description: &str,
+    ) -> io::Result<()> {
+        let mut context = None;
+        for format in &self.formats {
+            match format {
+                DiffFormat::Git(options) => context = Some(options.context),
+                DiffFormat::ColorWords(options) => context = Some(options.context),
+                // External tools can't tell the default number of lines.
+                DiffFormat::Tool(_) => context = context.or(Some(3)),
+                DiffFormat::Summary
+                | DiffFormat::Stat(_)
+                | DiffFormat::Types
+                | DiffFormat::NameOnly => {}
+            }
+        }
+        let Some(context) = context else {
+            return Ok(());
+        };
+        let options = UnifiedDiffOptions {
+            context,
+            line_diff: LineDiffOptions::default(),
+            color_moved: ColorMovedMode::No,
+            max_file_bytes: None,
+            function_context: FunctionContextPatterns::default(),
+            highlighter: None,
+            binary: false,
+        };
+        formatter.with_label("diff", |formatter| {
+            writeln!(formatter.labeled("header"), "Modified commit description:")?;
+            show_unified_diff_hunks(
+                formatter,
+                [from_description.into(), to_description.into()],
+                &options,
+                None,
+            )
+        })
+    }
 }
 
 pub fn get_copy_records<'a>(
@@ -574,6 +866,20 @@ pub fn get_copy_records<'a>(
     Ok(block_on_stream(stream).filter_ok(|record| matcher.matches(&record.target)))
 }
 
+/// Lists the paths changed between the two trees.
+///
+/// Only the trees are read, so file contents aren't loaded.
+pub fn changed_paths(
+    from_tree: &MergedTree,
+    to_tree: &MergedTree,
+) -> BackendResult<Vec<RepoPathBuf>> {
+    from_tree
+        .diff_stream(to_tree, &EverythingMatcher)
+        .map(|entry| entry.values.map(|_| entry.path))
+        .try_collect()
+        .block_on()
+}
+
 /// How conflicts are processed and rendered in diffs.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -636,6 +942,67 @@ fn diff_by_line<'input, T: AsRef<[u8]> + ?Sized + 'input>(
     }
 }
 
+/// How lines moved within a file are highlighted.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorMovedMode {
+    /// Don't highlight moved lines
+    #[default]
+    No,
+    /// Highlight every non-blank line that was moved
+    Plain,
+}
+
+/// Set of lines which were removed from one place and added to another place
+/// of the same file.
+#[derive(Debug, Default)]
+struct MovedLines<'content> {
+    lines: HashSet<&'content [u8]>,
+}
+
+impl<'content> MovedLines<'content> {
+    fn new(
+        removed_lines: impl IntoIterator<Item = &'content [u8]>,
+        added_lines: impl IntoIterator<Item = &'content [u8]>,
+    ) -> Self {
+        // Blank lines are too common to be considered moved.
+        let to_key = |line: &'content [u8]| {
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            (!line.trim_ascii().is_empty()).then_some(line)
+        };
+        let removed: HashSet<_> = removed_lines.into_iter().filter_map(to_key).collect();
+        let lines = added_lines
+            .into_iter()
+            .filter_map(to_key)
+            .filter(|line| removed.contains(line))
+            .collect();
+        MovedLines { lines }
+    }
+
+    /// Collects moved lines from the `Different` hunks of the line diff.
+    fn from_line_diff(line_diff: &Diff<'content>) -> Self {
+        let mut removed_lines = vec![];
+        let mut added_lines = vec![];
+        for hunk in line_diff.hunks() {
+            if hunk.kind == DiffHunkKind::Different {
+                let [left, right]: [&'content BStr; 2] = hunk.contents[..].try_into().unwrap();
+                removed_lines.extend(left.split_inclusive(|b| *b == b'\n'));
+                added_lines.extend(right.split_inclusive(|b| *b == b'\n'));
+            }
+        }
+        Self::new(removed_lines, added_lines)
+    }
+
+    /// Returns true if the line consisting of the given fragments was moved.
+    fn contains<'a>(&self, fragments: impl IntoIterator<Item = &'a [u8]>) -> bool {
+        if self.lines.is_empty() {
+            return false;
+        }
+        let line = fragments.into_iter().collect_vec().concat();
+        let line = line.strip_suffix(b"\n").unwrap_or(&line);
+        self.lines.contains(line)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ColorWordsDiffOptions {
     /// How conflicts are processed and rendered.
@@ -646,6 +1013,10 @@ pub struct ColorWordsDiffOptions {
     pub line_diff: LineDiffOptions,
     /// Maximum number of removed/added word alternation to inline.
     pub max_inline_alternation: Option<usize>,
+    /// How lines moved within a file are highlighted.
+    pub color_moved: ColorMovedMode,
+    /// Maximum size of the hunks to show per file.
+    pub max_file_bytes: Option<usize>,
 }
 
 impl ColorWordsDiffOptions {
@@ -666,6 +1037,8 @@ impl ColorWordsDiffOptions {
             context: settings.get("diff.color-words.context")?,
             line_diff: LineDiffOptions::default(),
             max_inline_alternation,
+            color_moved: ColorMovedMode::No,
+            max_file_bytes: None,
         })
     }
 
@@ -674,6 +1047,9 @@ impl ColorWordsDiffOptions {
             self.context = context;
         }
         self.line_diff.merge_args(args);
+        if let Some(mode) = args.color_moved {
+            self.color_moved = mode;
+        }
     }
 }
 
@@ -747,7 +1123,14 @@ fn show_color_words_conflict_hunks(
                     (hunk.lefts.as_resolved(), hunk.rights.as_resolved())
                 {
                     let contents = [left, right];
-                    show_color_words_diff_lines(formatter, contents, line_number, labels, options)?
+                    show_color_words_diff_lines(
+                        formatter,
+                        contents,
+                        line_number,
+                        labels,
+                        options,
+                        &MovedLines::default(),
+                    )?
                 } else {
                     show_color_words_unresolved_hunk(
                         formatter,
@@ -842,6 +1225,10 @@ fn show_color_words_resolved_hunks(
     options: &ColorWordsDiffOptions,
 ) -> io::Result<DiffLineNumber> {
     let line_diff = diff_by_line(contents, &options.line_diff);
+    let moved_lines = match options.color_moved {
+        ColorMovedMode::No => MovedLines::default(),
+        ColorMovedMode::Plain => MovedLines::from_line_diff(&line_diff),
+    };
     // Matching entries shouldn't appear consecutively in diff of two inputs.
     let mut context: Option<[&BStr; 2]> = None;
     let mut emitted = false;
@@ -872,6 +1259,7 @@ fn show_color_words_resolved_hunks(
                     line_number,
                     labels,
                     options,
+                    &moved_lines,
                 )?;
             }
         }
@@ -939,6 +1327,7 @@ fn show_color_words_context_lines(
                 line_number,
                 labels,
                 options,
+                &MovedLines::default(),
             )
         }
     };
@@ -971,6 +1360,7 @@ fn show_color_words_diff_lines(
     mut line_number: DiffLineNumber,
     labels: [&str; 2],
     options: &ColorWordsDiffOptions,
+    moved_lines: &MovedLines,
 ) -> io::Result<DiffLineNumber> {
     let word_diff_hunks = Diff::by_word(contents).hunks().collect_vec();
     let can_inline = match options.max_inline_alternation {
@@ -985,32 +1375,65 @@ fn show_color_words_diff_lines(
         let mut diff_line_iter =
             DiffLineIterator::with_line_number(word_diff_hunks.iter(), line_number);
         for diff_line in diff_line_iter.by_ref() {
-            show_color_words_line_number(
-                formatter,
-                [
-                    diff_line
-                        .has_left_content()
-                        .then_some(diff_line.line_number.left),
-                    diff_line
-                        .has_right_content()
-                        .then_some(diff_line.line_number.right),
-                ],
-                labels,
-            )?;
-            show_color_words_inline_hunks(formatter, &diff_line.hunks, labels)?;
+            let show_line = |formatter: &mut dyn Formatter| {
+                show_color_words_line_number(
+                    formatter,
+                    [
+                        diff_line
+                            .has_left_content()
+                            .then_some(diff_line.line_number.left),
+                        diff_line
+                            .has_right_content()
+                            .then_some(diff_line.line_number.right),
+                    ],
+                    labels,
+                )?;
+                show_color_words_inline_hunks(formatter, &diff_line.hunks, labels)
+            };
+            let single_sided = diff_line.hunks.iter().map(|(side, _)| side).all_equal()
+                && diff_line.hunks[0].0 != DiffLineHunkSide::Both;
+            let moved = single_sided
+                && moved_lines.contains(diff_line.hunks.iter().map(|&(_, data)| -> &[u8] { data }));
+            if moved {
+                formatter.with_label("moved", show_line)?;
+            } else {
+                show_line(formatter)?;
+            }
         }
         line_number = diff_line_iter.next_line_number();
     } else {
         let [left_lines, right_lines] = unzip_diff_hunks_to_lines(&word_diff_hunks);
         let [left_label, right_label] = labels;
+        let show_line = |formatter: &mut dyn Formatter,
+                         line_numbers: [Option<u32>; 2],
+                         tokens: &DiffTokenVec,
+                         label: &str| {
+            let show_line = |formatter: &mut dyn Formatter| {
+                show_color_words_line_number(formatter, line_numbers, labels)?;
+                show_color_words_single_sided_line(formatter, tokens, label)
+            };
+            if moved_lines.contains(tokens.iter().map(|(_, content)| *content)) {
+                formatter.with_label("moved", show_line)
+            } else {
+                show_line(formatter)
+            }
+        };
         for tokens in &left_lines {
-            show_color_words_line_number(formatter, [Some(line_number.left), None], labels)?;
-            show_color_words_single_sided_line(formatter, tokens, left_label)?;
+            show_line(
+                formatter,
+                [Some(line_number.left), None],
+                tokens,
+                left_label,
+            )?;
             line_number.left += 1;
         }
         for tokens in &right_lines {
-            show_color_words_line_number(formatter, [None, Some(line_number.right)], labels)?;
-            show_color_words_single_sided_line(formatter, tokens, right_label)?;
+            show_line(
+                formatter,
+                [None, Some(line_number.right)],
+                tokens,
+                right_label,
+            )?;
             line_number.right += 1;
         }
     }
@@ -1234,6 +1657,49 @@ fn basic_diff_file_type(value: &MaterializedTreeValue) -> &'static str {
     }
 }
 
+/// Writes the output of `write` to the `formatter`, truncating it at a line
+/// boundary if it exceeds `max_bytes`. The `indent` is prepended to the
+/// truncation marker.
+fn write_with_size_limit(
+    formatter: &mut dyn Formatter,
+    max_bytes: Option<usize>,
+    indent: &str,
+    write: impl FnOnce(&mut dyn Formatter) -> io::Result<()>,
+) -> io::Result<()> {
+    let Some(max_bytes) = max_bytes else {
+        return write(formatter);
+    };
+    let mut recorder = FormatRecorder::new();
+    write(&mut recorder)?;
+    let data = recorder.data();
+    if data.len() <= max_bytes {
+        return recorder.replay(formatter);
+    }
+    let end = data[..max_bytes].rfind_byte(b'\n').map_or(0, |pos| pos + 1);
+    recorder.replay_with(formatter, |formatter, range| {
+        formatter.write_all(&data[range.start.min(end)..range.end.min(end)])
+    })?;
+    writeln!(
+        formatter.labeled("truncated"),
+        "{indent}... (patch truncated)"
+    )
+}
+
+/// Limits the size of each file's patch in the Git and color-words formats.
+pub fn set_max_patch_bytes(formats: &mut [DiffFormat], max_bytes: usize) {
+    for format in formats {
+        match format {
+            DiffFormat::Git(options) => options.max_file_bytes = Some(max_bytes),
+            DiffFormat::ColorWords(options) => options.max_file_bytes = Some(max_bytes),
+            DiffFormat::Summary
+            | DiffFormat::Stat(_)
+            | DiffFormat::Types
+            | DiffFormat::NameOnly
+            | DiffFormat::Tool(_) => {}
+        }
+    }
+}
+
 pub async fn show_color_words_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
@@ -1282,12 +1748,14 @@ pub async fn show_color_words_diff(
             } else if right_content.is_binary {
                 writeln!(formatter.labeled("binary"), "    (binary)")?;
             } else {
-                show_color_words_diff_hunks(
-                    formatter,
-                    [&empty_content(), &right_content.contents],
-                    options,
-                    conflict_marker_style,
-                )?;
+                write_with_size_limit(formatter, options.max_file_bytes, "    ", |formatter| {
+                    show_color_words_diff_hunks(
+                        formatter,
+                        [&empty_content(), &right_content.contents],
+                        options,
+                        conflict_marker_style,
+                    )
+                })?;
             }
         } else if right_value.is_present() {
             let description = match (&left_value, &right_value) {
@@ -1349,12 +1817,14 @@ pub async fn show_color_words_diff(
             if left_content.is_binary || right_content.is_binary {
                 writeln!(formatter.labeled("binary"), "    (binary)")?;
             } else if left_content.contents != right_content.contents {
-                show_color_words_diff_hunks(
-                    formatter,
-                    [&left_content.contents, &right_content.contents],
-                    options,
-                    conflict_marker_style,
-                )?;
+                write_with_size_limit(formatter, options.max_file_bytes, "    ", |formatter| {
+                    show_color_words_diff_hunks(
+                        formatter,
+                        [&left_content.contents, &right_content.contents],
+                        options,
+                        conflict_marker_style,
+                    )
+                })?;
             }
         } else {
             let description = basic_diff_file_type(&left_value);
@@ -1368,12 +1838,14 @@ pub async fn show_color_words_diff(
             } else if left_content.is_binary {
                 writeln!(formatter.labeled("binary"), "    (binary)")?;
             } else {
-                show_color_words_diff_hunks(
-                    formatter,
-                    [&left_content.contents, &empty_content()],
-                    options,
-                    conflict_marker_style,
-                )?;
+                write_with_size_limit(formatter, options.max_file_bytes, "    ", |formatter| {
+                    show_color_words_diff_hunks(
+                        formatter,
+                        [&left_content.contents, &empty_content()],
+                        options,
+                        conflict_marker_style,
+                    )
+                })?;
             }
         }
     }
@@ -1460,9 +1932,9 @@ fn git_diff_part(
     value: MaterializedTreeValue,
     conflict_marker_style: ConflictMarkerStyle,
 ) -> Result<GitDiffPart, DiffRenderError> {
-    const DUMMY_HASH: &str = "0000000000";
+    const DUMMY_HASH: &str = "0000000000000000000000000000000000000000";
     let mode;
-    let mut hash;
+    let hash;
     let content;
     match value {
         MaterializedTreeValue::Absent => {
@@ -1527,7 +1999,6 @@ fn git_diff_part(
             panic!("Unexpected tree in diff at path {path:?}");
         }
     }
-    hash.truncate(10);
     Ok(GitDiffPart {
         mode: Some(mode),
         hash,
@@ -1541,6 +2012,16 @@ pub struct UnifiedDiffOptions {
     pub context: usize,
     /// How lines are tokenized and compared.
     pub line_diff: LineDiffOptions,
+    /// How lines moved within a file are highlighted.
+    pub color_moved: ColorMovedMode,
+    /// Maximum size of the hunks to show per file.
+    pub max_file_bytes: Option<usize>,
+    /// Patterns to find the enclosing function shown in hunk headers.
+    pub function_context: FunctionContextPatterns,
+    /// Command to pipe the diff through.
+    pub highlighter: Option<CommandNameAndArgs>,
+    /// Whether to emit binary patches and full object ids.
+    pub binary: bool,
 }
 
 impl UnifiedDiffOptions {
@@ -1548,6 +2029,11 @@ impl UnifiedDiffOptions {
         Ok(UnifiedDiffOptions {
             context: settings.get("diff.git.context")?,
             line_diff: LineDiffOptions::default(),
+            color_moved: ColorMovedMode::No,
+            max_file_bytes: None,
+            function_context: FunctionContextPatterns::from_settings(settings)?,
+            highlighter: settings.get("ui.diff-highlighter").optional()?,
+            binary: false,
         })
     }
 
@@ -1555,10 +2041,85 @@ impl UnifiedDiffOptions {
         if let Some(context) = args.context {
             self.context = context;
         }
+        if let Some(highlighter) = &args.highlighter {
+            self.highlighter = Some(highlighter.into());
+        }
         self.line_diff.merge_args(args);
+        if let Some(mode) = args.color_moved {
+            self.color_moved = mode;
+        }
+        self.binary |= args.binary;
+    }
+}
+
+/// Regex patterns per file glob to find the line shown in hunk headers, like
+/// Git's `xfuncname`.
+#[derive(Clone, Debug, Default)]
+pub struct FunctionContextPatterns {
+    patterns: Vec<(glob::Pattern, regex::bytes::Regex)>,
+}
+
+impl FunctionContextPatterns {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let patterns = settings
+            .table_keys("diff.git.function-context")
+            .map(|glob| {
+                settings.get_value_with(["diff", "git", "function-context", glob], |value| {
+                    let pattern = glob::Pattern::new(glob)?;
+                    let regex_str = value.as_str().ok_or("Expected a string")?;
+                    let regex = regex::bytes::Regex::new(regex_str)?;
+                    Ok::<_, Box<dyn std::error::Error + Send + Sync>>((pattern, regex))
+                })
+            })
+            .try_collect()?;
+        Ok(FunctionContextPatterns { patterns })
+    }
+
+    /// Returns the regex of the first glob matching the `path`.
+    fn regex_for(&self, path: &RepoPath) -> Option<&regex::bytes::Regex> {
+        let path = path.as_internal_file_string();
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map(|(_, regex)| regex)
+    }
+}
+
+impl PartialEq for FunctionContextPatterns {
+    fn eq(&self, other: &Self) -> bool {
+        let sources = |patterns: &Self| {
+            patterns
+                .patterns
+                .iter()
+                .map(|(pattern, regex)| (pattern.as_str().to_owned(), regex.as_str().to_owned()))
+                .collect_vec()
+        };
+        sources(self) == sources(other)
     }
 }
 
+impl Eq for FunctionContextPatterns {}
+
+/// Finds the last line before `line_index` matching the `regex`, and returns
+/// the matched text.
+///
+/// If the regex has a capture group, only the first group is returned.
+fn find_function_context<'a>(
+    lines: &[&'a [u8]],
+    line_index: usize,
+    regex: &regex::bytes::Regex,
+) -> Option<&'a [u8]> {
+    lines[..line_index.min(lines.len())]
+        .iter()
+        .rev()
+        .find_map(|line| {
+            let line = line.trim_end();
+            let captures = regex.captures(line)?;
+            let matched = captures.get(1).or_else(|| captures.get(0))?;
+            Some(matched.as_bytes().trim_end())
+        })
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum DiffLineType {
     Context,
@@ -1724,6 +2285,7 @@ fn show_unified_diff_hunks(
     formatter: &mut dyn Formatter,
     contents: [&BStr; 2],
     options: &UnifiedDiffOptions,
+    function_regex: Option<&regex::bytes::Regex>,
 ) -> io::Result<()> {
     // "If the chunk size is 0, the first number is one lower than one would
     // expect." - https://www.artima.com/weblogs/viewpost.jsp?thread=164293
@@ -1740,25 +2302,55 @@ fn show_unified_diff_hunks(
         }
     }
 
+    let moved_lines = match options.color_moved {
+        ColorMovedMode::No => MovedLines::default(),
+        ColorMovedMode::Plain => {
+            MovedLines::from_line_diff(&diff_by_line(contents, &options.line_diff))
+        }
+    };
+    let left_lines = if function_regex.is_some() {
+        contents[0].split_inclusive(|b| *b == b'\n').collect_vec()
+    } else {
+        vec![]
+    };
     for hunk in unified_diff_hunks(contents, options) {
-        writeln!(
-            formatter.labeled("hunk_header"),
-            "@@ -{},{} +{},{} @@",
-            to_line_number(hunk.left_line_range.clone()),
-            hunk.left_line_range.len(),
-            to_line_number(hunk.right_line_range.clone()),
-            hunk.right_line_range.len()
-        )?;
+        formatter.with_label("hunk_header", |formatter| {
+            write!(
+                formatter,
+                "@@ -{},{} +{},{} @@",
+                to_line_number(hunk.left_line_range.clone()),
+                hunk.left_line_range.len(),
+                to_line_number(hunk.right_line_range.clone()),
+                hunk.right_line_range.len()
+            )?;
+            let function_context = function_regex.and_then(|regex| {
+                find_function_context(&left_lines, hunk.left_line_range.start, regex)
+            });
+            if let Some(function_context) = function_context {
+                write!(formatter, " ")?;
+                formatter.write_all(function_context)?;
+            }
+            writeln!(formatter)
+        })?;
         for (line_type, tokens) in &hunk.lines {
             let (label, sigil) = match line_type {
                 DiffLineType::Context => ("context", " "),
                 DiffLineType::Removed => ("removed", "-"),
                 DiffLineType::Added => ("added", "+"),
             };
-            formatter.with_label(label, |formatter| {
-                write!(formatter, "{sigil}")?;
-                show_diff_line_tokens(formatter, tokens)
-            })?;
+            let show_line = |formatter: &mut dyn Formatter| {
+                formatter.with_label(label, |formatter| {
+                    write!(formatter, "{sigil}")?;
+                    show_diff_line_tokens(formatter, tokens)
+                })
+            };
+            let moved = *line_type != DiffLineType::Context
+                && moved_lines.contains(tokens.iter().map(|(_, content)| *content));
+            if moved {
+                formatter.with_label("moved", show_line)?;
+            } else {
+                show_line(formatter)?;
+            }
             let (_, content) = tokens.last().expect("hunk line must not be empty");
             if !content.ends_with(b"\n") {
                 write!(formatter, "\n\\ No newline at end of file\n")?;
@@ -1798,8 +2390,12 @@ pub async fn show_git_diff(
         let right_path_string = right_path.as_internal_file_string();
         let (left_value, right_value) = values?;
 
-        let left_part = git_diff_part(left_path, left_value, conflict_marker_style)?;
-        let right_part = git_diff_part(right_path, right_value, conflict_marker_style)?;
+        let mut left_part = git_diff_part(left_path, left_value, conflict_marker_style)?;
+        let mut right_part = git_diff_part(right_path, right_value, conflict_marker_style)?;
+        if !options.binary {
+            left_part.hash.truncate(10);
+            right_part.hash.truncate(10);
+        }
 
         formatter.with_label("file_header", |formatter| {
             writeln!(
@@ -1854,8 +2450,13 @@ pub async fn show_git_diff(
             Some(_) => format!("b/{right_path_string}"),
             None => "/dev/null".to_owned(),
         };
-        if left_part.content.is_binary || right_part.content.is_binary {
-            // TODO: add option to emit Git binary diff
+        if (left_part.content.is_binary || right_part.content.is_binary) && options.binary {
+            write_git_binary_patch(
+                formatter,
+                &left_part.content.contents,
+                &right_part.content.contents,
+            )?;
+        } else if left_part.content.is_binary || right_part.content.is_binary {
             writeln!(
                 formatter,
                 "Binary files {left_path} and {right_path} differ"
@@ -1866,16 +2467,72 @@ pub async fn show_git_diff(
                 writeln!(formatter, "+++ {right_path}")?;
                 io::Result::Ok(())
             })?;
-            show_unified_diff_hunks(
-                formatter,
-                [&left_part.content.contents, &right_part.content.contents].map(BStr::new),
-                options,
-            )?;
+            write_with_size_limit(formatter, options.max_file_bytes, "", |formatter| {
+                show_unified_diff_hunks(
+                    formatter,
+                    [&left_part.content.contents, &right_part.content.contents].map(BStr::new),
+                    options,
+                    options.function_context.regex_for(path.target()),
+                )
+            })?;
         }
     }
     Ok(())
 }
 
+/// Writes a `GIT binary patch` block which replaces the `left` contents with
+/// the `right` contents, followed by the reverse patch.
+fn write_git_binary_patch(
+    formatter: &mut dyn Formatter,
+    left: &[u8],
+    right: &[u8],
+) -> io::Result<()> {
+    writeln!(formatter, "GIT binary patch")?;
+    write_git_binary_literal(formatter, right)?;
+    write_git_binary_literal(formatter, left)?;
+    Ok(())
+}
+
+/// Writes the zlib-compressed `contents` as a literal hunk of a Git binary
+/// patch. Each line is prefixed by a letter encoding the number of bytes it
+/// holds (`A`-`Z` for 1-26, `a`-`z` for 27-52).
+fn write_git_binary_literal(formatter: &mut dyn Formatter, contents: &[u8]) -> io::Result<()> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(contents)?;
+    let compressed = encoder.finish()?;
+    writeln!(formatter, "literal {}", contents.len())?;
+    for chunk in compressed.chunks(52) {
+        let len_char = match chunk.len() {
+            len @ 1..=26 => b'A' + (len - 1) as u8,
+            len => b'a' + (len - 27) as u8,
+        };
+        let mut line = vec![len_char];
+        encode_base85(chunk, &mut line);
+        line.push(b'\n');
+        formatter.write_all(&line)?;
+    }
+    writeln!(formatter)?;
+    Ok(())
+}
+
+/// Encodes `data` in the base85 variant used by Git binary patches. The last
+/// group is padded with zero bytes.
+fn encode_base85(data: &[u8], out: &mut Vec<u8>) {
+    const ALPHABET: &[u8; 85] =
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+    for group in data.chunks(4) {
+        let mut bytes = [0; 4];
+        bytes[..group.len()].copy_from_slice(group);
+        let mut acc = u32::from_be_bytes(bytes);
+        let mut encoded = [0; 5];
+        for c in encoded.iter_mut().rev() {
+            *c = ALPHABET[(acc % 85) as usize];
+            acc /= 85;
+        }
+        out.extend_from_slice(&encoded);
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn show_diff_summary(
     formatter: &mut dyn Formatter,
@@ -1895,6 +2552,43 @@ pub async fn show_diff_summary(
     Ok(())
 }
 
+/// Kind of change made to a file.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffChangeType {
+    /// The file was added
+    Added,
+    /// The file was removed
+    Removed,
+    /// The file was modified
+    Modified,
+    /// The file was renamed from another path
+    Renamed,
+    /// The file was copied from another path
+    Copied,
+}
+
+impl DiffChangeType {
+    fn of(
+        path: &CopiesTreeDiffEntryPath,
+        before: &MergedTreeValue,
+        after: &MergedTreeValue,
+    ) -> Self {
+        if let Some(op) = path.copy_operation() {
+            match op {
+                CopyOperation::Copy => Self::Copied,
+                CopyOperation::Rename => Self::Renamed,
+            }
+        } else {
+            match (before.is_present(), after.is_present()) {
+                (true, true) => Self::Modified,
+                (false, true) => Self::Added,
+                (true, false) => Self::Removed,
+                (false, false) => panic!("values pair must differ"),
+            }
+        }
+    }
+}
+
 pub fn diff_status_label_and_char(
     path: &CopiesTreeDiffEntryPath,
     before: &MergedTreeValue,
@@ -1919,14 +2613,31 @@ pub fn diff_status_label_and_char(
 pub struct DiffStatOptions {
     /// How lines are tokenized and compared.
     pub line_diff: LineDiffOptions,
+    /// How the changed lines of each path are rendered.
+    pub style: DiffStatStyle,
 }
 
 impl DiffStatOptions {
     fn merge_args(&mut self, args: &DiffFormatArgs) {
         self.line_diff.merge_args(args);
+        if let Some(style) = args.stat_format {
+            self.style = style;
+        }
     }
 }
 
+/// How the changed lines of each path are rendered in diff stats.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DiffStatStyle {
+    /// Show the number of changed lines and a histogram of them
+    #[default]
+    Bar,
+    /// Show the numbers of added and removed lines
+    Count,
+    /// Show only the summary line
+    None,
+}
+
 #[derive(Clone, Debug)]
 pub struct DiffStats {
     entries: Vec<DiffStatEntry>,
@@ -2013,7 +2724,16 @@ pub fn show_diff_stats(
     stats: &DiffStats,
     path_converter: &RepoPathUiConverter,
     display_width: usize,
+    style: DiffStatStyle,
 ) -> io::Result<()> {
+    if style == DiffStatStyle::None {
+        return show_diff_stats_summary(
+            formatter,
+            stats.entries().len(),
+            stats.count_total_added(),
+            stats.count_total_removed(),
+        );
+    }
     let ui_paths = stats
         .entries()
         .iter()
@@ -2047,6 +2767,15 @@ pub fn show_diff_stats(
     };
 
     for (stat, ui_path) in iter::zip(stats.entries(), &ui_paths) {
+        if style == DiffStatStyle::Count {
+            let (path, path_width) = text_util::elide_start(ui_path, "...", max_path_width);
+            let path_pad_width = max_path_width - path_width;
+            write!(formatter, "{path}{:path_pad_width$} | ", "")?;
+            write!(formatter.labeled("added"), "+{}", stat.added)?;
+            write!(formatter, " ")?;
+            writeln!(formatter.labeled("removed"), "-{}", stat.removed)?;
+            continue;
+        }
         let bar_added = (stat.added as f64 * factor).ceil() as usize;
         let bar_removed = (stat.removed as f64 * factor).ceil() as usize;
         // replace start of path with ellipsis if the path is too long
@@ -2063,9 +2792,21 @@ pub fn show_diff_stats(
         writeln!(formatter.labeled("removed"), "{}", "-".repeat(bar_removed))?;
     }
 
-    let total_added = stats.count_total_added();
-    let total_removed = stats.count_total_removed();
-    let total_files = stats.entries().len();
+    show_diff_stats_summary(
+        formatter,
+        stats.entries().len(),
+        stats.count_total_added(),
+        stats.count_total_removed(),
+    )
+}
+
+/// Writes the summary line of diff stats.
+pub fn show_diff_stats_summary(
+    formatter: &mut dyn Formatter,
+    total_files: usize,
+    total_added: usize,
+    total_removed: usize,
+) -> io::Result<()> {
     writeln!(
         formatter.labeled("stat-summary"),
         "{} file{} changed, {} insertion{}(+), {} deletion{}(-)",
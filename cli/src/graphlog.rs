@@ -94,7 +94,7 @@ impl<'writer, R> SaplingGraphLog<'writer, R> {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize)]
 #[serde(rename_all(deserialize = "kebab-case"))]
 pub enum GraphStyle {
     Ascii,
@@ -107,6 +107,13 @@ impl GraphStyle {
     pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
         settings.get("ui.graph.style")
     }
+
+    pub fn is_ascii(self) -> bool {
+        match self {
+            Self::Ascii | Self::AsciiLarge => true,
+            Self::Curved | Self::Square => false,
+        }
+    }
 }
 
 pub fn get_graphlog<'a, K: Clone + Eq + Hash + 'a>(
@@ -6,6 +6,7 @@ use std::process::Command;
 use std::process::ExitStatus;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::thread;
 
 use bstr::BString;
 use itertools::Itertools as _;
@@ -514,6 +515,43 @@ pub fn invoke_external_diff(
     Ok(())
 }
 
+/// Invokes the `highlighter` command with the `diff` as input, directing its
+/// output into `writer`.
+pub fn invoke_diff_highlighter(
+    ui: &Ui,
+    writer: &mut dyn Write,
+    highlighter: &CommandNameAndArgs,
+    diff: &[u8],
+) -> Result<(), DiffGenerateError> {
+    let mut cmd = highlighter.to_command();
+    tracing::info!(?cmd, "Invoking the diff highlighter:");
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(ui.stderr_for_child().map_err(ExternalToolError::Io)?)
+        .spawn()
+        .map_err(|source| ExternalToolError::FailedToExecute {
+            tool_binary: highlighter.split_name().into_owned(),
+            source,
+        })?;
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    let copy_result = thread::scope(|s| {
+        s.spawn(move || {
+            // The highlighter may exit without consuming the whole input.
+            stdin.write_all(diff).ok();
+        });
+        io::copy(&mut stdout, writer)
+    });
+    let exit_status = child.wait().map_err(ExternalToolError::Io)?;
+    tracing::info!(?cmd, ?exit_status, "The diff highlighter exited:");
+    if !exit_status.success() {
+        return Err(ExternalToolError::ToolAborted { exit_status }.into());
+    }
+    copy_result.map_err(ExternalToolError::Io)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
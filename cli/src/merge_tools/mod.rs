@@ -51,6 +51,7 @@ pub(crate) use self::diff_working_copies::new_utf8_temp_dir;
 use self::diff_working_copies::DiffCheckoutError;
 use self::external::edit_diff_external;
 pub use self::external::generate_diff;
+pub use self::external::invoke_diff_highlighter;
 pub use self::external::invoke_external_diff;
 pub use self::external::DiffToolMode;
 pub use self::external::ExternalMergeTool;
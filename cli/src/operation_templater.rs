@@ -13,16 +13,34 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io;
+use std::rc::Rc;
+use std::slice;
+use std::sync::Arc;
 
+use itertools::chain;
 use itertools::Itertools as _;
+use jj_lib::backend::ChangeId;
+use jj_lib::backend::CommitId;
+use jj_lib::evolution::accumulate_predecessors;
 use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OpStoreResult;
 use jj_lib::op_store::OperationId;
 use jj_lib::operation::Operation;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::refs::diff_named_ref_targets;
+use jj_lib::refs::diff_named_remote_refs;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo;
 use jj_lib::repo::RepoLoader;
+use jj_lib::revset::RevsetEvaluationError;
+use jj_lib::revset::RevsetExpression;
 use jj_lib::settings::UserSettings;
 
 use crate::template_builder;
@@ -43,7 +61,9 @@ use crate::templater::ListTemplate;
 use crate::templater::PlainTextFormattedProperty;
 use crate::templater::Template;
 use crate::templater::TemplateFormatter;
+use crate::templater::TemplatePropertyError;
 use crate::templater::TemplatePropertyExt as _;
+use crate::time_util;
 
 pub trait OperationTemplateLanguageExtension {
     fn build_fn_table(&self) -> OperationTemplateBuildFnTable;
@@ -54,8 +74,10 @@ pub trait OperationTemplateLanguageExtension {
 pub struct OperationTemplateLanguage {
     repo_loader: RepoLoader,
     current_op_id: Option<OperationId>,
+    hidden_tags: Vec<String>,
     build_fn_table: OperationTemplateBuildFnTable,
     cache_extensions: ExtensionsMap,
+    change_counts: Rc<OperationChangeCounts>,
 }
 
 impl OperationTemplateLanguage {
@@ -80,10 +102,17 @@ impl OperationTemplateLanguage {
             // Clone these to keep lifetime simple
             repo_loader: repo_loader.clone(),
             current_op_id: current_op_id.cloned(),
+            hidden_tags: vec![],
             build_fn_table,
             cache_extensions,
+            change_counts: Rc::new(OperationChangeCounts::default()),
         }
     }
+
+    /// Excludes the tag of the given key from `tags()` output.
+    pub fn hide_tag(&mut self, key: impl Into<String>) {
+        self.hidden_tags.push(key.into());
+    }
 }
 
 impl TemplateLanguage<'static> for OperationTemplateLanguage {
@@ -298,13 +327,15 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
     );
     map.insert(
         "tags",
-        |_language, _diagnostics, _build_ctx, self_property, function| {
+        |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            let out_property = self_property.map(|op| {
+            let hidden_tags = language.hidden_tags.clone();
+            let out_property = self_property.map(move |op| {
                 // TODO: introduce map type
                 op.metadata()
                     .tags
                     .iter()
+                    .filter(|(key, _)| !hidden_tags.contains(key))
                     .map(|(key, value)| format!("{key}: {value}"))
                     .join("\n")
             });
@@ -327,6 +358,35 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "time_since_previous",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let root_op_id = language.repo_loader.op_store().root_operation_id().clone();
+            let out_property = self_property.and_then(move |op| {
+                // The root operation has no meaningful time.
+                let previous_ends: Vec<_> = op
+                    .parents()
+                    .filter_ok(|parent| parent.id() != &root_op_id)
+                    .map_ok(|parent| parent.metadata().time.end)
+                    .try_collect()?;
+                let Some(previous_end) =
+                    previous_ends.into_iter().max_by_key(|time| time.timestamp)
+                else {
+                    return Ok(String::new());
+                };
+                let start = &op.metadata().time.start;
+                // Clocks may be skewed between hosts
+                let from = if previous_end.timestamp > start.timestamp {
+                    start
+                } else {
+                    &previous_end
+                };
+                Ok(time_util::format_precise_duration(from, start)?)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "user",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -347,9 +407,200 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "commit_change_count",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo_loader = language.repo_loader.clone();
+            let current_op_id = language.current_op_id.clone();
+            let change_counts = language.change_counts.clone();
+            let out_property = self_property.and_then(move |op| {
+                let count = get_or_insert_count(&change_counts.commits, &op, || {
+                    count_changed_commits(
+                        &repo_loader,
+                        current_op_id.as_ref(),
+                        &change_counts.repo,
+                        &op,
+                    )
+                })?;
+                Ok(i64::try_from(count)?)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "bookmark_change_count",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let change_counts = language.change_counts.clone();
+            let out_property = self_property.and_then(move |op| {
+                let count = get_or_insert_count(&change_counts.bookmarks, &op, || {
+                    Ok(changed_bookmark_names(&op)?.len())
+                })?;
+                Ok(i64::try_from(count)?)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
+/// Numbers of commits and bookmarks changed by operations, computed on demand.
+#[derive(Default)]
+struct OperationChangeCounts {
+    /// Repo loaded at the current operation, which is reused to look up
+    /// commits changed by the ancestor operations.
+    repo: RefCell<Option<Arc<ReadonlyRepo>>>,
+    commits: RefCell<HashMap<OperationId, usize>>,
+    bookmarks: RefCell<HashMap<OperationId, usize>>,
+}
+
+fn get_or_insert_count(
+    counts: &RefCell<HashMap<OperationId, usize>>,
+    op: &Operation,
+    compute: impl FnOnce() -> Result<usize, TemplatePropertyError>,
+) -> Result<usize, TemplatePropertyError> {
+    if let Some(&count) = counts.borrow().get(op.id()) {
+        return Ok(count);
+    }
+    let count = compute()?;
+    counts.borrow_mut().insert(op.id().clone(), count);
+    Ok(count)
+}
+
+/// Counts commits created, rewritten, or abandoned by the operation.
+///
+/// This matches the "Changed commits" section of `jj op diff`, but only
+/// commit ids are looked up. If the operation merges concurrent operations,
+/// commits are compared against all parent operations combined.
+fn count_changed_commits(
+    repo_loader: &RepoLoader,
+    current_op_id: Option<&OperationId>,
+    cached_repo: &RefCell<Option<Arc<ReadonlyRepo>>>,
+    op: &Operation,
+) -> Result<usize, TemplatePropertyError> {
+    let parent_ops: Vec<_> = op.parents().try_collect()?;
+    if parent_ops.is_empty() {
+        return Ok(0);
+    }
+    let mut from_heads = vec![];
+    for parent_op in &parent_ops {
+        from_heads.extend(parent_op.view()?.heads().iter().cloned());
+    }
+    let to_heads = op.view()?.heads().iter().cloned().collect_vec();
+
+    if cached_repo.borrow().is_none() {
+        let current_op = match current_op_id {
+            Some(id) => repo_loader.load_operation(id)?,
+            None => op.clone(),
+        };
+        *cached_repo.borrow_mut() = Some(repo_loader.load_at(&current_op)?);
+    }
+    let cached_repo = cached_repo.borrow().clone().unwrap();
+    // The operation isn't necessarily an ancestor of the current operation.
+    let index = cached_repo.index();
+    let repo = if chain(&from_heads, &to_heads).all(|id| index.has_id(id)) {
+        cached_repo
+    } else {
+        repo_loader.load_at(op)?
+    };
+
+    let predecessor_commits = accumulate_predecessors(slice::from_ref(op), &parent_ops)?;
+    let diff =
+        diff_operation_commit_ids(repo.as_ref(), from_heads, to_heads, &predecessor_commits)?;
+    Ok(diff.existing.len() + diff.abandoned.len())
+}
+
+/// Commits created, rewritten, or abandoned between two operations.
+#[derive(Clone, Debug, Default)]
+pub struct OperationCommitIdsDiff {
+    /// Created or rewritten commits, and their predecessors.
+    pub existing: Vec<(CommitId, Vec<CommitId>)>,
+    /// Abandoned commits.
+    pub abandoned: HashSet<CommitId>,
+}
+
+/// Computes created/rewritten/abandoned commits between the heads of two
+/// operations.
+///
+/// Predecessors of the new commits are looked up in the
+/// `predecessor_commits` map, or deduced from the change ids of the newly
+/// hidden commits if not recorded. `repo` should contain the commits of both
+/// operations in its index.
+pub fn diff_operation_commit_ids(
+    repo: &dyn Repo,
+    from_heads: Vec<CommitId>,
+    to_heads: Vec<CommitId>,
+    predecessor_commits: &BTreeMap<CommitId, Vec<CommitId>>,
+) -> Result<OperationCommitIdsDiff, RevsetEvaluationError> {
+    let from_expr = RevsetExpression::commits(from_heads);
+    let to_expr = RevsetExpression::commits(to_heads);
+
+    // Collect hidden commits to find abandoned/rewritten changes.
+    let mut hidden_commits_by_change: HashMap<ChangeId, CommitId> = HashMap::new();
+    let mut abandoned: HashSet<CommitId> = HashSet::new();
+    let newly_hidden = to_expr.range(&from_expr).evaluate(repo)?;
+    for item in newly_hidden.commit_change_ids() {
+        let (commit_id, change_id) = item?;
+        // Just pick one if diverged. Divergent commits shouldn't be considered
+        // "squashed" into the new commit.
+        hidden_commits_by_change
+            .entry(change_id)
+            .or_insert_with(|| commit_id.clone());
+        abandoned.insert(commit_id);
+    }
+
+    // For each new commit, copy/deduce predecessors based on change id.
+    let mut existing = vec![];
+    let newly_visible = from_expr.range(&to_expr).evaluate(repo)?;
+    for item in newly_visible.commit_change_ids() {
+        let (commit_id, change_id) = item?;
+        let predecessor_ids = if let Some(ids) = predecessor_commits.get(&commit_id) {
+            ids // including visible predecessors
+        } else if let Some(id) = hidden_commits_by_change.get(&change_id) {
+            slice::from_ref(id)
+        } else {
+            &[]
+        };
+        for id in predecessor_ids {
+            abandoned.remove(id);
+        }
+        existing.push((commit_id, predecessor_ids.to_vec()));
+    }
+
+    // Remainders are abandoned.
+    Ok(OperationCommitIdsDiff {
+        existing,
+        abandoned,
+    })
+}
+
+/// Returns names of the local and remote bookmarks changed by the operation.
+///
+/// If the operation merges concurrent operations, only bookmarks that differ
+/// from all parent operations are included.
+pub fn changed_bookmark_names(op: &Operation) -> OpStoreResult<HashSet<RefNameBuf>> {
+    let view = op.view()?;
+    let mut names: Option<HashSet<RefNameBuf>> = None;
+    for parent_op in op.parents() {
+        let parent_view = parent_op?.view()?;
+        let local_names =
+            diff_named_ref_targets(parent_view.local_bookmarks(), view.local_bookmarks())
+                .map(|(name, _)| name.to_owned());
+        let remote_names = diff_named_remote_refs(
+            parent_view.all_remote_bookmarks(),
+            view.all_remote_bookmarks(),
+        )
+        .map(|(symbol, _)| symbol.name.to_owned());
+        let changed: HashSet<_> = local_names.chain(remote_names).collect();
+        names = Some(match names {
+            Some(names) => names.intersection(&changed).cloned().collect(),
+            None => changed,
+        });
+    }
+    Ok(names.unwrap_or_default())
+}
+
 impl Template for OperationId {
     fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
         write!(formatter, "{}", self.hex())
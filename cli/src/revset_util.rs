@@ -94,6 +94,11 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
         self.expression = self.expression.intersection(other);
     }
 
+    /// Subtracts the `other` expression from the underlying expression.
+    pub fn subtract(&mut self, other: &Rc<UserRevsetExpression>) {
+        self.expression = self.expression.minus(other);
+    }
+
     /// Resolves user symbols in the expression, returns new expression.
     pub fn resolve(&self) -> Result<Rc<ResolvedRevsetExpression>, RevsetResolutionError> {
         let symbol_resolver = default_symbol_resolver(
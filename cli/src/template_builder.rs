@@ -1230,16 +1230,20 @@ fn builtin_timestamp_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
     let mut map = TemplateBuildMethodFnMap::<L, Timestamp>::new();
     map.insert(
         "ago",
-        |_language, _diagnostics, _build_ctx, self_property, function| {
+        |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            let now = Timestamp::now();
-            let format = timeago::Formatter::new();
+            let now = language
+                .settings()
+                .current_timestamp()
+                .unwrap_or_else(Timestamp::now);
             let out_property = self_property.and_then(move |timestamp| {
-                Ok(time_util::format_duration(&timestamp, &now, &format)?)
+                Ok(time_util::format_relative_timestamp(&timestamp, &now)?)
             });
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    let ago = map["ago"];
+    map.insert("relative", ago);
     map.insert(
         "format",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1334,14 +1338,10 @@ fn builtin_timestamp_range_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
             function.expect_no_arguments()?;
             // TODO: Introduce duration type, and move formatting to it.
             let out_property = self_property.and_then(|time_range| {
-                let mut f = timeago::Formatter::new();
-                f.min_unit(timeago::TimeUnit::Microseconds).ago("");
-                let duration = time_util::format_duration(&time_range.start, &time_range.end, &f)?;
-                if duration == "now" {
-                    Ok("less than a microsecond".to_owned())
-                } else {
-                    Ok(duration)
-                }
+                Ok(time_util::format_precise_duration(
+                    &time_range.start,
+                    &time_range.end,
+                )?)
             });
             Ok(out_property.into_dyn_wrapped())
         },
@@ -2073,6 +2073,8 @@ mod tests {
     use std::iter;
 
     use jj_lib::backend::MillisSinceEpoch;
+    use jj_lib::config::ConfigLayer;
+    use jj_lib::config::ConfigSource;
     use jj_lib::config::StackedConfig;
 
     use super::*;
@@ -2665,6 +2667,7 @@ mod tests {
         insta::assert_snapshot!(env.render_ok(r#""a\nb\nc".lines().len()"#), @"3");
 
         insta::assert_snapshot!(env.render_ok(r#""".lines().join("|")"#), @"");
+        insta::assert_snapshot!(env.render_ok(r#""a".lines().join("|")"#), @"a");
         insta::assert_snapshot!(env.render_ok(r#""a\nb\nc".lines().join("|")"#), @"a|b|c");
         // Null separator
         insta::assert_snapshot!(env.render_ok(r#""a\nb\nc".lines().join("\0")"#), @"a\0b\0c");
@@ -2676,10 +2679,19 @@ mod tests {
         insta::assert_snapshot!(
             env.render_ok(r#""a\nbb\nc".lines().filter(|s| s.len() == 1)"#),
             @"a c");
+        insta::assert_snapshot!(
+            env.render_ok(r#""a\nbb\nc".lines().filter(|s| s.len() == 1).join(", ")"#),
+            @"a, c");
+        insta::assert_snapshot!(
+            env.render_ok(r#""a\nbb\nc".lines().filter(|s| s.len() > 2).join(", ")"#),
+            @"");
 
         insta::assert_snapshot!(
             env.render_ok(r#""a\nb\nc".lines().map(|s| s ++ s)"#),
             @"aa bb cc");
+        insta::assert_snapshot!(
+            env.render_ok(r#""a\nb\nc".lines().map(|s| s.upper()).join(", ")"#),
+            @"A, B, C");
         // Global keyword in item template
         insta::assert_snapshot!(
             env.render_ok(r#""a\nb\nc".lines().map(|s| s ++ empty)"#),
@@ -3050,6 +3062,29 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn test_timestamp_relative_method() {
+        let mut config = StackedConfig::with_defaults();
+        let mut layer = ConfigLayer::empty(ConfigSource::User);
+        layer
+            .set_value("debug.current-timestamp", "1970-01-01T02:00:00+00:00")
+            .unwrap();
+        config.add_layer(layer);
+        let mut env = TestTemplateEnv::with_config(config);
+        env.add_keyword("t0", || literal(new_timestamp(0, 0)));
+        env.add_keyword("t1", || literal(new_timestamp(7_200_000, 0)));
+        env.add_keyword("t2", || {
+            literal(new_timestamp(3 * 86_400_000 + 7_200_000, 0))
+        });
+
+        insta::assert_snapshot!(env.render_ok("t0.ago()"), @"2 hours ago");
+        insta::assert_snapshot!(env.render_ok("t1.ago()"), @"now");
+        insta::assert_snapshot!(env.render_ok("t2.ago()"), @"in 3 days");
+        insta::assert_snapshot!(env.render_ok("t0.relative()"), @"2 hours ago");
+        insta::assert_snapshot!(env.render_ok("t1.relative()"), @"now");
+        insta::assert_snapshot!(env.render_ok("t2.relative()"), @"in 3 days");
+    }
+
     #[test]
     fn test_fill_function() {
         let mut env = TestTemplateEnv::new();
@@ -172,6 +172,12 @@ impl TemplateParseError {
         self
     }
 
+    /// Attaches the path of the file the template was loaded from.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.pest_error = Box::new(self.pest_error.with_path(path));
+        self
+    }
+
     pub fn expected_type(expected: &str, actual: &str, span: pest::Span<'_>) -> Self {
         let message =
             format!("Expected expression of type `{expected}`, but actual type is `{actual}`");
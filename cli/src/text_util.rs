@@ -15,6 +15,7 @@
 use std::borrow::Cow;
 use std::cmp;
 use std::io;
+use std::ops::Range;
 
 use bstr::ByteSlice as _;
 use unicode_width::UnicodeWidthChar as _;
@@ -541,6 +542,33 @@ pub fn write_wrapped(
     })
 }
 
+/// Writes recorded content, labeling the given byte `ranges` with `label` on
+/// top of the recorded labels.
+///
+/// The `ranges` should be sorted and non-overlapping.
+pub fn write_highlighted(
+    formatter: &mut dyn Formatter,
+    recorded_content: &FormatRecorder,
+    ranges: &[Range<usize>],
+    label: &str,
+) -> io::Result<()> {
+    let data = recorded_content.data();
+    recorded_content.replay_with(formatter, |formatter, data_range| {
+        let mut pos = data_range.start;
+        let overlapping = ranges
+            .iter()
+            .filter(|range| range.start < data_range.end && data_range.start < range.end);
+        for range in overlapping {
+            let start = cmp::max(data_range.start, range.start);
+            let end = cmp::min(data_range.end, range.end);
+            formatter.write_all(&data[pos..start])?;
+            formatter.with_label(label, |formatter| formatter.write_all(&data[start..end]))?;
+            pos = end;
+        }
+        formatter.write_all(&data[pos..data_range.end])
+    })
+}
+
 pub fn parse_author(author: &str) -> Result<(String, String), &'static str> {
     let re = regex::Regex::new(r"(?<name>.*?)\s*<(?<email>.+)>$").unwrap();
     let captures = re.captures(author).ok_or("Invalid author string")?;
@@ -1539,6 +1567,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_write_highlighted() {
+        let mut recorder = FormatRecorder::new();
+        write!(recorder, "foo ").unwrap();
+        recorder.push_label("red").unwrap();
+        write!(recorder, "bar baz").unwrap();
+        recorder.pop_label().unwrap();
+        write!(recorder, " qux").unwrap();
+
+        // Highlighted range spanning labeled and unlabeled chunks
+        let ranges = [2..5, 8..13];
+        insta::assert_snapshot!(
+            format_colored(|formatter| write_highlighted(formatter, &recorder, &ranges, "cyan")),
+            @"fo[38;5;6mo [39m[38;5;6mb[38;5;1mar [38;5;6mbaz[39m[38;5;6m q[39mux"
+        );
+        insta::assert_snapshot!(
+            format_plain_text(|formatter| write_highlighted(formatter, &recorder, &ranges, "cyan")),
+            @"foo bar baz qux"
+        );
+
+        // No ranges
+        insta::assert_snapshot!(
+            format_colored(|formatter| write_highlighted(formatter, &recorder, &[], "cyan")),
+            @"foo [38;5;1mbar baz[39m qux"
+        );
+    }
+
     #[test]
     fn test_parse_author() {
         let expected_name = "Example";
@@ -67,3 +67,36 @@ pub fn format_duration(
         .map_err(|_: chrono::OutOfRangeError| TimestampOutOfRange)?;
     Ok(format.convert(duration))
 }
+
+/// Formats the timestamp relative to `now`, e.g. "2 hours ago" or "in 3 days".
+pub fn format_relative_timestamp(
+    timestamp: &Timestamp,
+    now: &Timestamp,
+) -> Result<String, TimestampOutOfRange> {
+    let mut format = timeago::Formatter::new();
+    if timestamp.timestamp <= now.timestamp {
+        return format_duration(timestamp, now, &format);
+    }
+    format.ago("");
+    let duration = format_duration(now, timestamp, &format)?;
+    if duration == "now" {
+        Ok(duration)
+    } else {
+        Ok(format!("in {duration}"))
+    }
+}
+
+/// Formats the duration between the timestamps down to microseconds.
+pub fn format_precise_duration(
+    from: &Timestamp,
+    to: &Timestamp,
+) -> Result<String, TimestampOutOfRange> {
+    let mut f = timeago::Formatter::new();
+    f.min_unit(timeago::TimeUnit::Microseconds).ago("");
+    let duration = format_duration(from, to, &f)?;
+    if duration == "now" {
+        Ok("less than a microsecond".to_owned())
+    } else {
+        Ok(duration)
+    }
+}
@@ -327,7 +327,7 @@ fn test_bookmark_move() {
     work_dir.run_jj(["bookmark", "delete", "foo"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     foo (deleted)
-      @origin: qpvuntsm 5f3ceb1e (empty) commit
+      @origin [tracked]: qpvuntsm 5f3ceb1e (empty) commit
     [EOF]
     ");
 
@@ -350,7 +350,7 @@ fn test_bookmark_move() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     foo: mzvwutvl 91b59745 (empty) (no description set)
-      @origin (behind by 1 commits): qpvuntsm 5f3ceb1e (empty) commit
+      @origin [tracked] (behind by 1 commits): qpvuntsm 5f3ceb1e (empty) commit
     [EOF]
     ");
 
@@ -367,7 +367,7 @@ fn test_bookmark_move() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     foo: mzvwutvl 91b59745 (empty) (no description set)
-    foo@origin: qpvuntsm 5f3ceb1e (empty) commit
+    foo@origin [untracked]: qpvuntsm 5f3ceb1e (empty) commit
     [EOF]
     ");
 }
@@ -500,6 +500,48 @@ fn test_bookmark_move_matching() {
     ");
 }
 
+#[test]
+fn test_bookmark_set_expect_at() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "A"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo", "bar"])
+        .success();
+    work_dir.run_jj(["new", "-m", "B"]).success();
+
+    // The bookmarks are moved if they point to the expected revision
+    let output = work_dir.run_jj(["bookmark", "set", "-r@", "foo", "--expect-at=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Moved 1 bookmarks to zsuskuln eb4ff2c9 foo | (empty) B
+    [EOF]
+    ");
+
+    // Nothing is updated if any bookmark points elsewhere
+    let output = work_dir.run_jj(["bookmark", "set", "-r@", "bar", "foo", "--expect-at=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmark foo points to zsuskuln eb4ff2c9 foo | (empty) B, not qpvuntsm 8777db25 bar | (empty) A
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "set", "-r@", "baz", "--expect-at=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmark baz points to nothing, not qpvuntsm 8777db25 bar | (empty) A
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  point bookmark foo to commit eb4ff2c977a30aa60e925692867e9e4fdee7dbfd
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_move_conflicting() {
     let test_env = TestEnvironment::default();
@@ -822,13 +864,13 @@ fn test_bookmark_delete_glob() {
     // The deleted bookmarks are still there
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bar-2: qpvuntsm 8e056f6b (empty) commit
-      @origin: qpvuntsm 8e056f6b (empty) commit
+      @origin [tracked, synced]: qpvuntsm 8e056f6b (empty) commit
     foo-1 (deleted)
-      @origin: qpvuntsm 8e056f6b (empty) commit
+      @origin [tracked]: qpvuntsm 8e056f6b (empty) commit
     foo-3 (deleted)
-      @origin: qpvuntsm 8e056f6b (empty) commit
+      @origin [tracked]: qpvuntsm 8e056f6b (empty) commit
     foo-4 (deleted)
-      @origin: qpvuntsm 8e056f6b (empty) commit
+      @origin [tracked]: qpvuntsm 8e056f6b (empty) commit
     [EOF]
     ");
 
@@ -872,7 +914,7 @@ fn test_bookmark_delete_export() {
     let output = work_dir.run_jj(["bookmark", "list", "--all-remotes"]);
     insta::assert_snapshot!(output, @r"
     foo (deleted)
-      @git: rlvkpnrz 43444d88 (empty) (no description set)
+      @git [tracked]: rlvkpnrz 43444d88 (empty) (no description set)
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted will be deleted from the underlying Git repo on the next `jj git export`.
@@ -962,7 +1004,7 @@ fn test_bookmark_forget_fetched_bookmark() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -1000,7 +1042,7 @@ fn test_bookmark_forget_fetched_bookmark() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -1018,7 +1060,7 @@ fn test_bookmark_forget_fetched_bookmark() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -1049,7 +1091,7 @@ fn test_bookmark_forget_fetched_bookmark() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: tyvxnvqr 9175cb32 (empty) another message
-      @origin: tyvxnvqr 9175cb32 (empty) another message
+      @origin [tracked, synced]: tyvxnvqr 9175cb32 (empty) another message
     [EOF]
     ");
 
@@ -1058,7 +1100,7 @@ fn test_bookmark_forget_fetched_bookmark() {
         .run_jj(["bookmark", "forget", "feature1"])
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
-    feature1@origin: tyvxnvqr 9175cb32 (empty) another message
+    feature1@origin [untracked]: tyvxnvqr 9175cb32 (empty) another message
     [EOF]
     ");
     // There should be no output here since the remote bookmark wasn't forgotten
@@ -1069,7 +1111,7 @@ fn test_bookmark_forget_fetched_bookmark() {
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
-    feature1@origin: tyvxnvqr 9175cb32 (empty) another message
+    feature1@origin [untracked]: tyvxnvqr 9175cb32 (empty) another message
     [EOF]
     ");
 }
@@ -1109,7 +1151,7 @@ fn test_bookmark_forget_deleted_or_nonexistent_bookmark() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1 (deleted)
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -1166,9 +1208,9 @@ fn test_bookmark_track_untrack() {
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
-    feature1@origin: qxxqrkql bd843888 commit 1
-    feature2@origin: qxxqrkql bd843888 commit 1
-    main@origin: qxxqrkql bd843888 commit 1
+    feature1@origin [untracked]: qxxqrkql bd843888 commit 1
+    feature2@origin [untracked]: qxxqrkql bd843888 commit 1
+    main@origin [untracked]: qxxqrkql bd843888 commit 1
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -1185,10 +1227,10 @@ fn test_bookmark_track_untrack() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qxxqrkql bd843888 commit 1
-      @origin: qxxqrkql bd843888 commit 1
-    feature2@origin: qxxqrkql bd843888 commit 1
+      @origin [tracked, synced]: qxxqrkql bd843888 commit 1
+    feature2@origin [untracked]: qxxqrkql bd843888 commit 1
     main: qxxqrkql bd843888 commit 1
-      @origin: qxxqrkql bd843888 commit 1
+      @origin [tracked, synced]: qxxqrkql bd843888 commit 1
     [EOF]
     ");
 
@@ -1201,13 +1243,13 @@ fn test_bookmark_track_untrack() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qxxqrkql bd843888 commit 1
-      @origin: qxxqrkql bd843888 commit 1
+      @origin [tracked, synced]: qxxqrkql bd843888 commit 1
     feature2 (conflicted):
       + qpvuntsm e8849ae1 (empty) (no description set)
       + qxxqrkql bd843888 commit 1
-      @origin (behind by 1 commits): qxxqrkql bd843888 commit 1
+      @origin [tracked] (behind by 1 commits): qxxqrkql bd843888 commit 1
     main: qxxqrkql bd843888 commit 1
-      @origin: qxxqrkql bd843888 commit 1
+      @origin [tracked, synced]: qxxqrkql bd843888 commit 1
     [EOF]
     ");
 
@@ -1221,10 +1263,10 @@ fn test_bookmark_track_untrack() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qxxqrkql bd843888 commit 1
-    feature1@origin: qxxqrkql bd843888 commit 1
-    feature2@origin: qxxqrkql bd843888 commit 1
+    feature1@origin [untracked]: qxxqrkql bd843888 commit 1
+    feature2@origin [untracked]: qxxqrkql bd843888 commit 1
     main: qxxqrkql bd843888 commit 1
-      @origin: qxxqrkql bd843888 commit 1
+      @origin [tracked, synced]: qxxqrkql bd843888 commit 1
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -1256,10 +1298,10 @@ fn test_bookmark_track_untrack() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qxxqrkql bd843888 commit 1
-    feature1@origin: psynomvr 48ec79a4 commit 2
-    feature2@origin: psynomvr 48ec79a4 commit 2
+    feature1@origin [untracked]: psynomvr 48ec79a4 commit 2
+    feature2@origin [untracked]: psynomvr 48ec79a4 commit 2
     main: psynomvr 48ec79a4 commit 2
-      @origin: psynomvr 48ec79a4 commit 2
+      @origin [tracked, synced]: psynomvr 48ec79a4 commit 2
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -1298,12 +1340,12 @@ fn test_bookmark_track_untrack() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qxxqrkql bd843888 commit 1
-    feature1@origin: yumopmsr d8cd3e02 commit 3
-    feature2@origin: yumopmsr d8cd3e02 commit 3
+    feature1@origin [untracked]: yumopmsr d8cd3e02 commit 3
+    feature2@origin [untracked]: yumopmsr d8cd3e02 commit 3
     feature3: yumopmsr d8cd3e02 commit 3
-      @origin: yumopmsr d8cd3e02 commit 3
+      @origin [tracked, synced]: yumopmsr d8cd3e02 commit 3
     main: yumopmsr d8cd3e02 commit 3
-      @origin: yumopmsr d8cd3e02 commit 3
+      @origin [tracked, synced]: yumopmsr d8cd3e02 commit 3
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -1317,6 +1359,81 @@ fn test_bookmark_track_untrack() {
     ");
 }
 
+#[test]
+fn test_bookmark_track_untrack_verbose() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let git_repo_path = test_env.env_root().join("git-repo");
+    let git_repo = git::init(git_repo_path);
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../git-repo"])
+        .success();
+    create_commit_with_refs(
+        &git_repo,
+        "commit 1",
+        b"content 1",
+        &[
+            "refs/heads/main",
+            "refs/heads/feature1",
+            "refs/heads/feature2",
+        ],
+    );
+    test_env.add_config("git.auto-local-bookmark = false");
+    work_dir.run_jj(["git", "fetch"]).success();
+
+    // Tracking a bookmark that already exists locally reveals the divergence
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "feature2"])
+        .success();
+    let output = work_dir.run_jj([
+        "bookmark",
+        "track",
+        "--verbose",
+        "feature1@origin",
+        "feature2@origin",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Started tracking 2 remote bookmarks.
+      feature1@origin: untracked -> tracked, in sync with local bookmark
+      feature2@origin: untracked -> tracked, diverged from local bookmark
+    feature2 (conflicted):
+      + qpvuntsm e8849ae1 (empty) (no description set)
+      + qxxqrkql bd843888 commit 1
+      @origin [tracked] (behind by 1 commits): qxxqrkql bd843888 commit 1
+    [EOF]
+    ");
+
+    // Untracking leaves the local bookmark alone
+    work_dir
+        .run_jj(["bookmark", "set", "--allow-backwards", "-r@", "feature1"])
+        .success();
+    let output = work_dir.run_jj([
+        "bookmark",
+        "untrack",
+        "--verbose",
+        "feature1@origin",
+        "feature2@origin",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Stopped tracking 2 remote bookmarks.
+      feature1@origin: tracked -> untracked, local bookmark points to a different commit
+      feature2@origin: tracked -> untracked, diverged from local bookmark
+    [EOF]
+    ");
+
+    // Without --verbose, only the summary is printed
+    let output = work_dir.run_jj(["bookmark", "track", "main@origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Started tracking 1 remote bookmarks.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_track_conflict() {
     let test_env = TestEnvironment::default();
@@ -1348,7 +1465,7 @@ fn test_bookmark_track_conflict() {
     main (conflicted):
       + qpvuntsm?? 56b9f16b (empty) b
       + qpvuntsm?? 7d5ca8e4 (empty) a
-      @origin (behind by 1 commits): qpvuntsm?? 7d5ca8e4 (empty) a
+      @origin [tracked] (behind by 1 commits): qpvuntsm?? 7d5ca8e4 (empty) a
     [EOF]
     ");
 }
@@ -1456,11 +1573,11 @@ fn test_bookmark_track_untrack_patterns() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: yrnqsqlx 41e7a49d commit
-      @git: yrnqsqlx 41e7a49d commit
-      @origin: yrnqsqlx 41e7a49d commit
-    feature2@origin: yrnqsqlx 41e7a49d commit
+      @git [tracked, synced]: yrnqsqlx 41e7a49d commit
+      @origin [tracked, synced]: yrnqsqlx 41e7a49d commit
+    feature2@origin [untracked]: yrnqsqlx 41e7a49d commit
     main: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 
@@ -1476,11 +1593,11 @@ fn test_bookmark_track_untrack_patterns() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: yrnqsqlx 41e7a49d commit
-      @git: yrnqsqlx 41e7a49d commit
-    feature1@origin: yrnqsqlx 41e7a49d commit
-    feature2@origin: yrnqsqlx 41e7a49d commit
+      @git [tracked, synced]: yrnqsqlx 41e7a49d commit
+    feature1@origin [untracked]: yrnqsqlx 41e7a49d commit
+    feature2@origin [untracked]: yrnqsqlx 41e7a49d commit
     main: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 
@@ -1493,12 +1610,12 @@ fn test_bookmark_track_untrack_patterns() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: yrnqsqlx 41e7a49d commit
-      @git: yrnqsqlx 41e7a49d commit
-      @origin: yrnqsqlx 41e7a49d commit
+      @git [tracked, synced]: yrnqsqlx 41e7a49d commit
+      @origin [tracked, synced]: yrnqsqlx 41e7a49d commit
     feature2: yrnqsqlx 41e7a49d commit
-      @origin: yrnqsqlx 41e7a49d commit
+      @origin [tracked, synced]: yrnqsqlx 41e7a49d commit
     main: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 }
@@ -1570,10 +1687,10 @@ fn test_bookmark_list() {
     insta::assert_snapshot!(output, @r"
     local-only: wqnwkozp 0353dd35 (empty) local-only
     remote-delete (deleted)
-      @origin: vruxwmqv b32031cf (empty) remote-delete
+      @origin [tracked]: vruxwmqv b32031cf (empty) remote-delete
     remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
     remote-unsync: wqnwkozp 0353dd35 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1584,12 +1701,12 @@ fn test_bookmark_list() {
     insta::assert_snapshot!(output, @r"
     local-only: wqnwkozp 0353dd35 (empty) local-only
     remote-delete (deleted)
-      @origin: vruxwmqv b32031cf (empty) remote-delete
+      @origin [tracked]: vruxwmqv b32031cf (empty) remote-delete
     remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
-      @origin: rlvkpnrz 7a07dbee (empty) remote-sync
+      @origin [tracked, synced]: rlvkpnrz 7a07dbee (empty) remote-sync
     remote-unsync: wqnwkozp 0353dd35 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
-    remote-untrack@origin: royxmykx 149bc756 (empty) remote-untrack
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+    remote-untrack@origin [untracked]: royxmykx 149bc756 (empty) remote-untrack
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1600,12 +1717,12 @@ fn test_bookmark_list() {
     insta::assert_snapshot!(output, @r"
     [38;5;5mlocal-only[39m: [1m[38;5;13mw[38;5;8mqnwkozp[39m [38;5;12m03[38;5;8m53dd35[39m [38;5;10m(empty)[39m local-only[0m
     [38;5;5mremote-delete[39m (deleted)
-      [38;5;5m@origin[39m: [1m[38;5;5mv[0m[38;5;8mruxwmqv[39m [1m[38;5;4mb[0m[38;5;8m32031cf[39m [38;5;2m(empty)[39m remote-delete
+      [38;5;5m@origin[39m [tracked]: [1m[38;5;5mv[0m[38;5;8mruxwmqv[39m [1m[38;5;4mb[0m[38;5;8m32031cf[39m [38;5;2m(empty)[39m remote-delete
     [38;5;5mremote-sync[39m: [1m[38;5;5mr[0m[38;5;8mlvkpnrz[39m [1m[38;5;4m7[0m[38;5;8ma07dbee[39m [38;5;2m(empty)[39m remote-sync
-      [38;5;5m@origin[39m: [1m[38;5;5mr[0m[38;5;8mlvkpnrz[39m [1m[38;5;4m7[0m[38;5;8ma07dbee[39m [38;5;2m(empty)[39m remote-sync
+      [38;5;5m@origin[39m [tracked, synced]: [1m[38;5;5mr[0m[38;5;8mlvkpnrz[39m [1m[38;5;4m7[0m[38;5;8ma07dbee[39m [38;5;2m(empty)[39m remote-sync
     [38;5;5mremote-unsync[39m: [1m[38;5;13mw[38;5;8mqnwkozp[39m [38;5;12m03[38;5;8m53dd35[39m [38;5;10m(empty)[39m local-only[0m
-      [38;5;5m@origin[39m (ahead by 1 commits, behind by 1 commits): [1m[38;5;5mzs[0m[38;5;8muskuln[39m [1m[38;5;4m5[0m[38;5;8m53203ba[39m [38;5;2m(empty)[39m remote-unsync
-    [38;5;5mremote-untrack@origin[39m: [1m[38;5;5mro[0m[38;5;8myxmykx[39m [1m[38;5;4m1[0m[38;5;8m49bc756[39m [38;5;2m(empty)[39m remote-untrack
+      [38;5;5m@origin[39m [tracked] (ahead by 1 commits, behind by 1 commits): [1m[38;5;5mzs[0m[38;5;8muskuln[39m [1m[38;5;4m5[0m[38;5;8m53203ba[39m [38;5;2m(empty)[39m remote-unsync
+    [38;5;5mremote-untrack@origin[39m [untracked]: [1m[38;5;5mro[0m[38;5;8myxmykx[39m [1m[38;5;4m1[0m[38;5;8m49bc756[39m [38;5;2m(empty)[39m remote-untrack
     [EOF]
     ------- stderr -------
     [1m[38;5;6mHint: [0m[39mBookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.[39m
@@ -1795,10 +1912,10 @@ fn test_bookmark_list_filtered() {
     insta::assert_snapshot!(output, @r"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
     remote-delete (deleted)
-      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+      @origin [tracked]: zsuskuln 0e6b7968 (empty) remote-delete
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1814,7 +1931,7 @@ fn test_bookmark_list_filtered() {
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
 
@@ -1824,19 +1941,19 @@ fn test_bookmark_list_filtered() {
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
 
     // Select bookmarks by name.
     insta::assert_snapshot!(query(&["remote-rewrite"]), @r"
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
     insta::assert_snapshot!(query(&["-rbookmarks(remote-rewrite)"]), @r"
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
 
@@ -1844,25 +1961,25 @@ fn test_bookmark_list_filtered() {
     local_dir.run_jj(["git", "export"]).success();
     insta::assert_snapshot!(query(&["--all-remotes", "remote-rewrite"]), @r"
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @git: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @git [tracked, synced]: royxmykx e6970e0e (empty) rewritten
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
     insta::assert_snapshot!(query(&["--all-remotes", "-rbookmarks(remote-rewrite)"]), @r"
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @git: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @git [tracked, synced]: royxmykx e6970e0e (empty) rewritten
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
 
     // Select bookmarks with --remote
     insta::assert_snapshot!(query(&["--remote", "origin"]), @r"
     remote-delete (deleted)
-      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+      @origin [tracked]: zsuskuln 0e6b7968 (empty) remote-delete
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
-      @origin: rlvkpnrz c2f2ee40 (empty) remote-keep
+      @origin [tracked, synced]: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1870,24 +1987,24 @@ fn test_bookmark_list_filtered() {
     ");
     insta::assert_snapshot!(query(&["--remote", "glob:gi?"]), @r"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
-      @git: kpqxywon 4b2bc95c (empty) local-keep
+      @git [tracked, synced]: kpqxywon 4b2bc95c (empty) local-keep
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
-      @git: rlvkpnrz c2f2ee40 (empty) remote-keep
+      @git [tracked, synced]: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @git: royxmykx e6970e0e (empty) rewritten
+      @git [tracked, synced]: royxmykx e6970e0e (empty) rewritten
     [EOF]
     ");
     insta::assert_snapshot!(query(&["--remote", "origin", "--remote", "git"]), @r"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
-      @git: kpqxywon 4b2bc95c (empty) local-keep
+      @git [tracked, synced]: kpqxywon 4b2bc95c (empty) local-keep
     remote-delete (deleted)
-      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+      @origin [tracked]: zsuskuln 0e6b7968 (empty) remote-delete
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
-      @git: rlvkpnrz c2f2ee40 (empty) remote-keep
-      @origin: rlvkpnrz c2f2ee40 (empty) remote-keep
+      @git [tracked, synced]: rlvkpnrz c2f2ee40 (empty) remote-keep
+      @origin [tracked, synced]: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @git: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @git [tracked, synced]: royxmykx e6970e0e (empty) rewritten
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1897,7 +2014,7 @@ fn test_bookmark_list_filtered() {
     // Can select deleted bookmark by name pattern, but not by revset.
     insta::assert_snapshot!(query(&["remote-delete"]), @r"
     remote-delete (deleted)
-      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+      @origin [tracked]: zsuskuln 0e6b7968 (empty) remote-delete
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1916,7 +2033,7 @@ fn test_bookmark_list_filtered() {
     insta::assert_snapshot!(query(&["glob:*-keep", "remote-delete"]), @r"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
     remote-delete (deleted)
-      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+      @origin [tracked]: zsuskuln 0e6b7968 (empty) remote-delete
     remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
     [EOF]
     ------- stderr -------
@@ -1934,7 +2051,7 @@ fn test_bookmark_list_filtered() {
     insta::assert_snapshot!(query(&["local-keep", "-rbookmarks(remote-rewrite)"]), @r"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @origin (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
 
@@ -1946,9 +2063,37 @@ fn test_bookmark_list_filtered() {
         "git",
     ]), @r"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
-      @git: kpqxywon 4b2bc95c (empty) local-keep
+      @git [tracked, synced]: kpqxywon 4b2bc95c (empty) local-keep
+    remote-rewrite: royxmykx e6970e0e (empty) rewritten
+      @git [tracked, synced]: royxmykx e6970e0e (empty) rewritten
+    [EOF]
+    ");
+
+    // --points-at filters bookmarks by local targets
+    insta::assert_snapshot!(query(&["--points-at", "description(rewritten)"]), @r"
+    remote-rewrite: royxmykx e6970e0e (empty) rewritten
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+    [EOF]
+    ");
+    insta::assert_snapshot!(query(&["--points-at", "description(rewritten)", "local-keep"]), @"");
+
+    // … and by remote targets if remote bookmarks are listed
+    insta::assert_snapshot!(query(&["--points-at", "all()", "--remote", "origin"]), @r"
+    remote-delete (deleted)
+      @origin [tracked]: zsuskuln 0e6b7968 (empty) remote-delete
+    remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
+      @origin [tracked, synced]: rlvkpnrz c2f2ee40 (empty) remote-keep
     remote-rewrite: royxmykx e6970e0e (empty) rewritten
-      @git: royxmykx e6970e0e (empty) rewritten
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
+    [EOF]
+    ------- stderr -------
+    Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        query(&["--points-at", "description(remote-rewrite)", "--all-remotes"]), @r"
+    remote-rewrite: royxmykx e6970e0e (empty) rewritten
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): royxmykx hidden 331d500d (empty) remote-rewrite
     [EOF]
     ");
 }
@@ -2040,7 +2185,7 @@ fn test_bookmark_list_much_remote_divergence() {
     insta::assert_snapshot!(output, @r"
     local-only: zkyosouw a30800ad (empty) local-only
     remote-unsync: zkyosouw a30800ad (empty) local-only
-      @origin (ahead by at least 10 commits, behind by at least 10 commits): uyznsvlq a52367f8 (empty) remote-unsync
+      @origin [tracked] (ahead by at least 10 commits, behind by at least 10 commits): uyznsvlq a52367f8 (empty) remote-unsync
     [EOF]
     ");
 }
@@ -2159,20 +2304,20 @@ fn test_bookmark_list_tracked() {
     let output = local_dir.run_jj(["bookmark", "list", "--all-remotes"]);
     insta::assert_snapshot!(output, @r"
     local-only: nmzmmopx 2a685e16 (empty) local-only
-      @git: nmzmmopx 2a685e16 (empty) local-only
+      @git [tracked, synced]: nmzmmopx 2a685e16 (empty) local-only
     remote-delete (deleted)
-      @origin: vruxwmqv b32031cf (empty) remote-delete
+      @origin [tracked]: vruxwmqv b32031cf (empty) remote-delete
     remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
-      @git: rlvkpnrz 7a07dbee (empty) remote-sync
-      @origin: rlvkpnrz 7a07dbee (empty) remote-sync
+      @git [tracked, synced]: rlvkpnrz 7a07dbee (empty) remote-sync
+      @origin [tracked, synced]: rlvkpnrz 7a07dbee (empty) remote-sync
     remote-unsync: nmzmmopx 2a685e16 (empty) local-only
-      @git: nmzmmopx 2a685e16 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
-      @upstream (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
-    remote-untrack@origin: royxmykx 149bc756 (empty) remote-untrack
+      @git [tracked, synced]: nmzmmopx 2a685e16 (empty) local-only
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @upstream [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+    remote-untrack@origin [untracked]: royxmykx 149bc756 (empty) remote-untrack
     upstream-sync: lylxulpl 169ba7d9 (empty) upstream-sync
-      @git: lylxulpl 169ba7d9 (empty) upstream-sync
-      @upstream: lylxulpl 169ba7d9 (empty) upstream-sync
+      @git [tracked, synced]: lylxulpl 169ba7d9 (empty) upstream-sync
+      @upstream [tracked, synced]: lylxulpl 169ba7d9 (empty) upstream-sync
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -2182,14 +2327,14 @@ fn test_bookmark_list_tracked() {
     let output = local_dir.run_jj(["bookmark", "list", "--tracked"]);
     insta::assert_snapshot!(output, @r"
     remote-delete (deleted)
-      @origin: vruxwmqv b32031cf (empty) remote-delete
+      @origin [tracked]: vruxwmqv b32031cf (empty) remote-delete
     remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
-      @origin: rlvkpnrz 7a07dbee (empty) remote-sync
+      @origin [tracked, synced]: rlvkpnrz 7a07dbee (empty) remote-sync
     remote-unsync: nmzmmopx 2a685e16 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
-      @upstream (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @upstream [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
     upstream-sync: lylxulpl 169ba7d9 (empty) upstream-sync
-      @upstream: lylxulpl 169ba7d9 (empty) upstream-sync
+      @upstream [tracked, synced]: lylxulpl 169ba7d9 (empty) upstream-sync
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -2199,11 +2344,11 @@ fn test_bookmark_list_tracked() {
     let output = local_dir.run_jj(["bookmark", "list", "--tracked", "--remote", "origin"]);
     insta::assert_snapshot!(output, @r"
     remote-delete (deleted)
-      @origin: vruxwmqv b32031cf (empty) remote-delete
+      @origin [tracked]: vruxwmqv b32031cf (empty) remote-delete
     remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
-      @origin: rlvkpnrz 7a07dbee (empty) remote-sync
+      @origin [tracked, synced]: rlvkpnrz 7a07dbee (empty) remote-sync
     remote-unsync: nmzmmopx 2a685e16 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -2213,8 +2358,8 @@ fn test_bookmark_list_tracked() {
     let output = local_dir.run_jj(["bookmark", "list", "--tracked", "remote-unsync"]);
     insta::assert_snapshot!(output, @r"
     remote-unsync: nmzmmopx 2a685e16 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
-      @upstream (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @upstream [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
     [EOF]
     ");
 
@@ -2228,7 +2373,7 @@ fn test_bookmark_list_tracked() {
     let output = local_dir.run_jj(["bookmark", "list", "--tracked", "remote-unsync"]);
     insta::assert_snapshot!(output, @r"
     remote-unsync: nmzmmopx 2a685e16 (empty) local-only
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
     [EOF]
     ");
 }
@@ -2272,6 +2417,264 @@ fn test_bookmark_list_conflicted() {
       + kkmpptxz a82129fb (empty) b
     [EOF]
     ");
+    insta::assert_snapshot!(
+        work_dir.run_jj(["bookmark", "list", "--points-at", "description(a)"]), @r"
+    foo (conflicted):
+      + rlvkpnrz 4e1b2d80 (empty) a
+      + kkmpptxz a82129fb (empty) b
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_list_ahead_behind() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-bookmark = true");
+
+    // Initialize remote refs
+    test_env.run_jj_in(".", ["git", "init", "remote"]).success();
+    let remote_dir = test_env.work_dir("remote");
+    for bookmark in ["sync", "ahead", "behind", "diverged"] {
+        remote_dir
+            .run_jj(["new", "root()", "-m", bookmark])
+            .success();
+        remote_dir
+            .run_jj(["bookmark", "create", "-r@", bookmark])
+            .success();
+    }
+    remote_dir.run_jj(["new"]).success();
+    remote_dir.run_jj(["git", "export"]).success();
+
+    let mut remote_git_path = remote_dir.root().to_owned();
+    remote_git_path.extend([".jj", "repo", "store", "git"]);
+    test_env
+        .run_jj_in(
+            ".",
+            ["git", "clone", remote_git_path.to_str().unwrap(), "local"],
+        )
+        .success();
+    let local_dir = test_env.work_dir("local");
+
+    // Move local bookmarks relative to their remote counterparts
+    local_dir
+        .run_jj(["new", "ahead", "-m", "local ahead 1"])
+        .success();
+    local_dir.run_jj(["new", "-m", "local ahead 2"]).success();
+    local_dir
+        .run_jj(["bookmark", "set", "ahead", "-r@"])
+        .success();
+    local_dir
+        .run_jj(["new", "diverged-", "-m", "local diverged"])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "set", "diverged", "-r@", "--allow-backwards"])
+        .success();
+    local_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "behind",
+            "-r",
+            "root()",
+            "--allow-backwards",
+        ])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "create", "local-only", "-r@"])
+        .success();
+
+    insta::assert_snapshot!(local_dir.run_jj(["bookmark", "list", "--ahead-behind"]), @r"
+    ahead: lylxulpl c17590fb (empty) local ahead 2
+      @origin [tracked] ↑2 ↓0: zsuskuln 66f6ebc6 (empty) ahead
+    behind: zzzzzzzz 00000000 (empty) (no description set)
+      @origin [tracked] ↑0 ↓1: royxmykx 47549b1f (empty) behind
+    diverged: nkmrtpmo f44f349d (empty) local diverged
+      @origin [tracked] ↑1 ↓1: vruxwmqv 68535464 (empty) diverged
+    local-only: nkmrtpmo f44f349d (empty) local diverged
+    sync: rlvkpnrz b7045d08 (empty) sync
+      @origin [tracked, synced] ↑0 ↓0: rlvkpnrz b7045d08 (empty) sync
+    [EOF]
+    ");
+
+    // Conflicted local bookmark: counts cover all local targets
+    local_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "sync",
+            "-r",
+            "diverged",
+            "--allow-backwards",
+        ])
+        .success();
+    local_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "sync",
+            "-r",
+            "ahead",
+            "--allow-backwards",
+            "--at-op=@-",
+        ])
+        .success();
+    insta::assert_snapshot!(local_dir.run_jj(["bookmark", "list", "--ahead-behind", "sync"]), @r"
+    sync (conflicted):
+      - rlvkpnrz b7045d08 (empty) sync
+      + nkmrtpmo f44f349d (empty) local diverged
+      + lylxulpl c17590fb (empty) local ahead 2
+      @origin [tracked] ↑4 ↓1: rlvkpnrz b7045d08 (empty) sync
+    [EOF]
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    [EOF]
+    ");
+
+    // Custom templates can use the counts through the alias
+    let template = r#"separate(" ", name, format_tracked_remote_ref_distances(self)) ++ "\n""#;
+    let output = local_dir.run_jj(["bookmark", "list", "--ahead-behind", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ahead
+    ahead ↑2 ↓0
+    behind
+    behind ↑0 ↓1
+    diverged
+    diverged ↑1 ↓1
+    local-only
+    sync
+    sync ↑4 ↓1
+    [EOF]
+    ");
+
+    // The configured template is respected
+    test_env.add_config(format!("templates.bookmark_list = '''{template}'''"));
+    let output = local_dir.run_jj(["bookmark", "list", "--ahead-behind", "sync"]);
+    insta::assert_snapshot!(output, @r"
+    sync
+    sync ↑4 ↓1
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_list_tracking_info() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-bookmark = true");
+
+    // Initialize remote refs
+    test_env.run_jj_in(".", ["git", "init", "remote"]).success();
+    let remote_dir = test_env.work_dir("remote");
+    for bookmark in ["sync", "ahead", "untracked"] {
+        remote_dir
+            .run_jj(["new", "root()", "-m", bookmark])
+            .success();
+        remote_dir
+            .run_jj(["bookmark", "create", "-r@", bookmark])
+            .success();
+    }
+    remote_dir.run_jj(["new"]).success();
+    remote_dir.run_jj(["git", "export"]).success();
+
+    let mut remote_git_path = remote_dir.root().to_owned();
+    remote_git_path.extend([".jj", "repo", "store", "git"]);
+    test_env
+        .run_jj_in(
+            ".",
+            ["git", "clone", remote_git_path.to_str().unwrap(), "local"],
+        )
+        .success();
+    let local_dir = test_env.work_dir("local");
+
+    local_dir
+        .run_jj(["new", "ahead", "-m", "local ahead"])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "set", "ahead", "-r@"])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "untrack", "untracked@origin"])
+        .success();
+    // Conflicted local bookmark
+    local_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "sync",
+            "-r",
+            "ahead",
+            "--allow-backwards",
+        ])
+        .success();
+    local_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "sync",
+            "-r",
+            "untracked",
+            "--allow-backwards",
+            "--at-op=@-",
+        ])
+        .success();
+    local_dir.run_jj(["status"]).success();
+
+    let output = local_dir.run_jj(["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @r"
+    ahead: kpqxywon 34a0f0f7 (empty) local ahead
+      @origin [tracked] (behind by 1 commits): zsuskuln 66f6ebc6 (empty) ahead
+    sync (conflicted):
+      - rlvkpnrz b7045d08 (empty) sync
+      + kpqxywon 34a0f0f7 (empty) local ahead
+      + royxmykx faf2ef05 (empty) untracked
+      @origin [tracked] (ahead by 1 commits, behind by 3 commits): rlvkpnrz b7045d08 (empty) sync
+    untracked: royxmykx faf2ef05 (empty) untracked
+    untracked@origin [untracked]: royxmykx faf2ef05 (empty) untracked
+    [EOF]
+    ");
+
+    // Tracking info can be omitted
+    let output = local_dir.run_jj(["bookmark", "list", "--all-remotes", "--no-tracking-info"]);
+    insta::assert_snapshot!(output, @r"
+    ahead: kpqxywon 34a0f0f7 (empty) local ahead
+      @origin (behind by 1 commits): zsuskuln 66f6ebc6 (empty) ahead
+    sync (conflicted):
+      - rlvkpnrz b7045d08 (empty) sync
+      + kpqxywon 34a0f0f7 (empty) local ahead
+      + royxmykx faf2ef05 (empty) untracked
+      @origin (ahead by 1 commits, behind by 3 commits): rlvkpnrz b7045d08 (empty) sync
+    untracked: royxmykx faf2ef05 (empty) untracked
+    untracked@origin: royxmykx faf2ef05 (empty) untracked
+    [EOF]
+    ");
+
+    // The flags can be combined
+    let output = local_dir.run_jj(["bookmark", "list", "--no-tracking-info", "--ahead-behind"]);
+    insta::assert_snapshot!(output, @r"
+    ahead: kpqxywon 34a0f0f7 (empty) local ahead
+      @origin ↑1 ↓0: zsuskuln 66f6ebc6 (empty) ahead
+    sync (conflicted):
+      - rlvkpnrz b7045d08 (empty) sync
+      + kpqxywon 34a0f0f7 (empty) local ahead
+      + royxmykx faf2ef05 (empty) untracked
+      @origin ↑3 ↓1: rlvkpnrz b7045d08 (empty) sync
+    untracked: royxmykx faf2ef05 (empty) untracked
+    [EOF]
+    ");
+
+    // The configured template is respected
+    test_env.add_config(
+        r#"templates.bookmark_list = 'separate(" ", name, format_remote_ref_tracking_info(self)) ++ "\n"'"#,
+    );
+    let output = local_dir.run_jj(["bookmark", "list", "--all-remotes", "--no-tracking-info"]);
+    insta::assert_snapshot!(output, @r"
+    ahead
+    ahead
+    sync
+    sync
+    untracked
+    untracked
+    [EOF]
+    ");
 }
 
 #[test]
@@ -2392,6 +2795,36 @@ fn test_bookmark_list_sort_overriding_config() {
     ");
 }
 
+#[test]
+fn test_bookmark_create_multiple() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Duplicated names are created once
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "foo", "bar", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 2 bookmarks pointing to qpvuntsm e8849ae1 bar foo | (empty) (no description set)
+    [EOF]
+    ");
+
+    // All existing bookmarks are reported, and no bookmark is created
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "foo", "baz", "bar"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmarks already exist: foo, bar
+    Hint: Use `jj bookmark set` to update them.
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bar: qpvuntsm e8849ae1 (empty) (no description set)
+    foo: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_create_with_default_target_revision() {
     let test_env = TestEnvironment::default();
@@ -16,6 +16,7 @@ use indoc::indoc;
 use regex::Regex;
 use testutils::git;
 
+use crate::common::create_commit_with_files;
 use crate::common::TestEnvironment;
 
 #[test]
@@ -136,6 +137,46 @@ fn test_log_author_timestamp_ago() {
     );
 }
 
+#[test]
+fn test_log_author_timestamp_relative() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    // The current time can be pinned
+    let template = r#"author.timestamp().relative() ++ "\n""#;
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--config=debug.current-timestamp=2001-02-03T04:05:10+07:00",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    1 second ago
+    2 seconds ago
+    31 years ago
+    [EOF]
+    ");
+
+    // Timestamps after the current time
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r@",
+        "-T",
+        template,
+        "--config=debug.current-timestamp=2001-02-03T04:05:06+07:00",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    in 3 seconds
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_author_timestamp_utc() {
     let test_env = TestEnvironment::default();
@@ -242,6 +283,61 @@ fn test_mine_is_true_when_author_is_user() {
     ");
 }
 
+#[test]
+fn test_log_author_committer_differ() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    // Rewriting a described commit updates only the committer timestamp
+    work_dir.run_jj(["describe", "-m", "original"]).success();
+    work_dir.run_jj(["describe", "-m", "rewritten"]).success();
+    work_dir.run_jj(["new", "-m", "unchanged"]).success();
+    work_dir.run_jj(["new", "-m", "other author"]).success();
+    work_dir
+        .run_jj([
+            "describe",
+            "--author=John Doe <johndoe@example.com>",
+            "--no-edit",
+        ])
+        .success();
+
+    let template = r#"
+    separate(" ",
+      description.first_line(),
+      self.author_committer_differ(),
+      self.author_committer_differ("identity"),
+      self.author_committer_differ("time"),
+      self.author_committer_differ("any"),
+    ) ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["log", "-r..", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    other author true true true true
+    unchanged false false false false
+    rewritten false false true true
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "-T",
+        r#"self.author_committer_differ("name")"#,
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Failed to parse template: Expected "identity", "time", or "any"
+    Caused by:  --> 1:30
+      |
+    1 | self.author_committer_differ("name")
+      |                              ^----^
+      |
+      = Expected "identity", "time", or "any"
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_log_json() {
     let test_env = TestEnvironment::default();
@@ -770,6 +866,45 @@ fn test_log_git_head() {
     ");
 }
 
+#[test]
+fn test_log_working_copies() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let template = r#"
+    separate(" ",
+      commit_id.short(),
+      working_copies.len(),
+      working_copies.map(|w| "[" ++ w ++ "]"),
+    ) ++ "\n"
+    "#;
+
+    // Working-copy commit isn't indicated in single-workspace repo
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  e8849ae12c70 0
+    ◆  000000000000 0
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj(["workspace", "add", "--name=second", "../second"])
+        .success();
+    work_dir
+        .run_jj(["workspace", "add", "--name=third", "../third"])
+        .success();
+    let second_dir = test_env.work_dir("second");
+    second_dir.run_jj(["edit", "third@"]).success();
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  e8849ae12c70 1 [default@]
+    │ ○  42960ba4ec00 2 [second@] [third@]
+    ├─╯
+    ◆  000000000000 0
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_customize_short_id() {
     let test_env = TestEnvironment::default();
@@ -1072,6 +1207,50 @@ fn test_short_prefix_in_transaction() {
     ");
 }
 
+#[test]
+fn test_log_files() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(
+        &work_dir,
+        "a",
+        &[],
+        &[("file1", "a\n"), ("dir/file2", "a\n")],
+    );
+    create_commit_with_files(&work_dir, "b", &["a"], &[("file1", "b\n")]);
+    create_commit_with_files(&work_dir, "c", &["a"], &[("dir/file3", "c\n")]);
+    create_commit_with_files(&work_dir, "d", &["b", "c"], &[]);
+    work_dir.remove_file("dir/file2");
+
+    let template =
+        r#"separate(" ", description.first_line(), self.files().len(), self.files()) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    d 2 dir/file2 dir/file3
+    c 1 dir/file3
+    b 1 file1
+    a 2 dir/file2 file1
+    0
+    [EOF]
+    ");
+
+    // Merge commits can be compared against any parent
+    let template =
+        r#"separate(" ", self.files(0).map(|p| p.display()), "|", self.files(1)) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    dir/file2 dir/file3 | dir/file2 file1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", "self.files(2)"]);
+    insta::assert_snapshot!(output, @"<Error: Commit has no parent at index 2>[EOF]");
+
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", "json(self.files())"]);
+    insta::assert_snapshot!(output, @r#"["dir/file2","dir/file3"][EOF]"#);
+}
+
 #[test]
 fn test_log_diff_predefined_formats() {
     let test_env = TestEnvironment::default();
@@ -419,21 +419,21 @@ fn test_default_command_is_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
-            --limit
+            --combine
             [EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
-            --limit:Limit number of revisions to show
+            --combine:How to combine the revsets given by multiple `-r` options
             [EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
-            --limit	Limit number of revisions to show
+            --combine	How to combine the revsets given by multiple `-r` options
             [EOF]
             ");
         }
@@ -1072,6 +1072,8 @@ fn test_template_alias() {
     builtin_log_compact_full_description
     builtin_log_detailed
     builtin_log_node
+    builtin_log_node_ancestry_path
+    builtin_log_node_ancestry_path_ascii
     builtin_log_node_ascii
     builtin_log_oneline
     builtin_op_log_comfortable
@@ -65,6 +65,79 @@ fn test_concurrent_operation_divergence() {
     ");
 }
 
+#[test]
+fn test_concurrent_operation_merge_parents() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "message 1"]).success();
+    work_dir
+        .run_jj(["describe", "-m", "message 2", "--at-op", "@-"])
+        .success();
+    // Merge the concurrent operations
+    work_dir.run_jj(["op", "log", "-n1"]).success();
+
+    // The parents of the merge operation are ambiguous
+    let output = work_dir.run_jj(["op", "show", "@-"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: The "@-" expression resolved to more than one operation
+    Hint: Try specifying one of the operations by ID: b2cffe4f3026, d8ced2ea64a8, or by parent index: @-(1), @-(2)
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    // Each parent can be selected by index
+    let template = r#"id.short() ++ " " ++ description ++ "\n""#;
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--at-op=@-(1)",
+        "-n1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    b2cffe4f3026 describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--at-op=@-(2)",
+        "-n1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    d8ced2ea64a8 describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--at-op=@-(2)-",
+        "-n1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    8f47435a3990 add workspace 'default'
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "show", "@-(3)"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: The "@-(3)" expression resolved to no operations
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_concurrent_operations_auto_rebase() {
     let test_env = TestEnvironment::default();
@@ -659,6 +659,97 @@ fn test_diff_name_only() {
     ");
 }
 
+#[test]
+fn test_diff_type_filter() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("deleted", "d\n");
+    work_dir.write_file("modified", "m\n");
+    work_dir.write_file("renamed-from", "1\n2\n3\n4\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("deleted");
+    work_dir.write_file("modified", "mod\n");
+    work_dir.write_file("added", "add\n");
+    work_dir.remove_file("renamed-from");
+    work_dir.write_file("renamed-to", "1\n2\n3\n4\n");
+
+    let output = work_dir.run_jj(["diff", "--summary", "--diff-type=added,removed"]);
+    insta::assert_snapshot!(output, @r"
+    A added
+    D deleted
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git", "--diff-type=modified"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/modified b/modified
+    index 28ce6a8b26..2680cfddbd 100644
+    --- a/modified
+    +++ b/modified
+    @@ -1,1 +1,1 @@
+    -m
+    +mod
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--stat", "--diff-type=renamed"]);
+    insta::assert_snapshot!(output, @r"
+    {renamed-from => renamed-to} | 0
+    1 file changed, 0 insertions(+), 0 deletions(-)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary", "--diff-type=copied"]);
+    insta::assert_snapshot!(output, @"");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "--no-graph",
+        "-Tdescription",
+        "--name-only",
+        "--diff-type=added",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    added
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "show", "-p", "--summary", "--diff-type=removed"]);
+    insta::assert_snapshot!(output, @r"
+    0a91198f5307 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    snapshot working copy
+    args: jj diff --summary '--diff-type=added,removed'
+
+    Changed commits:
+    ○  + rlvkpnrz 7db5a530 (no description set)
+       - rlvkpnrz hidden f6079b19 (empty) (no description set)
+       D deleted
+       D renamed-from
+       Removed regular file deleted:
+          1     : d
+       Removed regular file renamed-from:
+          1     : 1
+          2     : 2
+          3     : 3
+          4     : 4
+
+    Changed working copy default@:
+    + rlvkpnrz 7db5a530 (no description set)
+    - rlvkpnrz hidden f6079b19 (empty) (no description set)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--diff-type=bogus"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'bogus' for '--diff-type <TYPE>'
+      [possible values: added, removed, modified, renamed, copied]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_diff_renamed_file_and_dir() {
     let test_env = TestEnvironment::default();
@@ -1971,6 +2062,135 @@ fn test_diff_ignore_whitespace() {
     ");
 }
 
+#[test]
+fn test_diff_color_moved() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\nfoo()\nbar()\nb\n\nc\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "a\nb\nc\n\nfoo()\nbar()\nbaz()\n");
+
+    // Moved lines are labeled only if requested
+    let output = work_dir.run_jj(["diff", "--git", "--color=debug"]);
+    insta::assert_snapshot!(output, @r"
+    [1m<<diff file_header::diff --git a/file1 b/file1>>[0m
+    [1m<<diff file_header::index 0a55835598..210f6ef350 100644>>[0m
+    [1m<<diff file_header::--- a/file1>>[0m
+    [1m<<diff file_header::+++ b/file1>>[0m
+    [38;5;6m<<diff hunk_header::@@ -1,6 +1,7 @@>>[39m
+    <<diff context:: a>>
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::foo()>>[24m[39m
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::bar()>>[24m[39m
+    <<diff context:: b>>
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::>>[24m[39m
+    <<diff context:: c>>
+    [38;5;2m<<diff added::+>>[4m<<diff added token::>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::foo()>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::bar()>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::baz()>>[24m[39m
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git", "--color-moved", "--color=debug"]);
+    insta::assert_snapshot!(output, @r"
+    [1m<<diff file_header::diff --git a/file1 b/file1>>[0m
+    [1m<<diff file_header::index 0a55835598..210f6ef350 100644>>[0m
+    [1m<<diff file_header::--- a/file1>>[0m
+    [1m<<diff file_header::+++ b/file1>>[0m
+    [38;5;6m<<diff hunk_header::@@ -1,6 +1,7 @@>>[39m
+    <<diff context:: a>>
+    [38;5;5m<<diff moved removed::->>[4m<<diff moved removed token::foo()>>[24m[39m
+    [38;5;5m<<diff moved removed::->>[4m<<diff moved removed token::bar()>>[24m[39m
+    <<diff context:: b>>
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::>>[24m[39m
+    <<diff context:: c>>
+    [38;5;2m<<diff added::+>>[4m<<diff added token::>>[24m[39m
+    [38;5;6m<<diff moved added::+>>[4m<<diff moved added token::foo()>>[24m[39m
+    [38;5;6m<<diff moved added::+>>[4m<<diff moved added token::bar()>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::baz()>>[24m[39m
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--color-words", "--color-moved", "--color=debug"]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;3m<<diff header::Modified regular file file1:>>[39m
+    [38;5;1m<<diff removed line_number::   1>>[39m<<diff:: >>[38;5;2m<<diff added line_number::   1>>[39m<<diff::: a>>
+    [38;5;5m<<diff moved removed line_number::   2>>[39m<<diff moved::     : >>[4m[38;5;5m<<diff moved removed token::foo()>>[24m[39m
+    [38;5;5m<<diff moved removed line_number::   3>>[39m<<diff moved::     : >>[4m[38;5;5m<<diff moved removed token::bar()>>[24m[39m
+    [38;5;1m<<diff removed line_number::   4>>[39m<<diff:: >>[38;5;2m<<diff added line_number::   2>>[39m<<diff::: b>>
+    [38;5;1m<<diff removed line_number::   5>>[39m<<diff::     : >>[4m[38;5;1m<<diff removed token::>>[24m[39m
+    [38;5;1m<<diff removed line_number::   6>>[39m<<diff:: >>[38;5;2m<<diff added line_number::   3>>[39m<<diff::: c>>
+    <<diff::     >>[38;5;2m<<diff added line_number::   4>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::>>[24m[39m
+    <<diff moved::     >>[38;5;6m<<diff moved added line_number::   5>>[39m<<diff moved::: >>[4m[38;5;6m<<diff moved added token::foo()>>[24m[39m
+    <<diff moved::     >>[38;5;6m<<diff moved added line_number::   6>>[39m<<diff moved::: >>[4m[38;5;6m<<diff moved added token::bar()>>[24m[39m
+    <<diff::     >>[38;5;2m<<diff added line_number::   7>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::baz()>>[24m[39m
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "diff",
+        "--color-words",
+        "--color-moved",
+        "--color=debug",
+        "--config=diff.color-words.max-inline-alternation=0",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;3m<<diff header::Modified regular file file1:>>[39m
+    [38;5;1m<<diff removed line_number::   1>>[39m<<diff:: >>[38;5;2m<<diff added line_number::   1>>[39m<<diff::: a>>
+    [38;5;5m<<diff moved removed line_number::   2>>[39m<<diff moved::     : >>[4m[38;5;5m<<diff moved removed token::foo()>>[24m[39m
+    [38;5;5m<<diff moved removed line_number::   3>>[39m<<diff moved::     : >>[4m[38;5;5m<<diff moved removed token::bar()>>[24m[39m
+    [38;5;1m<<diff removed line_number::   4>>[39m<<diff:: >>[38;5;2m<<diff added line_number::   2>>[39m<<diff::: b>>
+    [38;5;1m<<diff removed line_number::   5>>[39m<<diff::     : >>[4m[38;5;1m<<diff removed token::>>[24m[39m
+    [38;5;1m<<diff removed line_number::   6>>[39m<<diff:: >>[38;5;2m<<diff added line_number::   3>>[39m<<diff::: c>>
+    <<diff::     >>[38;5;2m<<diff added line_number::   4>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::>>[24m[39m
+    <<diff moved::     >>[38;5;6m<<diff moved added line_number::   5>>[39m<<diff moved::: >>[4m[38;5;6m<<diff moved added token::foo()>>[24m[39m
+    <<diff moved::     >>[38;5;6m<<diff moved added line_number::   6>>[39m<<diff moved::: >>[4m[38;5;6m<<diff moved added token::bar()>>[24m[39m
+    <<diff::     >>[38;5;2m<<diff added line_number::   7>>[39m<<diff::: >>[4m[38;5;2m<<diff added token::baz()>>[24m[39m
+    [EOF]
+    ");
+
+    // Explicitly disabled
+    let output = work_dir.run_jj(["diff", "--git", "--color-moved=no", "--color=debug"]);
+    insta::assert_snapshot!(output, @r"
+    [1m<<diff file_header::diff --git a/file1 b/file1>>[0m
+    [1m<<diff file_header::index 0a55835598..210f6ef350 100644>>[0m
+    [1m<<diff file_header::--- a/file1>>[0m
+    [1m<<diff file_header::+++ b/file1>>[0m
+    [38;5;6m<<diff hunk_header::@@ -1,6 +1,7 @@>>[39m
+    <<diff context:: a>>
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::foo()>>[24m[39m
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::bar()>>[24m[39m
+    <<diff context:: b>>
+    [38;5;1m<<diff removed::->>[4m<<diff removed token::>>[24m[39m
+    <<diff context:: c>>
+    [38;5;2m<<diff added::+>>[4m<<diff added token::>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::foo()>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::bar()>>[24m[39m
+    [38;5;2m<<diff added::+>>[4m<<diff added token::baz()>>[24m[39m
+    [EOF]
+    ");
+
+    // No-op without color
+    let output = work_dir.run_jj(["diff", "--git", "--color-moved"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    index 0a55835598..210f6ef350 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,6 +1,7 @@
+     a
+    -foo()
+    -bar()
+     b
+    -
+     c
+    +
+    +foo()
+    +bar()
+    +baz()
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_skipped_context() {
     let test_env = TestEnvironment::default();
@@ -2192,6 +2412,178 @@ context = 0
     ");
 }
 
+#[test]
+fn test_diff_git_function_context() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    test_env.add_config(
+        r#"
+[diff.git]
+context = 1
+
+[diff.git.function-context]
+"*.rs" = '^(fn .*)\{$'
+"*.py" = '^def '
+        "#,
+    );
+
+    let content = indoc! {"
+        fn foo() {
+            1
+            2
+            3
+        }
+        fn bar() {
+            4
+            5
+            6
+        }
+    "};
+    work_dir.write_file("file.rs", content);
+    work_dir.write_file("file.py", "def foo():\n    1\n    2\n    3\n");
+    work_dir.write_file("file.txt", content);
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file.rs", content.replace('2', "two").replace('6', "six"));
+    work_dir.write_file("file.py", "def foo():\n    1\n    2\n    three\n");
+    work_dir.write_file("file.txt", content.replace('6', "six"));
+
+    // Without a capture group, the whole match is shown. Files not matching
+    // any glob have no context.
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file.py b/file.py
+    index 6f1865a71e..374d646816 100644
+    --- a/file.py
+    +++ b/file.py
+    @@ -3,2 +3,2 @@ def
+         2
+    -    3
+    +    three
+    diff --git a/file.rs b/file.rs
+    index 79beb2454d..49bfa4f3cc 100644
+    --- a/file.rs
+    +++ b/file.rs
+    @@ -2,3 +2,3 @@ fn foo()
+         1
+    -    2
+    +    two
+         3
+    @@ -8,3 +8,3 @@ fn bar()
+         5
+    -    6
+    +    six
+     }
+    diff --git a/file.txt b/file.txt
+    index 79beb2454d..7d4139b954 100644
+    --- a/file.txt
+    +++ b/file.txt
+    @@ -8,3 +8,3 @@
+         5
+    -    6
+    +    six
+     }
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_diff_git_highlighter() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    assert!(formatter_path.is_file());
+    let formatter_path = formatter_path.to_str().unwrap();
+    test_env.add_config(format!(
+        "ui.diff-highlighter = {}",
+        toml_edit::Value::from_iter([formatter_path, "--uppercase"])
+    ));
+
+    work_dir.write_file("file", "foo\n");
+
+    // Git diffs are piped through the highlighter
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    DIFF --GIT A/FILE B/FILE
+    NEW FILE MODE 100644
+    INDEX 0000000000..257CC5642C
+    --- /DEV/NULL
+    +++ B/FILE
+    @@ -0,0 +1,1 @@
+    +FOO
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r@", "-p", "--git", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │  DIFF --GIT A/FILE B/FILE
+    ~  NEW FILE MODE 100644
+       INDEX 0000000000..257CC5642C
+       --- /DEV/NULL
+       +++ B/FILE
+       @@ -0,0 +1,1 @@
+       +FOO
+    [EOF]
+    ");
+
+    // Other formats are unaffected
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    A file
+    [EOF]
+    ");
+
+    // The command line overrides the setting
+    let output = work_dir.run_jj([
+        "diff",
+        "--git",
+        &format!("--highlighter={formatter_path} --reverse"),
+    ]);
+    insta::assert_snapshot!(output, @r"
+    elif/b elif/a tig-- ffid
+    446001 edom elif wen
+    c2465cc752..0000000000 xedni
+    llun/ved/ ---
+    elif/b +++
+    @@ 1,1+ 0,0- @@
+    oof+
+    [EOF]
+    ");
+
+    // Errors are reported
+    let output = work_dir.run_jj(["diff", "--git", "--highlighter=nonexistent-highlighter"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to generate diff
+    Caused by:
+    1: Error executing 'nonexistent-highlighter' (run with --debug to see the exact invocation)
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj([
+        "diff",
+        "--git",
+        &format!("--highlighter={formatter_path} --fail"),
+    ]);
+    insta::assert_snapshot!(output.normalize_stderr_exit_status(), @r"
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..257cc5642c
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +foo
+    [EOF]
+    ------- stderr -------
+    Error: Failed to generate diff
+    Caused by: Tool exited with exit status: 1 (run with --debug to see the exact invocation)
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_diff_skipped_context_nondefault() {
     let test_env = TestEnvironment::default();
@@ -3201,6 +3593,150 @@ fn test_diff_external_tool() {
     ");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_diff_external_tool_fallback_not_executable() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let bin_dir = test_env.env_root().join("bin");
+    std::fs::create_dir(&bin_dir).unwrap();
+    let tool_path = bin_dir.join("not-executable-tool");
+    std::fs::write(&tool_path, "").unwrap();
+    std::fs::set_permissions(&tool_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    test_env.add_env_var("PATH", bin_dir.to_str().unwrap());
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+
+    // Files on $PATH which can't be executed are skipped
+    let output = work_dir.run_jj([
+        "diff",
+        "--tool=not-executable-tool",
+        "--config=ui.diff-tools=[':summary']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    [EOF]
+    ");
+
+    // So are file paths
+    let output = work_dir.run_jj([
+        "diff",
+        &format!("--tool={}", tool_path.display()),
+        "--config=ui.diff-tools=[':summary']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_diff_external_tool_fallback() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    std::fs::write(&edit_script, "print-files-after").unwrap();
+
+    // The first tool that can be found is used
+    let output = work_dir.run_jj([
+        "diff",
+        "--config=ui.diff-tools=['missing-diff-tool', 'fake-diff-editor']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    [EOF]
+    ");
+
+    // The tool specified by --tool is tried first
+    let output = work_dir.run_jj([
+        "diff",
+        "--tool=missing-diff-tool",
+        "--config=ui.diff-tools=[':summary']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    [EOF]
+    ");
+
+    // ui.diff-tools takes precedence over ui.diff-formatter
+    let output = work_dir.run_jj([
+        "diff",
+        "--config=ui.diff-formatter=:git",
+        "--config=ui.diff-tools=['missing-diff-tool', ':summary']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    [EOF]
+    ");
+
+    // The tools tried are listed if none of them can be found
+    let output = work_dir.run_jj([
+        "diff",
+        "--tool=missing-diff-tool",
+        "--config=ui.diff-tools=['another-missing-tool']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: None of the tools could be found: missing-diff-tool, another-missing-tool
+    Hint: Use --builtin to show the diff in the builtin format.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Invalid builtin format in config
+    let output = work_dir.run_jj(["diff", "--config=ui.diff-tools=[':bad']"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid type or value for ui.diff-tools
+    Caused by: Invalid builtin diff format: bad
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // --builtin always uses color-words
+    let output = work_dir.run_jj([
+        "diff",
+        "--config=ui.diff-tools=['fake-diff-editor']",
+        "--builtin",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Added regular file file1:
+            1: foo
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--tool=fake-diff-editor", "--builtin"]);
+    insta::assert_snapshot!(output, @r"
+    Added regular file file1:
+            1: foo
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "diff",
+        "--config=ui.diff-formatter=fake-diff-editor",
+        "--builtin",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Added regular file file1:
+            1: foo
+    [EOF]
+    ");
+
+    // --builtin can be combined with a short format
+    let output = work_dir.run_jj(["diff", "--tool=missing-diff-tool", "--builtin", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    Added regular file file1:
+            1: foo
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_do_chdir() {
     let mut test_env = TestEnvironment::default();
@@ -3721,6 +4257,31 @@ fn test_diff_binary() {
     4 files changed, 6 insertions(+), 6 deletions(-)
     [EOF]
     ");
+
+    // Binary patches can be included, with full object ids
+    let output = work_dir.run_jj(["diff", "--git", "--binary", "file1.png", "file2.png"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1.png b/file1.png
+    deleted file mode 100644
+    index 2b65b23c22953db3bdeca4a1ec6fc5c5d7c53cb2..0000000000000000000000000000000000000000
+    GIT binary patch
+    literal 0
+    Hc$@<O00001
+
+    literal 16
+    Xc%17D@N?(olHy8CN=`{lOJ@K8B_{-C
+
+    diff --git a/file2.png b/file2.png
+    index 7f036ce788241b5ff8adf8c1721e64b9598e3963..3bd1f0e29744a1f32b08d5650e62e2e62afb177c 100644
+    GIT binary patch
+    literal 8
+    Pc$`bi&*w@?EaCzH45k8(
+
+    literal 16
+    Xc%17D@N?(olHxKjG%_|ZHDdq(9&`f_
+
+    [EOF]
+    ");
 }
 
 #[test]
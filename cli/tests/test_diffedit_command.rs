@@ -173,6 +173,33 @@ fn test_diffedit() {
     ");
 }
 
+#[test]
+fn test_diffedit_ignores_diff_tools() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "a\n");
+
+    // ui.diff-tools only applies to diff formatting, not to the diff editor
+    std::fs::write(&edit_script, "reset file2").unwrap();
+    let output = work_dir.run_jj(["diffedit", "--config=ui.diff-tools=[':git']"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: qpvuntsm eb7b8a1f (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diffedit_new_file() {
     let mut test_env = TestEnvironment::default();
@@ -409,6 +409,65 @@ fn test_evolog_squash() {
     ");
 }
 
+#[test]
+fn test_evolog_note() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let template = r#"separate(" ", commit_id.short(), description.first_line()) ++ "\n""#;
+
+    work_dir.write_file("file", "a\n");
+    work_dir
+        .run_jj(["describe", "-m=a", "--evolog-note=add a description"])
+        .success();
+    work_dir.run_jj(["new", "root()", "-m=b"]).success();
+    work_dir
+        .run_jj([
+            "rebase",
+            "-r=description(a)",
+            "-d=@",
+            "--evolog-note=reorder",
+        ])
+        .success();
+
+    let output = work_dir.run_jj(["evolog", "-r=description(a)", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ○  9e2a023d28ea a
+    │  -- operation ec5644865604 (2001-02-03 08:05:10) rebase commit 7febbd3832d7a39691ec6be56c41a4ba3cd4ce79
+    │  -- note: reorder
+    ○  7febbd3832d7 a
+    │  -- operation 34920186583c (2001-02-03 08:05:08) describe commit 6b57e33cc56babbeaa6bcd6e2a296236b52ad93c
+    │  -- note: add a description
+    ○  6b57e33cc56b
+    │  -- operation eeeff3b1654c (2001-02-03 08:05:08) snapshot working copy
+    ○  e8849ae12c70
+       -- operation 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "evolog",
+        "-r=description(a)",
+        "--no-graph",
+        "--color=debug",
+        "-T",
+        template,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;4m<<log commit commit_id short::9e2a023d28ea>>[39m<<log commit:: >><<log commit description first_line::a>><<log commit::>>
+    -- operation [38;5;4m<<log operation id short::ec5644865604>>[39m<<log operation:: (>>[38;5;6m<<log operation time end local format::2001-02-03 08:05:10>>[39m<<log operation::) >><<log operation description first_line::rebase commit 7febbd3832d7a39691ec6be56c41a4ba3cd4ce79>>
+    <<evolog_note::-- note: reorder>>
+    [38;5;4m<<log commit commit_id short::7febbd3832d7>>[39m<<log commit:: >><<log commit description first_line::a>><<log commit::>>
+    -- operation [38;5;4m<<log operation id short::34920186583c>>[39m<<log operation:: (>>[38;5;6m<<log operation time end local format::2001-02-03 08:05:08>>[39m<<log operation::) >><<log operation description first_line::describe commit 6b57e33cc56babbeaa6bcd6e2a296236b52ad93c>>
+    <<evolog_note::-- note: add a description>>
+    [38;5;4m<<log commit commit_id short::6b57e33cc56b>>[39m<<log commit::>>
+    -- operation [38;5;4m<<log operation id short::eeeff3b1654c>>[39m<<log operation:: (>>[38;5;6m<<log operation time end local format::2001-02-03 08:05:08>>[39m<<log operation::) >><<log operation description first_line::snapshot working copy>>
+    [38;5;4m<<log commit commit_id short::e8849ae12c70>>[39m<<log commit::>>
+    -- operation [38;5;4m<<log operation id short::8f47435a3990>>[39m<<log operation:: (>>[38;5;6m<<log operation time end local format::2001-02-03 08:05:07>>[39m<<log operation::) >><<log operation description first_line::add workspace 'default'>>
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_evolog_with_no_template() {
     let test_env = TestEnvironment::default();
@@ -430,6 +489,8 @@ fn test_evolog_with_no_template() {
     - builtin_log_compact_full_description
     - builtin_log_detailed
     - builtin_log_node
+    - builtin_log_node_ancestry_path
+    - builtin_log_node_ancestry_path_ascii
     - builtin_log_node_ascii
     - builtin_log_oneline
     - builtin_op_log_comfortable
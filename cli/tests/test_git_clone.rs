@@ -270,8 +270,8 @@ fn test_git_clone_colocate() {
     // The old default bookmark "master" shouldn't exist.
     insta::assert_snapshot!(get_bookmark_output(&clone_dir), @r"
     main: qomsplrm ebeb70d8 message
-      @git: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @git [tracked, synced]: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -406,9 +406,9 @@ fn test_git_clone_remote_default_bookmark() {
     let clone_dir1 = test_env.work_dir("clone1");
     insta::assert_snapshot!(get_bookmark_output(&clone_dir1), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     main: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -435,9 +435,9 @@ fn test_git_clone_remote_default_bookmark() {
     "#);
     let clone_dir2 = test_env.work_dir("clone2");
     insta::assert_snapshot!(get_bookmark_output(&clone_dir2), @r"
-    feature1@origin: qomsplrm ebeb70d8 message
+    feature1@origin [untracked]: qomsplrm ebeb70d8 message
     main: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -458,8 +458,8 @@ fn test_git_clone_remote_default_bookmark() {
     let clone_dir3 = test_env.work_dir("clone3");
     insta::assert_snapshot!(get_bookmark_output(&clone_dir3), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
-    main@origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
+    main@origin [untracked]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -492,8 +492,8 @@ fn test_git_clone_remote_default_bookmark() {
     "#);
     let clone_dir4 = test_env.work_dir("clone4");
     insta::assert_snapshot!(get_bookmark_output(&clone_dir4), @r"
-    feature1@origin: qomsplrm ebeb70d8 message
-    main@origin: qomsplrm ebeb70d8 message
+    feature1@origin [untracked]: qomsplrm ebeb70d8 message
+    main@origin [untracked]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -521,9 +521,9 @@ fn test_git_clone_remote_default_bookmark() {
     let clone_dir5 = test_env.work_dir("clone5");
     insta::assert_snapshot!(get_bookmark_output(&clone_dir5), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     main: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 }
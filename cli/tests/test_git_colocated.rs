@@ -480,7 +480,7 @@ fn test_git_colocated_bookmark_forget() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     foo: rlvkpnrz 43444d88 (empty) (no description set)
-      @git: rlvkpnrz 43444d88 (empty) (no description set)
+      @git [tracked, synced]: rlvkpnrz 43444d88 (empty) (no description set)
     [EOF]
     ");
 
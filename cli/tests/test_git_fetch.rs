@@ -105,7 +105,7 @@ fn test_git_fetch_with_default_config() {
 
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
-    origin@origin: qmyrypzk ab8b299e message
+    origin@origin [untracked]: qmyrypzk ab8b299e message
     [EOF]
     ");
 }
@@ -121,7 +121,7 @@ fn test_git_fetch_default_remote() {
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     origin: qmyrypzk ab8b299e message
-      @origin: qmyrypzk ab8b299e message
+      @origin [tracked, synced]: qmyrypzk ab8b299e message
     [EOF]
     ");
 }
@@ -143,7 +143,7 @@ fn test_git_fetch_single_remote() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -159,7 +159,7 @@ fn test_git_fetch_single_remote_all_remotes_flag() {
     work_dir.run_jj(["git", "fetch", "--all-remotes"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -177,7 +177,7 @@ fn test_git_fetch_single_remote_from_arg() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -194,7 +194,7 @@ fn test_git_fetch_single_remote_from_config() {
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -213,9 +213,9 @@ fn test_git_fetch_multiple_remotes() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     rem2: pzqqpnpo 44c57802 message
-      @rem2: pzqqpnpo 44c57802 message
+      @rem2 [tracked, synced]: pzqqpnpo 44c57802 message
     [EOF]
     ");
 }
@@ -316,9 +316,9 @@ fn test_git_fetch_all_remotes() {
     work_dir.run_jj(["git", "fetch", "--all-remotes"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     rem2: pzqqpnpo 44c57802 message
-      @rem2: pzqqpnpo 44c57802 message
+      @rem2 [tracked, synced]: pzqqpnpo 44c57802 message
     [EOF]
     ");
 }
@@ -336,9 +336,9 @@ fn test_git_fetch_multiple_remotes_from_config() {
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     rem2: pzqqpnpo 44c57802 message
-      @rem2: pzqqpnpo 44c57802 message
+      @rem2 [tracked, synced]: pzqqpnpo 44c57802 message
     [EOF]
     ");
 }
@@ -375,7 +375,7 @@ fn test_git_fetch_nonexistent_remote() {
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
-    rem1@rem1: ppspxspk 4acd0343 message
+    rem1@rem1 [untracked]: ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -396,7 +396,7 @@ fn test_git_fetch_nonexistent_remote_from_config() {
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
-    rem1@rem1: ppspxspk 4acd0343 message
+    rem1@rem1 [untracked]: ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -462,8 +462,8 @@ fn test_git_fetch_from_remote_named_git() {
     let output = work_dir.run_jj(["bookmark", "list", "--all-remotes"]);
     insta::assert_snapshot!(output, @r"
     git: vkponlun 400c483d message
-      @bar: vkponlun 400c483d message
-      @git: vkponlun 400c483d message
+      @bar [tracked, synced]: vkponlun 400c483d message
+      @git [tracked, synced]: vkponlun 400c483d message
     [EOF]
     ------- stderr -------
     Done importing changes from the underlying Git repo.
@@ -505,7 +505,7 @@ fn test_git_fetch_prune_before_updating_tips() {
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     origin: qmyrypzk ab8b299e message
-      @origin: qmyrypzk ab8b299e message
+      @origin [tracked, synced]: qmyrypzk ab8b299e message
     [EOF]
     ");
 
@@ -525,7 +525,7 @@ fn test_git_fetch_prune_before_updating_tips() {
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     origin/subname: qmyrypzk ab8b299e message
-      @origin: qmyrypzk ab8b299e message
+      @origin [tracked, synced]: qmyrypzk ab8b299e message
     [EOF]
     ");
 }
@@ -556,7 +556,7 @@ fn test_git_fetch_conflicting_bookmarks() {
     rem1 (conflicted):
       + kkmpptxz 2b17ac71 (empty) (no description set)
       + ppspxspk 4acd0343 message
-      @rem1 (behind by 1 commits): ppspxspk 4acd0343 message
+      @rem1 [tracked] (behind by 1 commits): ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -581,7 +581,7 @@ fn test_git_fetch_conflicting_bookmarks_colocated() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     rem1: zsuskuln c2934cfb (empty) (no description set)
-      @git: zsuskuln c2934cfb (empty) (no description set)
+      @git [tracked, synced]: zsuskuln c2934cfb (empty) (no description set)
     [EOF]
     ");
 
@@ -594,8 +594,8 @@ fn test_git_fetch_conflicting_bookmarks_colocated() {
     rem1 (conflicted):
       + zsuskuln c2934cfb (empty) (no description set)
       + ppspxspk 4acd0343 message
-      @git (behind by 1 commits): zsuskuln c2934cfb (empty) (no description set)
-      @rem1 (behind by 1 commits): ppspxspk 4acd0343 message
+      @git [tracked] (behind by 1 commits): zsuskuln c2934cfb (empty) (no description set)
+      @rem1 [tracked] (behind by 1 commits): ppspxspk 4acd0343 message
     [EOF]
     ");
 }
@@ -679,13 +679,13 @@ fn test_git_fetch_all() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
     a1: mzvwutvl c8303692 a1
-      @origin: mzvwutvl c8303692 a1
+      @origin [tracked, synced]: mzvwutvl c8303692 a1
     a2: yqosqzyt d4d535f1 a2
-      @origin: yqosqzyt d4d535f1 a2
+      @origin [tracked, synced]: yqosqzyt d4d535f1 a2
     b: yostqsxw bc83465a b
-      @origin: yostqsxw bc83465a b
+      @origin [tracked, synced]: yostqsxw bc83465a b
     trunk1: kkmpptxz 38288177 trunk1
-      @origin: kkmpptxz 38288177 trunk1
+      @origin [tracked, synced]: kkmpptxz 38288177 trunk1
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&target_dir), @r#"
@@ -736,13 +736,13 @@ fn test_git_fetch_all() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
     a1: mzvwutvl c8303692 a1
-      @origin: mzvwutvl c8303692 a1
+      @origin [tracked, synced]: mzvwutvl c8303692 a1
     a2: yqosqzyt d4d535f1 a2
-      @origin: yqosqzyt d4d535f1 a2
+      @origin [tracked, synced]: yqosqzyt d4d535f1 a2
     b: yostqsxw 0fbbc495 new_descr_for_b_to_create_conflict
-      @origin (ahead by 1 commits, behind by 1 commits): yostqsxw hidden bc83465a b
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): yostqsxw hidden bc83465a b
     trunk1: kkmpptxz 38288177 trunk1
-      @origin: kkmpptxz 38288177 trunk1
+      @origin [tracked, synced]: kkmpptxz 38288177 trunk1
     [EOF]
     ");
     let output = target_dir.run_jj(["git", "fetch"]);
@@ -757,18 +757,18 @@ fn test_git_fetch_all() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
     a1: mzvwutvl 798c5e24 a1
-      @origin: mzvwutvl 798c5e24 a1
+      @origin [tracked, synced]: mzvwutvl 798c5e24 a1
     a2: yqosqzyt baad96fe a2
-      @origin: yqosqzyt baad96fe a2
+      @origin [tracked, synced]: yqosqzyt baad96fe a2
     b (conflicted):
       - yostqsxw hidden bc83465a b
       + yostqsxw?? 0fbbc495 new_descr_for_b_to_create_conflict
       + yostqsxw?? 6fc6fe17 b
-      @origin (behind by 1 commits): yostqsxw?? 6fc6fe17 b
+      @origin [tracked] (behind by 1 commits): yostqsxw?? 6fc6fe17 b
     trunk1: kkmpptxz 38288177 trunk1
-      @origin: kkmpptxz 38288177 trunk1
+      @origin [tracked, synced]: kkmpptxz 38288177 trunk1
     trunk2: uyznsvlq e80d998a trunk2
-      @origin: uyznsvlq e80d998a trunk2
+      @origin [tracked, synced]: uyznsvlq e80d998a trunk2
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&target_dir), @r#"
@@ -860,7 +860,7 @@ fn test_git_fetch_some_of_many_bookmarks() {
     // ...check what the intermediate state looks like...
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
     b: yostqsxw bc83465a b
-      @origin: yostqsxw bc83465a b
+      @origin [tracked, synced]: yostqsxw bc83465a b
     [EOF]
     ");
     // ...then fetch two others with a glob.
@@ -963,14 +963,14 @@ fn test_git_fetch_some_of_many_bookmarks() {
     // We left a2 where it was before, let's see how `jj bookmark list` sees this.
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
     a1: mzvwutvl bc7e74c2 a1
-      @origin: mzvwutvl bc7e74c2 a1
+      @origin [tracked, synced]: mzvwutvl bc7e74c2 a1
     a2: yqosqzyt d4d535f1 a2
-      @origin: yqosqzyt d4d535f1 a2
+      @origin [tracked, synced]: yqosqzyt d4d535f1 a2
     b (conflicted):
       - yostqsxw hidden bc83465a b
       + yostqsxw?? c62db311 new_descr_for_b_to_create_conflict
       + yostqsxw?? 2b30dbc9 b
-      @origin (behind by 1 commits): yostqsxw?? 2b30dbc9 b
+      @origin [tracked] (behind by 1 commits): yostqsxw?? 2b30dbc9 b
     [EOF]
     ");
     // Now, let's fetch a2 and double-check that fetching a1 and b again doesn't do
@@ -999,14 +999,14 @@ fn test_git_fetch_some_of_many_bookmarks() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
     a1: mzvwutvl bc7e74c2 a1
-      @origin: mzvwutvl bc7e74c2 a1
+      @origin [tracked, synced]: mzvwutvl bc7e74c2 a1
     a2: yqosqzyt 841140b1 a2
-      @origin: yqosqzyt 841140b1 a2
+      @origin [tracked, synced]: yqosqzyt 841140b1 a2
     b (conflicted):
       - yostqsxw hidden bc83465a b
       + yostqsxw?? c62db311 new_descr_for_b_to_create_conflict
       + yostqsxw?? 2b30dbc9 b
-      @origin (behind by 1 commits): yostqsxw?? 2b30dbc9 b
+      @origin [tracked] (behind by 1 commits): yostqsxw?? 2b30dbc9 b
     [EOF]
     ");
 }
@@ -1054,7 +1054,7 @@ fn test_git_fetch_bookmarks_some_missing() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     origin: qmyrypzk ab8b299e message
-      @origin: qmyrypzk ab8b299e message
+      @origin [tracked, synced]: qmyrypzk ab8b299e message
     [EOF]
     ");
 
@@ -1073,13 +1073,13 @@ fn test_git_fetch_bookmarks_some_missing() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     origin: qmyrypzk ab8b299e message
-      @origin: qmyrypzk ab8b299e message
+      @origin [tracked, synced]: qmyrypzk ab8b299e message
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     rem2: pzqqpnpo 44c57802 message
-      @rem2: pzqqpnpo 44c57802 message
+      @rem2 [tracked, synced]: pzqqpnpo 44c57802 message
     rem3: wrzwlmys 45a3faef message
-      @rem3: wrzwlmys 45a3faef message
+      @rem3 [tracked, synced]: wrzwlmys 45a3faef message
     [EOF]
     ");
 
@@ -1095,13 +1095,13 @@ fn test_git_fetch_bookmarks_some_missing() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     origin: qmyrypzk ab8b299e message
-      @origin: qmyrypzk ab8b299e message
+      @origin [tracked, synced]: qmyrypzk ab8b299e message
     rem1: ppspxspk 4acd0343 message
-      @rem1: ppspxspk 4acd0343 message
+      @rem1 [tracked, synced]: ppspxspk 4acd0343 message
     rem2: pzqqpnpo 44c57802 message
-      @rem2: pzqqpnpo 44c57802 message
+      @rem2 [tracked, synced]: pzqqpnpo 44c57802 message
     rem3: wrzwlmys 45a3faef message
-      @rem3: wrzwlmys 45a3faef message
+      @rem3 [tracked, synced]: wrzwlmys 45a3faef message
     [EOF]
     ");
 }
@@ -1266,7 +1266,7 @@ fn test_fetch_undo_what() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     b: yostqsxw bc83465a b
-      @origin: yostqsxw bc83465a b
+      @origin [tracked, synced]: yostqsxw bc83465a b
     [EOF]
     ");
 
@@ -1280,7 +1280,7 @@ fn test_fetch_undo_what() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     b (deleted)
-      @origin: yostqsxw hidden bc83465a b
+      @origin [tracked]: yostqsxw hidden bc83465a b
     [EOF]
     ");
 
@@ -1291,7 +1291,7 @@ fn test_fetch_undo_what() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     b (deleted)
-      @origin: yostqsxw hidden bc83465a b
+      @origin [tracked]: yostqsxw hidden bc83465a b
     newbookmark: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
@@ -1336,7 +1336,7 @@ fn test_git_fetch_remove_fetch() {
     origin (conflicted):
       + qpvuntsm e8849ae1 (empty) (no description set)
       + qmyrypzk ab8b299e message
-      @origin (behind by 1 commits): qmyrypzk ab8b299e message
+      @origin [tracked] (behind by 1 commits): qmyrypzk ab8b299e message
     [EOF]
     ");
 
@@ -1365,7 +1365,7 @@ fn test_git_fetch_remove_fetch() {
     origin (conflicted):
       + qpvuntsm e8849ae1 (empty) (no description set)
       + qmyrypzk ab8b299e message
-      @origin (behind by 1 commits): qmyrypzk ab8b299e message
+      @origin [tracked] (behind by 1 commits): qmyrypzk ab8b299e message
     [EOF]
     ");
 }
@@ -1391,7 +1391,7 @@ fn test_git_fetch_rename_fetch() {
     origin (conflicted):
       + qpvuntsm e8849ae1 (empty) (no description set)
       + qmyrypzk ab8b299e message
-      @origin (behind by 1 commits): qmyrypzk ab8b299e message
+      @origin [tracked] (behind by 1 commits): qmyrypzk ab8b299e message
     [EOF]
     ");
 
@@ -1402,7 +1402,7 @@ fn test_git_fetch_rename_fetch() {
     origin (conflicted):
       + qpvuntsm e8849ae1 (empty) (no description set)
       + qmyrypzk ab8b299e message
-      @upstream (behind by 1 commits): qmyrypzk ab8b299e message
+      @upstream [tracked] (behind by 1 commits): qmyrypzk ab8b299e message
     [EOF]
     ");
 
@@ -1627,7 +1627,7 @@ fn test_git_fetch_remote_only_bookmark() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 
@@ -1653,8 +1653,8 @@ fn test_git_fetch_remote_only_bookmark() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qomsplrm ebeb70d8 message
-      @origin: qomsplrm ebeb70d8 message
-    feature2@origin: qomsplrm ebeb70d8 message
+      @origin [tracked, synced]: qomsplrm ebeb70d8 message
+    feature2@origin [untracked]: qomsplrm ebeb70d8 message
     [EOF]
     ");
 }
@@ -1692,10 +1692,10 @@ fn test_git_fetch_preserve_commits_across_repos() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature: srwrtuky 16ec9ef2 message
-      @fork: srwrtuky 16ec9ef2 message
+      @fork [tracked, synced]: srwrtuky 16ec9ef2 message
     upstream: zkvzklqn bcd7cd77 message
-      @fork: zkvzklqn bcd7cd77 message
-      @upstream: zkvzklqn bcd7cd77 message
+      @fork [tracked, synced]: zkvzklqn bcd7cd77 message
+      @upstream [tracked, synced]: zkvzklqn bcd7cd77 message
     [EOF]
     ");
 
@@ -1751,8 +1751,8 @@ fn test_git_fetch_preserve_commits_across_repos() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     upstream: trrkvuqr f3e9250b merge
-      @fork (behind by 2 commits): zkvzklqn bcd7cd77 message
-      @upstream: trrkvuqr f3e9250b merge
+      @fork [tracked] (behind by 2 commits): zkvzklqn bcd7cd77 message
+      @upstream [tracked, synced]: trrkvuqr f3e9250b merge
     [EOF]
     ");
 }
@@ -41,7 +41,7 @@ fn test_resolution_of_git_tracking_bookmarks() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm 384a1421 (empty) new_message
-      @git (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden a7f9930b (empty) old_message
+      @git [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden a7f9930b (empty) old_message
     [EOF]
     ");
 
@@ -183,7 +183,7 @@ fn test_git_import_undo() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     a: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 
@@ -204,7 +204,7 @@ fn test_git_import_undo() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     a: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 }
@@ -245,7 +245,7 @@ fn test_git_import_move_export_with_default_undo() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     a: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 
@@ -256,14 +256,14 @@ fn test_git_import_move_export_with_default_undo() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     a: yqosqzyt 507c0edc (empty) (no description set)
-      @git (behind by 1 commits): qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked] (behind by 1 commits): qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
     let output = work_dir.run_jj(["git", "export"]);
     insta::assert_snapshot!(output, @"");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     a: yqosqzyt 507c0edc (empty) (no description set)
-      @git: yqosqzyt 507c0edc (empty) (no description set)
+      @git [tracked, synced]: yqosqzyt 507c0edc (empty) (no description set)
     [EOF]
     ");
 
@@ -300,7 +300,7 @@ fn test_git_import_move_export_with_default_undo() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     a: yqosqzyt 507c0edc (empty) (no description set)
-      @git: yqosqzyt 507c0edc (empty) (no description set)
+      @git [tracked, synced]: yqosqzyt 507c0edc (empty) (no description set)
     [EOF]
     ");
 }
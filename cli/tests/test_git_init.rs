@@ -564,11 +564,11 @@ fn test_git_init_colocated_via_git_repo_path_imported_refs() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&local_dir), @r"
     local-remote: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
-      @origin: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
+      @origin [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     remote-only: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
-      @origin: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
+      @origin [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 
@@ -589,9 +589,9 @@ fn test_git_init_colocated_via_git_repo_path_imported_refs() {
     "#);
     insta::assert_snapshot!(get_bookmark_output(&local_dir), @r"
     local-remote: qpvuntsm e8849ae1 (empty) (no description set)
-      @git: qpvuntsm e8849ae1 (empty) (no description set)
-    local-remote@origin: qpvuntsm e8849ae1 (empty) (no description set)
-    remote-only@origin: qpvuntsm e8849ae1 (empty) (no description set)
+      @git [tracked, synced]: qpvuntsm e8849ae1 (empty) (no description set)
+    local-remote@origin [untracked]: qpvuntsm e8849ae1 (empty) (no description set)
+    remote-only@origin [untracked]: qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
     ");
 }
@@ -68,9 +68,9 @@ fn test_git_push_nothing() {
     // Show the setup. `insta` has trouble if this is done inside `set_up()`
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: qpvuntsm 9b2e76de (empty) description 1
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
     // No bookmarks to push yet
@@ -104,9 +104,9 @@ fn test_git_push_current_bookmark() {
     // Check the setup
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: qpvuntsm e5ce6d9a (empty) modified bookmark1 commit
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 9b2e76de (empty) description 1
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 9b2e76de (empty) description 1
     bookmark2: yostqsxw 88ca14a7 (empty) foo
-      @origin (behind by 1 commits): zsuskuln 38a20473 (empty) description 2
+      @origin [tracked] (behind by 1 commits): zsuskuln 38a20473 (empty) description 2
     my-bookmark: yostqsxw 88ca14a7 (empty) foo
     [EOF]
     ");
@@ -130,11 +130,11 @@ fn test_git_push_current_bookmark() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: qpvuntsm e5ce6d9a (empty) modified bookmark1 commit
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 9b2e76de (empty) description 1
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 9b2e76de (empty) description 1
     bookmark2: yostqsxw 88ca14a7 (empty) foo
-      @origin: yostqsxw 88ca14a7 (empty) foo
+      @origin [tracked, synced]: yostqsxw 88ca14a7 (empty) foo
     my-bookmark: yostqsxw 88ca14a7 (empty) foo
-      @origin: yostqsxw 88ca14a7 (empty) foo
+      @origin [tracked, synced]: yostqsxw 88ca14a7 (empty) foo
     [EOF]
     ");
 
@@ -351,9 +351,9 @@ fn test_git_push_sideways_unexpectedly_moved() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&origin_dir), @r"
     bookmark1: vruxwmqv 7ce4029e remote
-      @git (behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
+      @git [tracked] (behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @git: zsuskuln 38a20473 (empty) description 2
+      @git [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
     origin_dir.run_jj(["git", "export"]).success();
@@ -366,9 +366,9 @@ fn test_git_push_sideways_unexpectedly_moved() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: kmkuslsw 827b8a38 local
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -419,9 +419,9 @@ fn test_git_push_deletion_unexpectedly_moved() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&origin_dir), @r"
     bookmark1: vruxwmqv 7ce4029e remote
-      @git (behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
+      @git [tracked] (behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @git: zsuskuln 38a20473 (empty) description 2
+      @git [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
     origin_dir.run_jj(["git", "export"]).success();
@@ -432,9 +432,9 @@ fn test_git_push_deletion_unexpectedly_moved() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1 (deleted)
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -465,9 +465,9 @@ fn test_git_push_unexpectedly_deleted() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&origin_dir), @r"
     bookmark1 (deleted)
-      @git: qpvuntsm 9b2e76de (empty) description 1
+      @git [tracked]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @git: zsuskuln 38a20473 (empty) description 2
+      @git [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
     origin_dir.run_jj(["git", "export"]).success();
@@ -480,9 +480,9 @@ fn test_git_push_unexpectedly_deleted() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: kpqxywon 09919fb0 local
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -505,9 +505,9 @@ fn test_git_push_unexpectedly_deleted() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1 (deleted)
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -549,7 +549,7 @@ fn test_git_push_creation_unexpectedly_already_exists() {
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: yostqsxw a43cb801 new bookmark1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -610,11 +610,11 @@ fn test_git_push_locally_created_and_rewritten() {
     work_dir.run_jj(["describe", "-mlocal 2"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: qpvuntsm 9b2e76de (empty) description 1
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     my: vruxwmqv 5eb416c1 (empty) local 2
-      @origin (ahead by 1 commits, behind by 1 commits): vruxwmqv hidden e0cba5e4 (empty) local 1
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): vruxwmqv hidden e0cba5e4 (empty) local 1
     [EOF]
     ");
     let output = work_dir.run_jj(["git", "push"]);
@@ -626,6 +626,215 @@ fn test_git_push_locally_created_and_rewritten() {
     ");
 }
 
+#[test]
+fn test_git_push_bookmark_created_with_remote() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+
+    // The new bookmark tracks the remote bookmark which doesn't exist yet
+    work_dir.run_jj(["new", "root()", "-mlocal 1"]).success();
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "my", "--tracked=origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 bookmarks pointing to vruxwmqv e0cba5e4 my | (empty) local 1
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
+    my: vruxwmqv e0cba5e4 (empty) local 1
+      @origin [tracked] (not pushed yet)
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-n1",
+        "--no-graph",
+        "--op-diff",
+        "-Tdescription",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    create bookmark my pointing to commit e0cba5e497eeb9bbc79d347029d5de5d8900f918
+    Changed local bookmarks:
+    my:
+    + vruxwmqv e0cba5e4 my | (empty) local 1
+    - (absent)
+
+    Changed pending remote bookmarks:
+    + my@origin
+    [EOF]
+    ");
+
+    // It can be pushed without --allow-new
+    let output = work_dir.run_jj(["git", "push"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark my to e0cba5e497ee
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
+    my: vruxwmqv e0cba5e4 (empty) local 1
+      @origin [tracked, synced]: vruxwmqv e0cba5e4 (empty) local 1
+    [EOF]
+    ");
+
+    // Existing remote bookmark at the same revision gets tracked
+    work_dir
+        .run_jj(["bookmark", "untrack", "bookmark1@origin"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark1"])
+        .success();
+    let output = work_dir.run_jj([
+        "bookmark",
+        "create",
+        "-rbookmark1@origin",
+        "bookmark1",
+        "--remote=origin",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 bookmarks pointing to qpvuntsm 9b2e76de bookmark1 | (empty) description 1
+    [EOF]
+    ");
+
+    // Existing remote bookmark at a different revision isn't overwritten
+    work_dir
+        .run_jj(["bookmark", "untrack", "bookmark2@origin"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark2"])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "bookmark2", "--tracked=origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Remote bookmark already exists at a different revision: bookmark2@origin
+    Hint: Use `jj bookmark track bookmark2@origin` to track it, and `jj bookmark set` to move the local bookmark.
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2@origin [untracked]: zsuskuln 38a20473 (empty) description 2
+    my: vruxwmqv e0cba5e4 (empty) local 1
+      @origin [tracked, synced]: vruxwmqv e0cba5e4 (empty) local 1
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "foo", "--tracked=git"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot track bookmarks on the Git-tracking remote: git
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "foo", "--tracked=typo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No git remote named 'typo'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Forgetting the local bookmark also forgets the pending remote bookmark
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo", "--tracked=origin"])
+        .success();
+    work_dir.run_jj(["bookmark", "forget", "foo"]).success();
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-n1",
+        "--no-graph",
+        "--op-diff",
+        "-Tdescription",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    forget bookmark foo
+    Changed local bookmarks:
+    foo:
+    + (absent)
+    - vruxwmqv e0cba5e4 my | (empty) local 1
+
+    Changed pending remote bookmarks:
+    - foo@origin
+    [EOF]
+    ");
+
+    // So does deleting or renaming the local bookmark
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "-r@",
+            "foo",
+            "bar",
+            "--tracked=origin",
+        ])
+        .success();
+    work_dir.run_jj(["bookmark", "delete", "foo"]).success();
+    work_dir
+        .run_jj(["bookmark", "rename", "bar", "baz"])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "list", "foo", "bar", "baz"]);
+    insta::assert_snapshot!(output, @r"
+    baz: vruxwmqv e0cba5e4 (empty) local 1
+    [EOF]
+    ");
+
+    // Pending remote bookmarks can be untracked
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "-r@",
+            "qux",
+            "quux",
+            "--tracked=origin",
+        ])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "untrack", "--verbose", "qux@origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Stopped tracking 1 remote bookmarks.
+      qux@origin: tracked -> untracked, not pushed yet
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "untrack", "glob:qu*@origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Stopped tracking 1 remote bookmarks.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--tracked", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Re-created bookmarks aren't pushed as tracked bookmarks
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo", "bar"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--tracked"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_multiple() {
     let test_env = TestEnvironment::default();
@@ -644,9 +853,9 @@ fn test_git_push_multiple() {
     // Check the setup
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1 (deleted)
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: yqosqzyt 352fa187 (empty) foo
-      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 38a20473 (empty) description 2
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): zsuskuln 38a20473 (empty) description 2
     my-bookmark: yqosqzyt 352fa187 (empty) foo
     [EOF]
     ");
@@ -758,9 +967,9 @@ fn test_git_push_multiple() {
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark2: yqosqzyt 352fa187 (empty) foo
-      @origin: yqosqzyt 352fa187 (empty) foo
+      @origin [tracked, synced]: yqosqzyt 352fa187 (empty) foo
     my-bookmark: yqosqzyt 352fa187 (empty) foo
-      @origin: yqosqzyt 352fa187 (empty) foo
+      @origin [tracked, synced]: yqosqzyt 352fa187 (empty) foo
     [EOF]
     ");
     let output = work_dir.run_jj(["log", "-rall()"]);
@@ -1046,7 +1255,7 @@ fn test_git_push_changes_with_name_deleted_tracked() {
         .success();
     insta::assert_snapshot!(output, @r"
     b1 (deleted)
-      @origin: kpqxywon 08f401c1 pushed
+      @origin [tracked]: kpqxywon 08f401c1 pushed
     [EOF]
     ------- stderr -------
     Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
@@ -1081,7 +1290,7 @@ fn test_git_push_changes_with_name_deleted_tracked() {
         .run_jj(["bookmark", "list", "--all", "b1"])
         .success();
     insta::assert_snapshot!(output, @r"
-    b1@origin: kpqxywon 08f401c1 pushed
+    b1@origin [untracked]: kpqxywon 08f401c1 pushed
     [EOF]
     ");
     let output = work_dir.run_jj(["git", "push", "--named", "b1=@", "--remote=another_remote"]);
@@ -1096,8 +1305,8 @@ fn test_git_push_changes_with_name_deleted_tracked() {
         .success();
     insta::assert_snapshot!(output, @r"
     b1: kpqxywon 08f401c1 pushed
-      @another_remote: kpqxywon 08f401c1 pushed
-    b1@origin: kpqxywon 08f401c1 pushed
+      @another_remote [tracked, synced]: kpqxywon 08f401c1 pushed
+    b1@origin [untracked]: kpqxywon 08f401c1 pushed
     [EOF]
     ");
 }
@@ -1145,7 +1354,7 @@ fn test_git_push_changes_with_name_untracked_or_forgotten() {
         .run_jj(["bookmark", "list", "--all", "b1"])
         .success();
     insta::assert_snapshot!(output, @r"
-    b1@origin: yostqsxw 767b63a5 pushed_to_remote
+    b1@origin [untracked]: yostqsxw 767b63a5 pushed_to_remote
     [EOF]
     ");
 
@@ -1720,11 +1929,11 @@ fn test_git_push_conflicting_bookmarks() {
     work_dir.run_jj(["git", "fetch"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: qpvuntsm 9b2e76de (empty) description 1
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2 (conflicted):
       + yostqsxw ebedbe63 (empty) description 3
       + zsuskuln 38a20473 (empty) description 2
-      @origin (behind by 1 commits): zsuskuln 38a20473 (empty) description 2
+      @origin [tracked] (behind by 1 commits): zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -1834,9 +2043,9 @@ fn test_git_push_tracked_vs_all() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: vruxwmqv d7607a25 (empty) moved bookmark1
-    bookmark1@origin: qpvuntsm 9b2e76de (empty) description 1
+    bookmark1@origin [untracked]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2 (deleted)
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked]: zsuskuln 38a20473 (empty) description 2
     bookmark3: znkkpsqq 0004a65e (empty) moved bookmark2
     [EOF]
     ");
@@ -1867,8 +2076,8 @@ fn test_git_push_tracked_vs_all() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: vruxwmqv d7607a25 (empty) moved bookmark1
-    bookmark1@origin: qpvuntsm 9b2e76de (empty) description 1
-    bookmark2@origin: zsuskuln 38a20473 (empty) description 2
+    bookmark1@origin [untracked]: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2@origin [untracked]: zsuskuln 38a20473 (empty) description 2
     bookmark3: znkkpsqq 0004a65e (empty) moved bookmark2
     [EOF]
     ");
@@ -2161,9 +2370,9 @@ fn test_git_push_rejected_by_remote() {
     // show repo state
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     bookmark1: qpvuntsm 9b2e76de (empty) description 1
-      @origin: qpvuntsm 9b2e76de (empty) description 1
+      @origin [tracked, synced]: qpvuntsm 9b2e76de (empty) description 1
     bookmark2: zsuskuln 38a20473 (empty) description 2
-      @origin: zsuskuln 38a20473 (empty) description 2
+      @origin [tracked, synced]: zsuskuln 38a20473 (empty) description 2
     [EOF]
     ");
 
@@ -1066,6 +1066,7 @@ fn test_help() {
       -t, --to <REVSET>          Edit changes in this revision
           --tool <NAME>          Specify diff editor to be used
           --restore-descendants  Preserve the content (not the diff) when rebasing descendants
+          --evolog-note <TEXT>   Record a note explaining the edit, shown in `jj evolog`
       -h, --help                 Print help (see more with '--help')
 
     Global Options:
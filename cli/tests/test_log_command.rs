@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::common::create_commit_with_files;
 use crate::common::to_toml_value;
 use crate::common::TestEnvironment;
 
@@ -53,6 +54,8 @@ fn test_log_with_no_template() {
     - builtin_log_compact_full_description
     - builtin_log_detailed
     - builtin_log_node
+    - builtin_log_node_ancestry_path
+    - builtin_log_node_ancestry_path_ascii
     - builtin_log_node_ascii
     - builtin_log_oneline
     - builtin_op_log_comfortable
@@ -377,6 +380,236 @@ fn test_log_null_terminate_multiline_descriptions() {
     );
 }
 
+#[test]
+fn test_log_record_separator() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["commit", "-m", "commit 1 line 1", "-m", "commit 1 line 2"])
+        .success();
+    work_dir
+        .run_jj(["describe", "-m", "commit 2 line 1", "-m", "commit 2 line 2"])
+        .success();
+
+    let output = work_dir
+        .run_jj([
+            "log",
+            "-r",
+            "~root()",
+            "-T",
+            "description",
+            "--no-graph",
+            "--record-separator=---\n",
+        ])
+        .success();
+    insta::assert_debug_snapshot!(
+        output.stdout.normalized(),
+        @r#""commit 2 line 1\n\ncommit 2 line 2\n---\ncommit 1 line 1\n\ncommit 1 line 2\n---\n""#
+    );
+
+    let output = work_dir
+        .run_jj([
+            "log",
+            "-r",
+            "~root()",
+            "-T",
+            "description.first_line()",
+            "--no-graph",
+            "--record-separator=,",
+            "--no-trailing-separator",
+        ])
+        .success();
+    insta::assert_debug_snapshot!(
+        output.stdout.normalized(),
+        @r#""commit 2 line 1,commit 1 line 1""#
+    );
+
+    // No separator is written if there are no revisions
+    let output = work_dir
+        .run_jj(["log", "-r", "none()", "--no-graph", "--record-separator=,"])
+        .success();
+    insta::assert_debug_snapshot!(
+        output.stdout.normalized(),
+        @r#""""#
+    );
+
+    let output = work_dir.run_jj(["log", "--record-separator=,"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --no-graph
+
+    Usage: jj log --no-graph --record-separator <SEPARATOR> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_log_columns() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir
+        .run_jj(["describe", "-m", "tab\there", "-m", "back\\slash"])
+        .success();
+
+    // Fields are separated by tabs, and the separator is escaped
+    let output = work_dir
+        .run_jj([
+            "log",
+            "-r~root()",
+            "--no-graph",
+            "--column=change_id.short()",
+            "--column=description",
+        ])
+        .success();
+    insta::assert_debug_snapshot!(
+        output.stdout.normalized(),
+        @r#""rlvkpnrzqnoo\ttab\\there\\n\\nback\\\\slash\\n\nqpvuntsmwlqt\tfirst\\n\n""#
+    );
+
+    // Each template is parsed separately, so commas don't split columns
+    let output = work_dir.run_jj([
+        "log",
+        "-r~root()",
+        "--no-graph",
+        r#"--column=separate(",", "a", "b")"#,
+        r#"--column=",""#,
+        "--column=description.first_line()",
+        "--column-sep=,",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    a\x2cb,\x2c,tab	here
+    a\x2cb,\x2c,first
+    [EOF]
+    ");
+
+    // Whole multi-byte separators are escaped
+    let output = work_dir.run_jj([
+        "log",
+        "-r@-",
+        "--no-graph",
+        r#"--column="a│b""#,
+        "--column=description.first_line()",
+        "--column-sep=│",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    a\xe2\x94\x82b│first
+    [EOF]
+    ");
+
+    // Rows are NUL-terminated with --null
+    let output = work_dir
+        .run_jj([
+            "log",
+            "-r~root()",
+            "--no-graph",
+            "--column=change_id.short()",
+            "--column=description",
+            "--null",
+        ])
+        .success();
+    insta::assert_debug_snapshot!(
+        output.stdout.normalized(),
+        @r#""rlvkpnrzqnoo\ttab\\there\n\nback\\\\slash\n\0qpvuntsmwlqt\tfirst\n\0""#
+    );
+
+    // --column requires --no-graph
+    let output = work_dir.run_jj(["log", "--column", "change_id"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --no-graph
+
+    Usage: jj log --no-graph --column <TEMPLATE> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_log_highlight() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["describe", "-m", "TODO: fix the todo list"])
+        .success();
+    let template = r#"label("bookmark", description.first_line()) ++ "\n""#;
+
+    // Matches are highlighted on top of the template colors
+    let output = work_dir.run_jj([
+        "--color=always",
+        "log",
+        "-r@",
+        "--no-graph",
+        "-T",
+        template,
+        "--highlight=TODO",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [7m[38;5;5mTODO[27m: fix the todo list[39m
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "--color=always",
+        "log",
+        "-r@",
+        "-T",
+        template,
+        "--highlight=regex-i:to+do",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [1m[38;5;2m@[0m  [7m[38;5;5mTODO[27m: fix the [7mtodo[27m list[39m
+    │
+    ~
+    [EOF]
+    ");
+
+    // No-op without colors
+    let output = work_dir.run_jj(["log", "-r@", "-T", template, "--highlight=TODO"]);
+    insta::assert_snapshot!(output, @r"
+    @  TODO: fix the todo list
+    │
+    ~
+    [EOF]
+    ");
+
+    // Glob patterns match whole words
+    let output = work_dir.run_jj([
+        "--color=always",
+        "log",
+        "-r@",
+        "--no-graph",
+        "-T",
+        "description.first_line()",
+        "--highlight=glob-i:todo*",
+    ]);
+    insta::assert_snapshot!(output, @"[7mTODO:[27m fix the [7mtodo[27m list[EOF]");
+
+    // Bad pattern
+    let output = work_dir.run_jj(["log", "--highlight=bad:*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'bad:*' for '--highlight <PATTERN>': Invalid string pattern kind `bad:`
+
+    For more information, try '--help'.
+    Hint: Try prefixing with one of `exact:`, `glob:`, `regex:`, `substring:`, or one of these with `-i` suffix added (e.g. `glob-i:`) for case-insensitive matching
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_shortest_accessors() {
     let test_env = TestEnvironment::default();
@@ -522,6 +755,14 @@ fn test_log_bad_short_prefixes() {
     [exit status: 1]
     ");
 
+    // Bad config can be bypassed
+    let output = work_dir.run_jj(["log", "--no-short-prefixes", "-Tcommit_id.shortest()"]);
+    insta::assert_snapshot!(output, @r"
+    @  e
+    ◆  0
+    [EOF]
+    ");
+
     // Warn on resolution of short prefixes
     test_env.add_config("revsets.short-prefixes = 'missing'");
     let output = work_dir.run_jj(["log", "-Tcommit_id.shortest()"]);
@@ -542,6 +783,14 @@ fn test_log_bad_short_prefixes() {
     [EOF]
     ");
 
+    // Warning can be bypassed
+    let output = work_dir.run_jj(["log", "--no-short-prefixes", "-Tcommit_id.shortest()"]);
+    insta::assert_snapshot!(output, @r"
+    @  e
+    ◆  0
+    [EOF]
+    ");
+
     // Error on resolution of short prefixes
     test_env.add_config("revsets.short-prefixes = 'missing'");
     let output = work_dir.run_jj(["log", "-r0"]);
@@ -552,6 +801,84 @@ fn test_log_bad_short_prefixes() {
     [EOF]
     [exit status: 1]
     ");
+
+    // Error can be bypassed
+    let output = work_dir.run_jj(["log", "-r0", "--no-short-prefixes"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_template_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"template-aliases.'my_id(id)' = 'id.short(4)'"#);
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    // Aliases from the config can be used in the file
+    std::fs::write(
+        test_env.env_root().join("good.jjtmpl"),
+        "separate(' ',\n  my_id(commit_id),\n  description.first_line(),\n) ++ '\n'\n",
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["log", "--template-file", "../good.jjtmpl"]);
+    insta::assert_snapshot!(output, @r"
+    @  68a5 first
+    ◆  0000
+    [EOF]
+    ");
+
+    // Errors refer to the file path and line
+    std::fs::write(
+        test_env.env_root().join("bad.jjtmpl"),
+        "separate(' ',\n  commit_id,\n  no_such_keyword,\n)\n",
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["log", "--template-file", "../bad.jjtmpl"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: Keyword `no_such_keyword` doesn't exist
+    Caused by:  --> ../bad.jjtmpl:3:3
+      |
+    3 |   no_such_keyword,
+      |   ^-------------^
+      |
+      = Keyword `no_such_keyword` doesn't exist
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Missing file
+    let output = work_dir.run_jj(["log", "--template-file", "../missing.jjtmpl"]);
+    insta::assert_snapshot!(
+        output.normalize_stderr_with(|s| s.split_inclusive('\n').take(1).collect()), @r"
+    ------- stderr -------
+    Error: Failed to read template file ../missing.jjtmpl
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Cannot be combined with -T
+    let output = work_dir.run_jj([
+        "log",
+        "--template-file",
+        "../good.jjtmpl",
+        "-T",
+        "commit_id",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--template-file <PATH>' cannot be used with '--template <TEMPLATE>'
+
+    Usage: jj log --template-file <PATH> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
 }
 
 #[test]
@@ -831,6 +1158,38 @@ fn test_log_reversed() {
     ");
 }
 
+#[test]
+fn test_log_no_graph_separator() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--no-graph", "--separator"]);
+    insta::assert_snapshot!(output, @r"
+    second
+
+    first
+
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--separator"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --no-graph
+
+    Usage: jj log --no-graph --template <TEMPLATE> --separator [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_filtered_by_path() {
     let test_env = TestEnvironment::default();
@@ -860,70 +1219,575 @@ fn test_log_filtered_by_path() {
     [EOF]
     ");
 
-    let output = work_dir.run_jj(["log", "-T", "description", "-s", "file1"]);
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    @  second
+    │  M file1
+    ○  first
+    │  A file1
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "file2", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    second
+    A file2
+    [EOF]
+    ");
+
+    // empty revisions are filtered out by "all()" fileset.
+    let output = work_dir.run_jj(["log", "-Tdescription", "-s", "all()"]);
+    insta::assert_snapshot!(output, @r"
+    @  second
+    │  M file1
+    │  A file2
+    ○  first
+    │  A file1
+    ~
+    [EOF]
+    ");
+
+    // "root:<path>" is resolved relative to the workspace root.
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "log",
+            "-R",
+            work_dir.root().to_str().unwrap(),
+            "-Tdescription",
+            "-s",
+            "root:file1",
+        ],
+    );
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    @  second
+    │  M repo/file1
+    ○  first
+    │  A repo/file1
+    ~
+    [EOF]
+    ");
+
+    // files() revset doesn't filter the diff.
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "-s",
+        "-rfiles(file2)",
+        "--no-graph",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    second
+    M file1
+    A file2
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_date_order() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "A"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "B"]).success();
+    work_dir
+        .run_jj(["new", "description(A)", "-m", "C"])
+        .success();
+    work_dir.run_jj(["new", "root()", "-m", "D"]).success();
+    // Rewriting B updates its committer date, but not its author date
+    work_dir
+        .run_jj(["describe", "description(B)", "-m", "B2"])
+        .success();
+
+    let template = r#"description.first_line() ++ " " ++ author.timestamp().format("%H:%M:%S") ++ " " ++ committer.timestamp().format("%H:%M:%S")"#;
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  D 04:05:11 04:05:11
+    │ ○  B2 04:05:09 04:05:12
+    ├─╯
+    │ ○  C 04:05:10 04:05:10
+    │ ○  A 04:05:08 04:05:08
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+
+    // Unconstrained revisions are ordered newest first
+    let output = work_dir.run_jj(["log", "-T", template, "--author-date-order"]);
+    insta::assert_snapshot!(output, @r"
+    @  D 04:05:11 04:05:11
+    │ ○  C 04:05:10 04:05:10
+    │ │ ○  B2 04:05:09 04:05:12
+    ├───╯
+    │ ○  A 04:05:08 04:05:08
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+    // The working-copy branch isn't shown first
+    let output = work_dir.run_jj(["log", "-T", template, "--committer-date-order"]);
+    insta::assert_snapshot!(output, @r"
+    ○  B2 04:05:09 04:05:12
+    │ @  D 04:05:11 04:05:11
+    ├─╯
+    │ ○  C 04:05:10 04:05:10
+    │ ○  A 04:05:08 04:05:08
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+
+    // Parents are still shown after their children
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        &format!(r#"{template} ++ "\n""#),
+        "--author-date-order",
+        "--no-graph",
+        "--reversed",
+        "-r",
+        "description(A) | description(C) | description(D)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    A 04:05:08 04:05:08
+    C 04:05:10 04:05:10
+    D 04:05:11 04:05:11
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_date_order_same_timestamps() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "A"]).success();
+    // Duplicates share the author date of the original commit
+    for _ in 0..3 {
+        work_dir.run_jj(["duplicate", "@"]).success();
+    }
+
+    // Ties are broken by commit id in both directions
+    let template = r#"commit_id.short() ++ " " ++ author.timestamp() ++ "\n""#;
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "--author-date-order",
+        "--no-graph",
+        "-r=~root()",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    f109d7637e6a 2001-02-03 04:05:08.000 +07:00
+    cee25e359fdc 2001-02-03 04:05:08.000 +07:00
+    8777db25171c 2001-02-03 04:05:08.000 +07:00
+    5664bf6d6d39 2001-02-03 04:05:08.000 +07:00
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "--author-date-order",
+        "--no-graph",
+        "--reversed",
+        "-r=~root()",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    5664bf6d6d39 2001-02-03 04:05:08.000 +07:00
+    8777db25171c 2001-02-03 04:05:08.000 +07:00
+    cee25e359fdc 2001-02-03 04:05:08.000 +07:00
+    f109d7637e6a 2001-02-03 04:05:08.000 +07:00
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_focus() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir
+        .run_jj(["new", "-m", "c", "description(a)"])
+        .success();
+    work_dir
+        .run_jj(["new", "-m", "d", "description(c)", "description(b)"])
+        .success();
+
+    let template = r#"commit_id.short() ++ " " ++ description"#;
+    let output = work_dir.run_jj(["log", "-T", template, "--focus=description(b) | @"]);
+    insta::assert_snapshot!(output, @r"
+    @    3d8da5b82e6c d
+    ├─╮
+    │ ○  77b3867e67b8 b
+    ○ │
+    ├─╯
+    ○
+    ◆
+    [EOF]
+    ");
+
+    // Diffs are only shown for focused revisions, and elision still applies
+    // outside the shown revisions
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "-r=@ | description(a)",
+        "--focus=description(a)",
+        "--summary",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ~  (elided revisions)
+    ○  f4036f09e4a4 a
+    │  A file
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--focus=@", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--focus <REVSETS>' cannot be used with '--no-graph'
+
+    Usage: jj log --focus <REVSETS> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_log_highlight_path() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir
+        .run_jj(["new", "-m", "c", "description(a)"])
+        .success();
+    work_dir
+        .run_jj(["new", "-m", "d", "description(b)", "description(c)"])
+        .success();
+    work_dir.run_jj(["new", "-m", "e"]).success();
+
+    let template = "description";
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "--highlight-path=description(c)::description(d)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  e
+    ●    d
+    ├─╮
+    │ ●  c
+    ○ │  b
+    ├─╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "--highlight-path=description(c)::description(d)",
+        "--config=ui.graph.style=ascii",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  e
+    *    d
+    |\
+    | *  c
+    o |  b
+    |/
+    o  a
+    +
+    [EOF]
+    ");
+
+    // The working-copy and immutable nodes on the path are distinct
+    let output = work_dir.run_jj(["log", "-T", template, "--highlight-path=::@"]);
+    insta::assert_snapshot!(output, @r"
+    ◉  e
+    ●    d
+    ├─╮
+    │ ●  c
+    ● │  b
+    ├─╯
+    ●  a
+    ◈
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "--highlight-path=::@",
+        "--config=ui.graph.style=ascii",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    &  e
+    *    d
+    |\
+    | *  c
+    * |  b
+    |/
+    *  a
+    #
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "--highlight-path=description(a)::@",
+        "--color=always",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [1m[38;5;2m◉[0m  e
+    [1m[38;5;3m●[0m    d
+    ├─╮
+    │ [1m[38;5;3m●[0m  c
+    [1m[38;5;3m●[0m │  b
+    ├─╯
+    [1m[38;5;3m●[0m  a
+    [1m[38;5;14m◆[0m
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_iso_time() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(
+        r#"template-aliases.'format_timestamp(timestamp)' = 'timestamp.format("%b %e %Y")'"#,
+    );
+
+    // The configured time format is overridden in the default template
+    let output = work_dir.run_jj(["log", "-r@", "--iso-time"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03T04:05:07+07:00 e8849ae1
+    │  (empty) (no description set)
+    ~
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r@", "--iso-time", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsm test.user@example.com 2001-02-03T04:05:07+07:00 e8849ae1
+    (empty) (no description set)
+    [EOF]
+    ");
+
+    // Custom templates are unaffected
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "--iso-time",
+        "-T",
+        "format_timestamp(committer.timestamp())",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  Feb  3 2001
+    │
+    ~
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_exclude() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.run_jj(["new", "-m", "c"]).success();
+    work_dir
+        .run_jj(["new", "-m", "d", "description(a)"])
+        .success();
+
+    let template = "description";
+    // Excluded commits in the middle of the graph are elided
+    let output = work_dir.run_jj(["log", "-T", template, "--exclude=description(b)"]);
+    insta::assert_snapshot!(output, @r"
+    @  d
+    │ ○  c
+    │ ~  (elided revisions)
+    ├─╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // Composes with multiple -r, and multiple --exclude are unioned
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "-r=description(a)::",
+        "-r=root()",
+        "--exclude=description(c)",
+        "--exclude=description(d)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_decorate_refs() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "-r@",
+            "main",
+            "feature-1",
+            "feature-2",
+        ])
+        .success();
+
+    let template = r#"bookmarks ++ " | " ++ local_bookmarks"#;
+    let output = work_dir.run_jj(["log", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  feature-1 feature-2 main | feature-1 feature-2 main
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "-T",
+        template,
+        "--decorate-refs=glob:feature-*",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  feature-1 feature-2 | feature-1 feature-2
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "-T",
+        template,
+        "--decorate-refs=glob:feature-*",
+        "--decorate-refs-exclude=feature-2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  feature-1 | feature-1
+    │
+    ~
+    [EOF]
+    ");
+
+    // Excluded bookmarks can still be referenced in revsets
+    let output = work_dir.run_jj(["log", "-rmain", "--decorate-refs-exclude=main"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:07 feature-1 feature-2 e8849ae1
+    │  (empty) (no description set)
+    ~
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_graph_lines_only() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir
+        .run_jj(["new", "-m", "c", "description(a)"])
+        .success();
+    work_dir
+        .run_jj(["new", "-m", "d", "description(c)", "description(b)"])
+        .success();
+
+    let output = work_dir.run_jj(["log", "--graph-lines-only"]);
+    insta::assert_snapshot!(output, @r"
+    @    c619d6bad812e4eaf454d03b2c2f7264023986fa
+    ├─╮
+    │ ○  74bc807266401e947b3495707532918adb8fe60f
+    ○ │  247a3b79774913ed7d1150803249865ac138694b
+    ├─╯
+    ○  b86e28cd6862624ad77e1aaf31e34b2c7545bebd
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    // The configured graph style is used, and elided revisions have no id
+    let output = work_dir.run_jj([
+        "log",
+        "--graph-lines-only",
+        "--config=ui.graph.style=ascii",
+        "-r=description(d) | description(a)",
+    ]);
     insta::assert_snapshot!(output, @r"
-    @  second
-    │  M file1
-    ○  first
-    │  A file1
+    @  c619d6bad812e4eaf454d03b2c2f7264023986fa
+    ~
+    o  b86e28cd6862624ad77e1aaf31e34b2c7545bebd
+    |
     ~
     [EOF]
     ");
 
-    let output = work_dir.run_jj(["log", "-T", "description", "-s", "file2", "--no-graph"]);
+    // Diffs aren't rendered
+    let output = work_dir.run_jj(["log", "--graph-lines-only", "--stat"]);
     insta::assert_snapshot!(output, @r"
-    second
-    A file2
+    @    c619d6bad812e4eaf454d03b2c2f7264023986fa
+    ├─╮
+    │ ○  74bc807266401e947b3495707532918adb8fe60f
+    ○ │  247a3b79774913ed7d1150803249865ac138694b
+    ├─╯
+    ○  b86e28cd6862624ad77e1aaf31e34b2c7545bebd
+    ◆  0000000000000000000000000000000000000000
     [EOF]
     ");
 
-    // empty revisions are filtered out by "all()" fileset.
-    let output = work_dir.run_jj(["log", "-Tdescription", "-s", "all()"]);
+    let output = work_dir.run_jj(["log", "--graph-lines-only", "-T=description"]);
     insta::assert_snapshot!(output, @r"
-    @  second
-    │  M file1
-    │  A file2
-    ○  first
-    │  A file1
-    ~
-    [EOF]
-    ");
+    ------- stderr -------
+    error: the argument '--graph-lines-only' cannot be used with '--template <TEMPLATE>'
 
-    // "root:<path>" is resolved relative to the workspace root.
-    let output = test_env.run_jj_in(
-        ".",
-        [
-            "log",
-            "-R",
-            work_dir.root().to_str().unwrap(),
-            "-Tdescription",
-            "-s",
-            "root:file1",
-        ],
-    );
-    insta::assert_snapshot!(output.normalize_backslash(), @r"
-    @  second
-    │  M repo/file1
-    ○  first
-    │  A repo/file1
-    ~
-    [EOF]
-    ");
+    Usage: jj log --graph-lines-only [FILESETS]...
 
-    // files() revset doesn't filter the diff.
-    let output = work_dir.run_jj([
-        "log",
-        "-T",
-        "description",
-        "-s",
-        "-rfiles(file2)",
-        "--no-graph",
-    ]);
-    insta::assert_snapshot!(output, @r"
-    second
-    M file1
-    A file2
+    For more information, try '--help'.
     [EOF]
+    [exit status: 2]
     ");
 }
 
@@ -1006,6 +1870,82 @@ fn test_log_limit() {
     ");
 }
 
+#[test]
+fn test_log_limit_per_root() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a1"]).success();
+    work_dir.run_jj(["new", "-m", "a2"]).success();
+    work_dir.run_jj(["new", "-m", "a3"]).success();
+    work_dir.run_jj(["new", "-m", "a4"]).success();
+    work_dir.run_jj(["new", "-m", "b1", "root()"]).success();
+    work_dir.run_jj(["new", "-m", "b2"]).success();
+
+    // Each root keeps its own budget, and the truncated ancestors are elided
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "-r",
+        "~root()",
+        "--limit-per-root=2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  b2
+    ○  b1
+    │
+    ~
+
+    ○  a4
+    ○  a3
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "-r",
+        "~root()",
+        "--limit-per-root=1",
+        "--no-graph",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    b2
+    a4
+    [EOF]
+    ");
+
+    // A merge counts against both of the roots it descends from
+    work_dir
+        .run_jj(["new", "-m", "merge", "description(a4)", "description(b2)"])
+        .success();
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "-r",
+        "~root()",
+        "--limit-per-root=2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @    merge
+    ├─╮
+    │ ○  b2
+    │ │
+    │ ~
+    │
+    ○  a4
+    │
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_warn_path_might_be_revset() {
     let test_env = TestEnvironment::default();
@@ -1147,6 +2087,25 @@ fn test_multiple_revsets() {
     ~
     [EOF]
     ");
+
+    // Revsets can be intersected instead
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--combine=intersect", "-r::baz", "-rbar::"]), @r"
+    @  baz
+    ○  bar
+    │
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--combine=intersect", "-rfoo"]), @r"
+    ○  foo
+    │
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--combine=intersect", "-rfoo", "-rbar"]), @"");
 }
 
 #[test]
@@ -1435,6 +2394,67 @@ fn test_log_word_wrap() {
     ");
 }
 
+#[test]
+fn test_log_diff_paths_from() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(
+        &work_dir,
+        "base",
+        &[],
+        &[("file1", "a\n"), ("file2", "a\n")],
+    );
+    create_commit_with_files(&work_dir, "main", &["base"], &[("file2", "b\n")]);
+    create_commit_with_files(
+        &work_dir,
+        "topic",
+        &["base"],
+        &[("file1", "c\n"), ("file2", "c\n"), ("file3", "c\n")],
+    );
+
+    // Only the diffs are restricted, not the revisions
+    let output = work_dir.run_jj([
+        "log",
+        "-T=description",
+        "-r=main|topic",
+        "--summary",
+        "--diff-paths-from=main",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  topic
+    │  M file2
+    ~
+
+    ○  main
+    │  M file2
+    ~
+    [EOF]
+    ");
+
+    // Combined with path arguments
+    let output = work_dir.run_jj([
+        "log",
+        "-T=description",
+        "-r=topic",
+        "--git",
+        "--diff-paths-from=base",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  topic
+    │  diff --git a/file1 b/file1
+    ~  index 7898192261..f2ad6c76f0 100644
+       --- a/file1
+       +++ b/file1
+       @@ -1,1 +1,1 @@
+       -a
+       +c
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_diff_stat_width() {
     let test_env = TestEnvironment::default();
@@ -1478,6 +2498,49 @@ fn test_log_diff_stat_width() {
     ");
 }
 
+#[test]
+fn test_log_diff_stat_format() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\nbar\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "foo\n".repeat(100));
+
+    let output = work_dir.run_jj(["log", "-r@", "--stat", "--stat-format=count"]);
+    insta::assert_snapshot!(output, @r"
+    @  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 4076ff98
+    │  (no description set)
+    ~  file1 | +0 -1
+       file2 | +100 -0
+       2 files changed, 100 insertions(+), 1 deletion(-)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r@", "--stat", "--stat-format=none"]);
+    insta::assert_snapshot!(output, @r"
+    @  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 4076ff98
+    │  (no description set)
+    ~  2 files changed, 100 insertions(+), 1 deletion(-)
+    [EOF]
+    ");
+
+    // --stat-format only applies to --stat
+    let output = work_dir.run_jj(["log", "-r@", "--stat-format=count"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --stat
+
+    Usage: jj log --stat --revisions <REVSETS> --stat-format <FORMAT> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_elided() {
     // Test that elided commits are shown as synthetic nodes.
@@ -1584,6 +2647,115 @@ fn test_elided() {
     ");
 }
 
+#[test]
+fn test_log_graph_from() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "initial"]).success();
+    work_dir.run_jj(["new", "-m", "main 1"]).success();
+    work_dir.run_jj(["new", "-m", "main 2"]).success();
+    work_dir.run_jj(["new", "-m", "main 3"]).success();
+    work_dir
+        .run_jj(["new", "description(initial)", "-m", "side 1"])
+        .success();
+
+    let template = r#"description ++ "\n""#;
+
+    // Without --graph-from, ancestors that aren't shown are indicated by "~"
+    let output = work_dir.run_jj(["log", "-T", template, "-r", "~::description(initial)"]);
+    insta::assert_snapshot!(output, @r"
+    @  side 1
+    │
+    ~
+
+    ○  main 3
+    │
+    ○  main 2
+    │
+    ○  main 1
+    │
+    ~
+    [EOF]
+    ");
+
+    // Graph roots are rendered without "~"
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "-r",
+        "~::description(initial)",
+        "--graph-from",
+        "description(side) | description(main)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  side 1
+
+    ○  main 3
+    │
+    ○  main 2
+    │
+    ○  main 1
+
+    [EOF]
+    ");
+
+    // A graph-from revision which is a descendant of another shown revision is
+    // still connected to it
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "-r",
+        "description('main 1') | description('main 3')",
+        "--graph-from",
+        "description(main)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  main 3
+    │
+    ~  (elided revisions)
+    ○  main 1
+
+    [EOF]
+    ");
+
+    // Reversed
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        template,
+        "-r",
+        "description(main)",
+        "--graph-from",
+        "description(main)",
+        "--reversed",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  main 1
+    │
+    ○  main 2
+    │
+    ○  main 3
+
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--graph-from", "@", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--graph-from <REVSETS>' cannot be used with '--no-graph'
+
+    Usage: jj log --graph-from <REVSETS> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_with_custom_symbols() {
     // Test that elided commits are shown as synthetic nodes.
@@ -88,6 +88,7 @@ fn test_op_log() {
     │  Changed commits:
     │  ○  + qpvuntsm 3ae22e7f (empty) description 0
     │     - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    │       (description changed)
     │
     │  Changed working copy default@:
     │  + qpvuntsm 3ae22e7f (empty) description 0
@@ -114,6 +115,7 @@ fn test_op_log() {
     │  Changed commits:
     │  ○  [38;5;2m+[39m [1m[38;5;13mq[38;5;8mpvuntsm[39m [38;5;12m3[38;5;8mae22e7f[39m [38;5;10m(empty)[39m description 0[0m
     │     [38;5;1m-[39m [1m[39mq[0m[38;5;8mpvuntsm[39m hidden [1m[38;5;4me[0m[38;5;8m8849ae1[39m [38;5;2m(empty)[39m [38;5;2m(no description set)[39m
+    │       (description changed)
     │
     │  Changed working copy [38;5;2mdefault@[39m:
     │  [38;5;2m+[39m [1m[38;5;13mq[38;5;8mpvuntsm[39m [38;5;12m3[38;5;8mae22e7f[39m [38;5;10m(empty)[39m description 0[0m
@@ -198,6 +200,8 @@ fn test_op_log_with_no_template() {
     - builtin_log_compact_full_description
     - builtin_log_detailed
     - builtin_log_node
+    - builtin_log_node_ancestry_path
+    - builtin_log_node_ancestry_path_ascii
     - builtin_log_node_ascii
     - builtin_log_oneline
     - builtin_op_log_comfortable
@@ -229,6 +233,485 @@ fn test_op_log_limit() {
     ");
 }
 
+#[test]
+fn test_op_log_ancestors_of() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let render = |args: &[&str]| {
+        work_dir.run_jj_with(|cmd| {
+            cmd.args(["op", "log", "-T", r#"description.first_line() ++ "\n""#])
+                .args(args)
+        })
+    };
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["describe", "-m", "second"]).success();
+
+    // Later operations are excluded
+    insta::assert_snapshot!(render(&["--ancestors-of=@-"]), @r"
+    ○  describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+    insta::assert_snapshot!(render(&["--ancestors-of=@-", "--reversed", "--no-graph"]), @r"
+    add workspace 'default'
+    describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+
+    // Both parents of a merge operation are included
+    work_dir
+        .run_jj(["describe", "-m", "concurrent", "--at-op=@-"])
+        .success();
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    insta::assert_snapshot!(render(&["--ancestors-of=@-"]), @r"
+    ○    reconcile divergent operations
+    ├─╮
+    ○ │  describe commit 68a505386f936fff6d718f55005e77ea72589bc1
+    │ ○  describe commit 68a505386f936fff6d718f55005e77ea72589bc1
+    ├─╯
+    ○  describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_after_before() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let render = |args: &[&str]| {
+        work_dir.run_jj_with(|cmd| {
+            cmd.args(["op", "log", "-T", r#"description.first_line() ++ "\n""#])
+                .args(args)
+        })
+    };
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    let first_op_id = work_dir.current_operation_id();
+    work_dir.run_jj(["describe", "-m", "second"]).success();
+    work_dir
+        .run_jj(["describe", "-m", "concurrent", "--at-op=@-"])
+        .success();
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    let after_arg = format!("--after={first_op_id}");
+    let before_arg = format!("--before={first_op_id}");
+
+    // Only descendants of the operation are shown, on each concurrent branch
+    insta::assert_snapshot!(render(&[&after_arg]), @r"
+    @  new empty commit
+    ○    reconcile divergent operations
+    ├─╮
+    ○ │  describe commit 68a505386f936fff6d718f55005e77ea72589bc1
+    │ │
+    ~ │
+      │
+      ○  describe commit 68a505386f936fff6d718f55005e77ea72589bc1
+      │
+      ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(render(&[&after_arg, "--no-graph", "-n2"]), @r"
+    new empty commit
+    reconcile divergent operations
+    [EOF]
+    ");
+
+    // Only ancestors of the operation are shown
+    insta::assert_snapshot!(render(&[&before_arg]), @r"
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+
+    // The bounds can be combined
+    insta::assert_snapshot!(render(&[&after_arg, "--before=@"]), @r"
+    ○    reconcile divergent operations
+    ├─╮
+    ○ │  describe commit 68a505386f936fff6d718f55005e77ea72589bc1
+    │ │
+    ~ │
+      │
+      ○  describe commit 68a505386f936fff6d718f55005e77ea72589bc1
+      │
+      ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(render(&["--after=@", "--no-graph"]), @"");
+}
+
+#[test]
+fn test_op_log_merges() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["describe", "-m", "description 0"])
+        .success();
+    work_dir
+        .run_jj(["describe", "-m", "description 1", "--at-op", "@-"])
+        .success();
+    work_dir.run_jj(["new", "-m", "new 2"]).success();
+    let template = r#"id.short() ++ " " ++ description ++ "\n""#;
+
+    let output = work_dir.run_jj(["op", "log", "--merges-only", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ○  174a0fa9fa34 reconcile divergent operations
+    │
+    ~
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--merges-only", "--reversed", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ○  174a0fa9fa34 reconcile divergent operations
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--no-merges", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @    5c519002d3cd new empty commit
+    ├─╮
+    ○ ╷  12f7cbba4278 describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    │ ○  dd1534c4b064 describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ├─╯
+    ○  8f47435a3990 add workspace 'default'
+    ○  000000000000
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-merges",
+        "--no-graph",
+        "--limit=2",
+        "-T",
+        template,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    5c519002d3cd new empty commit
+    12f7cbba4278 describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_touching_bookmark() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "release/1"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "move", "release/1", "--to=@"])
+        .success();
+    // Concurrent change to another bookmark
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "release/2", "--at-op=@-"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    let template = r#"description ++ "\n""#;
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--touching-bookmark=glob:release/*",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  point bookmark release/1 to commit 1c1c95df80e53b1e654608d7589f5baabb10ebb2
+    ╷ ○  create bookmark release/2 pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ╭─╯
+    ○  create bookmark release/1 pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--touching-bookmark=main",
+        "--no-graph",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    create bookmark main pointing to commit 1c1c95df80e53b1e654608d7589f5baabb10ebb2
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--touching-bookmark=glob:*",
+        "--limit=2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  point bookmark release/1 to commit 1c1c95df80e53b1e654608d7589f5baabb10ebb2
+    │ ○  create bookmark release/2 pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ├─╯
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_grep() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "main"])
+        .success();
+    work_dir.run_jj(["new", "main", "-m", "third"]).success();
+    let template = r#"description ++ "\n""#;
+
+    // Matches the description
+    let output = work_dir.run_jj(["op", "log", "-T", template, "--grep=describe"]);
+    insta::assert_snapshot!(output, @r"
+    ○  describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    │
+    ~
+    [EOF]
+    ");
+
+    // Matches the command-line arguments
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--grep=regex:new\\s+main",
+        "--no-graph",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    new empty commit
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--grep=commit",
+        "--limit=2",
+        "--reversed",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  create bookmark main pointing to commit 68a505386f936fff6d718f55005e77ea72589bc1
+    @  new empty commit
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "--grep=bad:pattern"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'bad:pattern' for '--grep <PATTERN>': Invalid string pattern kind `bad:`
+
+    For more information, try '--help'.
+    Hint: Try prefixing with one of `exact:`, `glob:`, `regex:`, `substring:`, or one of these with `-i` suffix added (e.g. `glob-i:`) for case-insensitive matching
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_log_iso_time() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(
+        r#"template-aliases.'format_timestamp(timestamp)' = 'timestamp.format("%b %e %Y")'"#,
+    );
+
+    let output = work_dir.run_jj(["op", "log", "-n1", "--iso-time"]);
+    insta::assert_snapshot!(output, @r"
+    @  8f47435a3990 test-username@host.example.com 2001-02-03T04:05:07+07:00 - 2001-02-03T04:05:07+07:00
+    │  add workspace 'default'
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "--iso-time", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    8f47435a3990 test-username@host.example.com 2001-02-03T04:05:07+07:00 - 2001-02-03T04:05:07+07:00
+    add workspace 'default'
+    [EOF]
+    ");
+
+    // Custom templates are unaffected
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-n1",
+        "--iso-time",
+        "-T",
+        "format_timestamp(self.time().end())",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  Feb  3 2001
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_fields() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["describe", "-m", "first line\nsecond line"])
+        .success();
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "--fields=id.short,user,time.start",
+        "--fields=description",
+    ]);
+    insta::assert_snapshot!(output.normalize_stdout_with(|s| s.replace('\t', "<TAB>")), @r"
+    a0a642592339<TAB>test-username@host.example.com<TAB>2001-02-03 04:05:08.000 +07:00<TAB>describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    8f47435a3990<TAB>test-username@host.example.com<TAB>2001-02-03 04:05:07.000 +07:00<TAB>add workspace 'default'
+    000000000000<TAB>@<TAB>1970-01-01 00:00:00.000 +00:00<TAB>
+    [EOF]
+    ");
+
+    // Operations can be separated by NUL
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-n1",
+        "--fields=id.short,description",
+        "--null",
+    ]);
+    insta::assert_snapshot!(
+        output.normalize_stdout_with(|s| s.replace('\t', "<TAB>").replace('\0', "<NUL>")),
+        @"a0a642592339<TAB>describe commit e8849ae12c709f2321908879bc724fdb2ab8a781<NUL>[EOF]");
+
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "--fields=id,unknown"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'unknown' for '--fields <FIELDS>'
+      [possible values: id, id.short, user, time.start, time.end, description, snapshot]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--fields=id"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --no-graph
+
+    Usage: jj operation log --no-graph --fields <FIELDS> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_log_graph_style() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["describe", "-m", "description 0"])
+        .success();
+
+    let template = r#"id.short(4) ++ "\n""#;
+    let output = work_dir.run_jj(["op", "log", "-T", template, "--graph-style=ascii"]);
+    insta::assert_snapshot!(output, @r"
+    @  12f7
+    o  8f47
+    o  0000
+    [EOF]
+    ");
+
+    // Op-log specific config overrides ui.graph.style
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--config=ui.graph.style=ascii",
+        "--config=ui.op-log.graph.style=square",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  12f7
+    ○  8f47
+    ○  0000
+    [EOF]
+    ");
+
+    // Command-line flag overrides both
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--config=ui.op-log.graph.style=square",
+        "--graph-style=ascii-large",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  12f7
+    o  8f47
+    o  0000
+    [EOF]
+    ");
+
+    // Builtin node alias can be overridden
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--config=template-aliases.builtin_op_log_node_ascii='if(current_operation, \"#\", \"*\")'",
+        "--graph-style=ascii",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    #  12f7
+    *  8f47
+    *  0000
+    [EOF]
+    ");
+
+    // Invalid style name
+    let output = work_dir.run_jj(["op", "log", "--graph-style=unknown"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value 'unknown' for '--graph-style <STYLE>'
+      [possible values: ascii, ascii-large, curved, square]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--config=ui.op-log.graph.style=unknown"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid type or value for ui.op-log.graph.style
+    Caused by: unknown variant `unknown`, expected one of `ascii`, `ascii-large`, `curved`, `square`
+
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_op_log_no_graph() {
     let test_env = TestEnvironment::default();
@@ -257,6 +740,28 @@ fn test_op_log_no_graph() {
     000000000000 root()
     [EOF]
     ");
+
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "--separator"]);
+    insta::assert_snapshot!(output, @r"
+    8f47435a3990 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
+    add workspace 'default'
+
+    000000000000 root()
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "--separator"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --no-graph
+
+    Usage: jj operation log --no-graph --separator [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
 }
 
 #[test]
@@ -423,6 +928,149 @@ fn test_op_log_template() {
     ");
 }
 
+#[test]
+fn test_op_log_time_since_previous() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["describe", "-m", "description 0"])
+        .success();
+    work_dir
+        .run_jj(["describe", "-m", "description 1", "--at-op", "@-"])
+        .success();
+    work_dir.run_jj(["new", "-m", "new 2"]).success();
+
+    let template = r#"
+    separate(" ", id.short(), time.start(), self.time_since_previous()) ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["op", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  5c519002d3cd 2001-02-03 04:05:10.000 +07:00 less than a microsecond
+    ○    174a0fa9fa34 2001-02-03 04:05:10.000 +07:00 1 second
+    ├─╮
+    ○ │  12f7cbba4278 2001-02-03 04:05:08.000 +07:00 1 second
+    │ ○  dd1534c4b064 2001-02-03 04:05:09.000 +07:00 2 seconds
+    ├─╯
+    ○  8f47435a3990 2001-02-03 04:05:07.000 +07:00
+    ○  000000000000 1970-01-01 00:00:00.000 +00:00
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_change_counts() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "a", "b"])
+        .success();
+    work_dir.run_jj(["abandon", "@-"]).success();
+
+    let template = r#"
+    id.short() ++ " (" ++ commit_change_count ++ "c/" ++ bookmark_change_count ++ "b) "
+      ++ description.first_line() ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["op", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  ac516c84bf15 (2c/2b) abandon commit 68a505386f936fff6d718f55005e77ea72589bc1
+    ○  791234bb28dd (0c/2b) create bookmark a, b pointing to commit 68a505386f936fff6d718f55005e77ea72589bc1
+    ○  456fd653dafc (1c/0b) new empty commit
+    ○  75545f7ff2df (1c/0b) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ○  8f47435a3990 (1c/0b) add workspace 'default'
+    ○  000000000000 (0c/0b)
+    [EOF]
+    ");
+
+    // Operations which aren't ancestors of the current operation can be counted
+    let output = work_dir.run_jj([
+        "op",
+        "show",
+        "ac516c84bf15",
+        "--at-op=456fd653dafc",
+        "--config",
+        &format!("templates.op_log='''{template}'''"),
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ac516c84bf15 (2c/2b) abandon commit 68a505386f936fff6d718f55005e77ea72589bc1
+
+    Changed commits:
+    ○  + kkmpptxz 736cf758 (empty) second
+       - kkmpptxz hidden b1cb6b2f (empty) second
+    ○  - qpvuntsm hidden 68a50538 (empty) first
+
+    Changed working copy default@:
+    + kkmpptxz 736cf758 (empty) second
+    - kkmpptxz hidden b1cb6b2f (empty) second
+
+    Changed local bookmarks:
+    a:
+    + (absent)
+    - qpvuntsm hidden 68a50538 (empty) first
+    b:
+    + (absent)
+    - qpvuntsm hidden 68a50538 (empty) first
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_snapshot() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    // The root operation isn't a snapshot
+    let template = r#"separate(" ", id.short(), snapshot, description) ++ "\n""#;
+    let output = work_dir.run_jj(["op", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  29ffe382ef05 false describe commit 3dcf981ee67045adb61588f39b36ab6180f8ef15
+    ○  2f4a87483bde true snapshot working copy
+    ○  8f47435a3990 false add workspace 'default'
+    ○  000000000000 false
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_template_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    std::fs::write(
+        test_env.env_root().join("op.jjtmpl"),
+        "separate(' ',\n  id.short(5),\n  description,\n) ++ '\n'\n",
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["op", "log", "--template-file", "../op.jjtmpl"]);
+    insta::assert_snapshot!(output, @r"
+    @  8f474 add workspace 'default'
+    ○  00000
+    [EOF]
+    ");
+
+    std::fs::write(test_env.env_root().join("bad.jjtmpl"), "id.\n  bad(\n").unwrap();
+    let output = work_dir.run_jj(["op", "log", "--template-file", "../bad.jjtmpl"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: Syntax error
+    Caused by:  --> ../bad.jjtmpl:1:4
+      |
+    1 | id.␊
+      |    ^---
+      |
+      = expected <identifier>
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_op_log_builtin_templates() {
     let test_env = TestEnvironment::default();
@@ -592,29 +1240,107 @@ fn test_op_log_word_wrap() {
     [EOF]
     ");
 
-    // Nested graph widths should be subtracted from the term width
-    let config = r#"templates.commit_summary='"0 1 2 3 4 5 6 7 8 9"'"#;
-    insta::assert_snapshot!(
-        render(&["op", "log", "-T''", "--op-diff", "-n1", "--config", config], 15, true), @r"
-    @
-    │
-    │  Changed
-    │  commits:
-    │  ○  + 0 1 2 3
-    │     4 5 6 7 8
-    │     9
-    │     - 0 1 2 3
-    │     4 5 6 7 8
-    │     9
-    │
-    │  Changed
-    │  working copy
-    │  default@:
-    │  + 0 1 2 3 4
-    │  5 6 7 8 9
-    │  - 0 1 2 3 4
-    │  5 6 7 8 9
+    // Nested graph widths should be subtracted from the term width
+    let config = r#"templates.commit_summary='"0 1 2 3 4 5 6 7 8 9"'"#;
+    insta::assert_snapshot!(
+        render(&["op", "log", "-T''", "--op-diff", "-n1", "--config", config], 15, true), @r"
+    @
+    │
+    │  Changed
+    │  commits:
+    │  ○  + 0 1 2 3
+    │     4 5 6 7 8
+    │     9
+    │     - 0 1 2 3
+    │     4 5 6 7 8
+    │     9
+    │
+    │  Changed
+    │  working copy
+    │  default@:
+    │  + 0 1 2 3 4
+    │  5 6 7 8 9
+    │  - 0 1 2 3 4
+    │  5 6 7 8 9
+    [EOF]
+    ");
+
+    // Escape sequences shouldn't be split, and labels should be restored on
+    // the wrapped lines
+    insta::assert_snapshot!(
+        render(&["op", "log", "-n1", "--no-graph", "--color=always"], 20, true), @r"
+    [1m[38;5;12m2144f9621985[39m[0m
+    [1m[38;5;3mtest-username@host.example.com[39m[0m
+    [1m[38;5;14m2001-02-03[0m
+    [1m[38;5;14m04:05:08.000 +07:00[39m[0m
+    [1m- [38;5;14m2001-02-03[0m
+    [1m[38;5;14m04:05:08.000 +07:00[39m[0m
+    [1msnapshot working[0m
+    [1mcopy[0m
+    [1m[38;5;13margs: jj debug[0m
+    [1m[38;5;13msnapshot[39m[0m
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_op_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n".repeat(3));
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.run_jj(["describe", "@-", "-m", "first"]).success();
+
+    // One summary per operation, with files counted once
+    let output = work_dir.run_jj(["op", "log", "-T", "description ++ '\n'", "--op-stat"]);
+    insta::assert_snapshot!(output, @r"
+    @  describe commit f9a121d9014cb280d5a369fe36cc9c179928ca9a
+    │  0 files changed, 0 insertions(+), 0 deletions(-)
+    ○  snapshot working copy
+    │  2 files changed, 1 insertion(+), 2 deletions(-)
+    ○  new empty commit
+    │  0 files changed, 0 insertions(+), 0 deletions(-)
+    ○  snapshot working copy
+    │  1 file changed, 3 insertions(+), 0 deletions(-)
+    ○  add workspace 'default'
+    │  0 files changed, 0 insertions(+), 0 deletions(-)
+    ○
+       0 files changed, 0 insertions(+), 0 deletions(-)
+    [EOF]
+    ");
+
+    // Word-wrapped within the graph
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["op", "log", "-n1", "-T''", "--op-stat"])
+            .arg("--config=ui.log-word-wrap=true")
+            .env("COLUMNS", "20")
+    });
+    insta::assert_snapshot!(output, @r"
+    @
+    │  0 files changed,
+    │  0 insertions(+),
+    │  0 deletions(-)
+    [EOF]
+    ");
+
+    // Can't be combined with per-commit diffs
+    let output = work_dir.run_jj(["op", "log", "--op-stat", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--op-stat' cannot be used with:
+      --summary
+      --stat
+      --types
+      --name-only
+
+    Usage: jj operation log --op-stat --stat [FILESETS]...
+
+    For more information, try '--help'.
     [EOF]
+    [exit status: 2]
     ");
 }
 
@@ -764,6 +1490,107 @@ fn test_op_abandon_ancestors() {
     ");
 }
 
+#[test]
+fn test_op_abandon_matching() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "1");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.write_file("file", "2");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+    work_dir.run_jj(["describe", "-m", "second"]).success();
+    work_dir.write_file("file", "3");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    // The current operation is kept even if it matches
+    let output = work_dir.run_jj(["op", "abandon", "--match", "snapshot working copy"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Skipped 1 matching operations which are current or merge operations.
+    Abandoned 2 operations and reparented 3 descendant operations.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["op", "log", "-T", "description ++ '\n'"]), @r"
+    @  snapshot working copy
+    ○  describe commit 0324288201f8b08b61403e53455180dc58a55a57
+    ○  describe commit 6f551099cbf077936f57f92734840498b0bd9323
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["log", "-T", "description"]), @r"
+    @  second
+    ◆
+    [EOF]
+    ");
+
+    // Patterns can match part of the description
+    let output = work_dir.run_jj(["op", "abandon", "--match", "substring:describe"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 2 operations and reparented 1 descendant operations.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["op", "log", "-T", "description ++ '\n'"]), @r"
+    @  snapshot working copy
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "abandon", "--match", "no such description"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "abandon", "--match", "foo", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--match <PATTERN>' cannot be used with '[OPERATION]'
+
+    Usage: jj operation abandon --match <PATTERN> [OPERATION]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_abandon_report_freed() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 2"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 3"]).success();
+
+    // The reparented operations are unreferenced in addition to the abandoned
+    // ones
+    let output = work_dir.run_jj(["op", "abandon", "@--", "--report-freed"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 1 operations and reparented 2 descendant operations.
+    Unreferenced 3 operations and 1 views (1.1KiB), which can be freed by `jj util gc`.
+    [EOF]
+    ");
+
+    // Previously unreferenced objects aren't reported again
+    let output = work_dir.run_jj(["op", "abandon", "@-", "--report-freed"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 1 operations and reparented 1 descendant operations.
+    Unreferenced 2 operations and 1 views (753.0B), which can be freed by `jj util gc`.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_abandon_without_updating_working_copy() {
     let test_env = TestEnvironment::default();
@@ -903,6 +1730,100 @@ fn test_op_abandon_multiple_heads() {
     ");
 }
 
+#[test]
+fn test_op_restore_preserve_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let get_op_id = || {
+        work_dir
+            .run_jj(["op", "log", "--no-graph", "-n1", "-T", "id.short()"])
+            .success()
+            .stdout
+            .into_raw()
+    };
+
+    work_dir.write_file("file1", "1\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    let base_op_id = get_op_id();
+    work_dir.write_file("file1", "2\n");
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir.write_file("file2", "x\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+    let setup_op_id = get_op_id();
+
+    // The working-copy changes are applied on top of the restored working copy
+    let output = work_dir.run_jj(["op", "restore", "--preserve-working-copy", &base_op_id]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: 9f81fcef2103 (2001-02-03 08:05:08) commit 52ecbbdfc5ebf3eeed5a79540f38be8a9e1fb48b
+    Preserved working-copy changes in commit yqosqzyt dcdde244 (no description set)
+    Working copy  (@) now at: yqosqzyt dcdde244 (no description set)
+    Parent commit (@-)      : rlvkpnrz 5f24cec7 (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-s", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │  A file2
+    ○
+    ○  first
+    │  A file1
+    ◆
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file1"), @"1");
+    insta::assert_snapshot!(work_dir.read_file("file2"), @"x");
+
+    // Conflicting changes result in a conflicted working-copy commit
+    work_dir.run_jj(["op", "restore", &setup_op_id]).success();
+    work_dir.write_file("file1", "3\n");
+    let output = work_dir.run_jj(["op", "restore", "--preserve-working-copy", &base_op_id]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: 9f81fcef2103 (2001-02-03 08:05:08) commit 52ecbbdfc5ebf3eeed5a79540f38be8a9e1fb48b
+    Preserved working-copy changes in commit znkkpsqq b5cdbe94 (conflict) (no description set)
+    Working copy  (@) now at: znkkpsqq b5cdbe94 (conflict) (no description set)
+    Parent commit (@-)      : rlvkpnrz 5f24cec7 (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file1    2-sided conflict
+    New conflicts appeared in 1 commits:
+      znkkpsqq b5cdbe94 (conflict) (no description set)
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new znkkpsqq
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-s", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │  M file1
+    │  A file2
+    ○
+    ○  first
+    │  A file1
+    ◆
+    [EOF]
+    ");
+
+    // Without the flag, the working-copy changes are discarded
+    work_dir.run_jj(["op", "restore", &setup_op_id]).success();
+    let output = work_dir.run_jj(["op", "restore", &base_op_id]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: 9f81fcef2103 (2001-02-03 08:05:08) commit 52ecbbdfc5ebf3eeed5a79540f38be8a9e1fb48b
+    Working copy  (@) now at: rlvkpnrz 5f24cec7 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 1cd4f179 first
+    Added 0 files, modified 1 files, removed 1 files
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_recover_from_bad_gc() {
     let test_env = TestEnvironment::default();
@@ -1635,6 +2556,85 @@ fn test_op_diff_patch() {
     ");
 }
 
+#[test]
+fn test_op_diff_patch_and_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+    work_dir.write_file("file", "a\nb\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    // The diff stat is followed by the patch, as in `jj log`
+    let output = work_dir.run_jj(["op", "diff", "--patch", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: b8e8ee153014 (2001-02-03 08:05:08) snapshot working copy
+      To operation: 997ef8dbe320 (2001-02-03 08:05:09) snapshot working copy
+
+    Changed commits:
+    ○  + qpvuntsm 9eb8c5cc (no description set)
+       - qpvuntsm hidden 6b57e33c (no description set)
+       file | 1 +
+       1 file changed, 1 insertion(+), 0 deletions(-)
+       Modified regular file file:
+          1    1: a
+               2: b
+
+    Changed working copy default@:
+    + qpvuntsm 9eb8c5cc (no description set)
+    - qpvuntsm hidden 6b57e33c (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "show", "--stat", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    997ef8dbe320 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    snapshot working copy
+    args: jj debug snapshot
+
+    Changed commits:
+    ○  + qpvuntsm 9eb8c5cc (no description set)
+       - qpvuntsm hidden 6b57e33c (no description set)
+       file | 1 +
+       1 file changed, 1 insertion(+), 0 deletions(-)
+       diff --git a/file b/file
+       index 7898192261..422c2b7ab3 100644
+       --- a/file
+       +++ b/file
+       @@ -1,1 +1,2 @@
+        a
+       +b
+
+    Changed working copy default@:
+    + qpvuntsm 9eb8c5cc (no description set)
+    - qpvuntsm hidden 6b57e33c (no description set)
+    [EOF]
+    ");
+
+    // The same is rendered within the op log graph
+    let output = work_dir.run_jj(["op", "log", "-n1", "--patch", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    @  997ef8dbe320 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    │  snapshot working copy
+    │  args: jj debug snapshot
+    │
+    │  Changed commits:
+    │  ○  + qpvuntsm 9eb8c5cc (no description set)
+    │     - qpvuntsm hidden 6b57e33c (no description set)
+    │     file | 1 +
+    │     1 file changed, 1 insertion(+), 0 deletions(-)
+    │     Modified regular file file:
+    │        1    1: a
+    │             2: b
+    │
+    │  Changed working copy default@:
+    │  + qpvuntsm 9eb8c5cc (no description set)
+    │  - qpvuntsm hidden 6b57e33c (no description set)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_diff_sibling() {
     let test_env = TestEnvironment::default();
@@ -1691,74 +2691,144 @@ fn test_op_diff_sibling() {
     Concurrent modification detected, resolving automatically.
     [EOF]
     ");
-    let output = work_dir
-        .run_jj(["op", "log", "--no-graph", r#"-Tid.short() ++ "\n""#])
-        .success();
-    let [head_op_id, p1_op_id, _, _, _, _, p2_op_id] =
-        output.stdout.raw().lines().next_array().unwrap();
-    insta::assert_snapshot!(head_op_id, @"0fce99d88f9f");
-    insta::assert_snapshot!(p1_op_id, @"9f1e89c03a5b");
-    insta::assert_snapshot!(p2_op_id, @"252ff3a5a0e6");
+    let output = work_dir
+        .run_jj(["op", "log", "--no-graph", r#"-Tid.short() ++ "\n""#])
+        .success();
+    let [head_op_id, p1_op_id, _, _, _, _, p2_op_id] =
+        output.stdout.raw().lines().next_array().unwrap();
+    insta::assert_snapshot!(head_op_id, @"0fce99d88f9f");
+    insta::assert_snapshot!(p1_op_id, @"9f1e89c03a5b");
+    insta::assert_snapshot!(p2_op_id, @"252ff3a5a0e6");
+
+    // Diff between p1 and p2 operations should work no matter if p2 is chosen
+    // as a base operation.
+    let output = work_dir.run_jj([
+        "op",
+        "diff",
+        "--at-op",
+        p1_op_id,
+        "--from",
+        p1_op_id,
+        "--to",
+        p2_op_id,
+        "--summary",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 9f1e89c03a5b (2001-02-03 08:05:11) new empty commit
+      To operation: 252ff3a5a0e6 (2001-02-03 08:05:12) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+
+    Changed commits:
+    ○    - mzvwutvl hidden 08c63613 (empty) A
+    ├─╮
+    │ ○  - kkmpptxz hidden 6c70a4f7 A.1
+    │    A file1
+    ○  - zsuskuln hidden 47b9525e A.2
+       A file2
+    ○  + qpvuntsm b1ca67e2 (empty) B
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+         (description changed)
+
+    Changed working copy default@:
+    + qpvuntsm b1ca67e2 (empty) B
+    - mzvwutvl hidden 08c63613 (empty) A
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "diff",
+        "--at-op",
+        p2_op_id,
+        "--from",
+        p2_op_id,
+        "--to",
+        p1_op_id,
+        "--summary",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 252ff3a5a0e6 (2001-02-03 08:05:12) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+      To operation: 9f1e89c03a5b (2001-02-03 08:05:11) new empty commit
+
+    Changed commits:
+    ○  - qpvuntsm hidden b1ca67e2 (empty) B
+    ○    + mzvwutvl 08c63613 (empty) A
+    ├─╮
+    │ ○  + kkmpptxz 6c70a4f7 A.1
+    │    A file1
+    ○  + zsuskuln 47b9525e A.2
+       A file2
+
+    Changed working copy default@:
+    + mzvwutvl 08c63613 (empty) A
+    - qpvuntsm hidden b1ca67e2 (empty) B
+    [EOF]
+    ");
+
+    // The flat list shows all entries of merge-shaped changes
+    let output = work_dir.run_jj([
+        "op",
+        "diff",
+        "--at-op",
+        p2_op_id,
+        "--from",
+        p2_op_id,
+        "--to",
+        p1_op_id,
+        "--no-graph",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 252ff3a5a0e6 (2001-02-03 08:05:12) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+      To operation: 9f1e89c03a5b (2001-02-03 08:05:11) new empty commit
+
+    Changed commits:
+    - qpvuntsm hidden b1ca67e2 (empty) B
+    + mzvwutvl 08c63613 (empty) A
+    + zsuskuln 47b9525e A.2
+    + kkmpptxz 6c70a4f7 A.1
+
+    Changed working copy default@:
+    + mzvwutvl 08c63613 (empty) A
+    - qpvuntsm hidden b1ca67e2 (empty) B
+    [EOF]
+    ");
 
-    // Diff between p1 and p2 operations should work no matter if p2 is chosen
-    // as a base operation.
+    // Diff from the merge base shows what each side contributed.
     let output = work_dir.run_jj([
         "op",
         "diff",
         "--at-op",
         p1_op_id,
-        "--from",
-        p1_op_id,
-        "--to",
+        "--merge-base",
         p2_op_id,
+        p1_op_id,
         "--summary",
     ]);
     insta::assert_snapshot!(output, @r"
-    From operation: 9f1e89c03a5b (2001-02-03 08:05:11) new empty commit
+    From operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
       To operation: 252ff3a5a0e6 (2001-02-03 08:05:12) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
 
     Changed commits:
-    ○    - mzvwutvl hidden 08c63613 (empty) A
-    ├─╮
-    │ ○  - kkmpptxz hidden 6c70a4f7 A.1
-    │    A file1
-    ○  - zsuskuln hidden 47b9525e A.2
-       A file2
     ○  + qpvuntsm b1ca67e2 (empty) B
        - qpvuntsm hidden e8849ae1 (empty) (no description set)
+         (description changed)
 
     Changed working copy default@:
     + qpvuntsm b1ca67e2 (empty) B
-    - mzvwutvl hidden 08c63613 (empty) A
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
     [EOF]
     ");
     let output = work_dir.run_jj([
         "op",
         "diff",
         "--at-op",
-        p2_op_id,
-        "--from",
-        p2_op_id,
-        "--to",
         p1_op_id,
+        "--merge-base",
+        p1_op_id,
+        head_op_id,
         "--summary",
     ]);
     insta::assert_snapshot!(output, @r"
-    From operation: 252ff3a5a0e6 (2001-02-03 08:05:12) describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    From operation: 9f1e89c03a5b (2001-02-03 08:05:11) new empty commit
       To operation: 9f1e89c03a5b (2001-02-03 08:05:11) new empty commit
-
-    Changed commits:
-    ○  - qpvuntsm hidden b1ca67e2 (empty) B
-    ○    + mzvwutvl 08c63613 (empty) A
-    ├─╮
-    │ ○  + kkmpptxz 6c70a4f7 A.1
-    │    A file1
-    ○  + zsuskuln 47b9525e A.2
-       A file2
-
-    Changed working copy default@:
-    + mzvwutvl 08c63613 (empty) A
-    - qpvuntsm hidden b1ca67e2 (empty) B
     [EOF]
     ");
 }
@@ -2031,6 +3101,7 @@ fn test_op_diff_at_merge_op_with_rebased_commits() {
     │ │  │  - rlvkpnrz hidden ab92d1a8 (empty) 2a
     │ │  ○  + qpvuntsm 6666e5c3 (empty) 1
     │ │     - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    │ │       (description changed)
     │ │
     │ │  Changed working copy default@:
     │ │  + rlvkpnrz 7ed5a610 (empty) 2a
@@ -2042,6 +3113,7 @@ fn test_op_diff_at_merge_op_with_rebased_commits() {
     │    Changed commits:
     │    ○  + rlvkpnrz 50ec12eb (empty) 2b
     │       - rlvkpnrz hidden ab92d1a8 (empty) 2a
+    │         (description changed)
     │
     │    Changed working copy default@:
     │    + rlvkpnrz 50ec12eb (empty) 2b
@@ -2050,6 +3122,231 @@ fn test_op_diff_at_merge_op_with_rebased_commits() {
     ");
 }
 
+#[test]
+fn test_op_diff_multiple_working_copies() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir
+        .run_jj(["workspace", "add", "--name=second", "-r@-", "../secondary"])
+        .success();
+
+    // Rewriting the shared parent changes both working-copy commits
+    work_dir
+        .run_jj(["describe", "-r@-", "-m", "new base"])
+        .success();
+    let output = work_dir.run_jj(["op", "diff"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 16bee1ee5c5b (2001-02-03 08:05:09) create initial working-copy commit in workspace second
+      To operation: 1c45aa87e8b5 (2001-02-03 08:05:10) describe commit 572bf308e16a037421f872b5b8530d32aed12e20
+
+    Changed commits:
+    ○  + pmmvwywv 862cfa39 (empty) (no description set)
+    │  - pmmvwywv hidden 030b8216 (empty) (no description set)
+    │ ○  + rlvkpnrz 06ffe51d (empty) (no description set)
+    ├─╯  - rlvkpnrz hidden fdfc42cf (empty) (no description set)
+    ○  + qpvuntsm b9f5b16a (empty) new base
+       - qpvuntsm hidden 572bf308 (empty) base
+         (description changed)
+
+    Changed working copy default@:
+    + rlvkpnrz 06ffe51d (empty) (no description set)
+    - rlvkpnrz hidden fdfc42cf (empty) (no description set)
+    Changed working copy second@:
+    + pmmvwywv 862cfa39 (empty) (no description set)
+    - pmmvwywv hidden 030b8216 (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_diff_bookmark_rename() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo", "bar"])
+        .success();
+
+    // A deleted and an added bookmark at the same target is a rename
+    work_dir
+        .run_jj(["bookmark", "rename", "foo", "baz"])
+        .success();
+    let output = work_dir.run_jj(["op", "diff"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 44728a042a50 (2001-02-03 08:05:08) create bookmark foo, bar pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+      To operation: c6cbab8dcff7 (2001-02-03 08:05:09) rename bookmark foo to baz
+
+    Renamed local bookmarks:
+    foo -> baz:
+      qpvuntsm e8849ae1 bar baz | (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "diff", "--no-rename-detection"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 44728a042a50 (2001-02-03 08:05:08) create bookmark foo, bar pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+      To operation: c6cbab8dcff7 (2001-02-03 08:05:09) rename bookmark foo to baz
+
+    Changed local bookmarks:
+    baz:
+    + qpvuntsm e8849ae1 bar baz | (empty) (no description set)
+    - (absent)
+    foo:
+    + (absent)
+    - qpvuntsm e8849ae1 bar baz | (empty) (no description set)
+    [EOF]
+    ");
+
+    // Ambiguous pairs aren't detected as renames
+    work_dir
+        .run_jj(["bookmark", "delete", "bar", "baz"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "qux", "quux"])
+        .success();
+    let output = work_dir.run_jj(["op", "diff", "--from=@--", "--to=@"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: c6cbab8dcff7 (2001-02-03 08:05:09) rename bookmark foo to baz
+      To operation: 61084d2c7a2a (2001-02-03 08:05:13) create bookmark qux, quux pointing to commit e8849ae12c709f2321908879bc724fdb2ab8a781
+
+    Changed local bookmarks:
+    bar:
+    + (absent)
+    - qpvuntsm e8849ae1 quux qux | (empty) (no description set)
+    baz:
+    + (absent)
+    - qpvuntsm e8849ae1 quux qux | (empty) (no description set)
+    quux:
+    + qpvuntsm e8849ae1 quux qux | (empty) (no description set)
+    - (absent)
+    qux:
+    + qpvuntsm e8849ae1 quux qux | (empty) (no description set)
+    - (absent)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_diff_skip_snapshots() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file2", "foo\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "foo"])
+        .success();
+    work_dir.write_file("file3", "foo\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    let output = work_dir.run_jj(["op", "log", "-T", "description ++ '\n'"]);
+    insta::assert_snapshot!(output, @r"
+    @  snapshot working copy
+    ○  create bookmark foo pointing to commit 46de3a2c5aec4f415515149e4bf5ac1375146713
+    ○  snapshot working copy
+    ○  new empty commit
+    ○  describe commit c664a51bff8c9375136612b706f7706a775e0f32
+    ○  snapshot working copy
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+
+    // The working-copy commit was only rewritten by snapshots
+    let output = work_dir.run_jj(["op", "diff", "--from", "@---", "--to", "@"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: c0d04d4b798a (2001-02-03 08:05:09) new empty commit
+      To operation: c35f9095bd29 (2001-02-03 08:05:11) snapshot working copy
+
+    Changed commits:
+    ○  + kkmpptxz 4d7c8149 (no description set)
+       - kkmpptxz hidden 8855ab6f (empty) (no description set)
+
+    Changed working copy default@:
+    + kkmpptxz 4d7c8149 (no description set)
+    - kkmpptxz hidden 8855ab6f (empty) (no description set)
+
+    Changed local bookmarks:
+    foo:
+    + qpvuntsm 46de3a2c foo | first
+    - (absent)
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "diff",
+        "--from",
+        "@---",
+        "--to",
+        "@",
+        "--skip-snapshots",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    From operation: c0d04d4b798a (2001-02-03 08:05:09) new empty commit
+      To operation: c35f9095bd29 (2001-02-03 08:05:11) snapshot working copy
+
+    Changed local bookmarks:
+    foo:
+    + qpvuntsm 46de3a2c foo | first
+    - (absent)
+    [EOF]
+    ");
+
+    // Commits created or rewritten by other operations are still shown
+    let output = work_dir.run_jj(["op", "diff", "--from", "@------", "--to", "@"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+      To operation: c35f9095bd29 (2001-02-03 08:05:11) snapshot working copy
+
+    Changed commits:
+    ○  + kkmpptxz 4d7c8149 (no description set)
+    ○  + qpvuntsm 46de3a2c foo | first
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+
+    Changed working copy default@:
+    + kkmpptxz 4d7c8149 (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+
+    Changed local bookmarks:
+    foo:
+    + qpvuntsm 46de3a2c foo | first
+    - (absent)
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "diff",
+        "--from",
+        "@------",
+        "--to",
+        "@",
+        "--skip-snapshots",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+      To operation: c35f9095bd29 (2001-02-03 08:05:11) snapshot working copy
+
+    Changed commits:
+    ○  + kkmpptxz 4d7c8149 (no description set)
+    ○  + qpvuntsm 46de3a2c foo | first
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+
+    Changed working copy default@:
+    + kkmpptxz 4d7c8149 (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+
+    Changed local bookmarks:
+    foo:
+    + qpvuntsm 46de3a2c foo | first
+    - (absent)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_diff_word_wrap() {
     let test_env = TestEnvironment::default();
@@ -2464,57 +3761,163 @@ fn test_op_show() {
     point bookmark bookmark-1 to commit 8f340dd76dc637e4deac17f30056eef7d8eaf682
     args: jj bookmark set bookmark-1 -r @
 
-    Changed local bookmarks:
-    bookmark-1:
-    + tlkvzzqu 8f340dd7 bookmark-1* | (empty) new commit
-    - (added) zkmtkqvo 0dee6313 bookmark-1@origin | Commit 4
-    - (added) rnnslrkn 4ff62539 Commit 2
+    Changed local bookmarks:
+    bookmark-1:
+    + tlkvzzqu 8f340dd7 bookmark-1* | (empty) new commit
+    - (added) zkmtkqvo 0dee6313 bookmark-1@origin | Commit 4
+    - (added) rnnslrkn 4ff62539 Commit 2
+    [EOF]
+    ");
+
+    // Test deletion of local bookmark.
+    let output = work_dir.run_jj(["bookmark", "delete", "bookmark-2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Deleted 1 bookmarks.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "show"]);
+    insta::assert_snapshot!(output, @r"
+    5dc28083437f test-username@host.example.com 2001-02-03 04:05:31.000 +07:00 - 2001-02-03 04:05:31.000 +07:00
+    delete bookmark bookmark-2
+    args: jj bookmark delete bookmark-2
+
+    Changed local bookmarks:
+    bookmark-2:
+    + (absent)
+    - kulxwnxm e1a239a5 bookmark-2@origin | Commit 5
+    [EOF]
+    ");
+
+    // Test pushing to Git remote.
+    let output = work_dir.run_jj(["git", "push", "--tracked", "--deleted"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark-1 from 0dee631320b1 to 8f340dd76dc6
+      Delete bookmark bookmark-2 from e1a239a57eb1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "show"]);
+    insta::assert_snapshot!(output, @r"
+    7d47c561dcb4 test-username@host.example.com 2001-02-03 04:05:33.000 +07:00 - 2001-02-03 04:05:33.000 +07:00
+    push all tracked bookmarks to git remote origin
+    args: jj git push --tracked --deleted
+
+    Changed remote bookmarks:
+    bookmark-1@origin:
+    + tracked tlkvzzqu 8f340dd7 bookmark-1 | (empty) new commit
+    - tracked zkmtkqvo 0dee6313 Commit 4
+    bookmark-2@origin:
+    + untracked (absent)
+    - tracked kulxwnxm e1a239a5 Commit 5
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_show_no_args() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    let output = work_dir.run_jj(["op", "show", "--no-args", "-p"]);
+    insta::assert_snapshot!(output, @r"
+    2d3c72ec8eb7 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    describe commit 6b57e33cc56babbeaa6bcd6e2a296236b52ad93c
+
+    Changed commits:
+    ○  + qpvuntsm d89b4dad first
+       - qpvuntsm hidden 6b57e33c (no description set)
+         (description changed)
+       Modified commit description:
+       @@ -0,0 +1,1 @@
+       +first
+
+    Changed working copy default@:
+    + qpvuntsm d89b4dad first
+    - qpvuntsm hidden 6b57e33c (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--no-args", "-n2"]);
+    insta::assert_snapshot!(output, @r"
+    @  2d3c72ec8eb7 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    │  describe commit 6b57e33cc56babbeaa6bcd6e2a296236b52ad93c
+    ○  737828fe371a test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    │  snapshot working copy
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_show_against_parent() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["new", "-m", "concurrent", "--at-op=@-"])
+        .success();
+    // Reconcile the divergent operations
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    // Changes of a merge operation aren't shown by default
+    let output = work_dir.run_jj(["op", "show", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    10e4be57df2c test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    reconcile divergent operations
+    args: jj debug snapshot
     [EOF]
     ");
 
-    // Test deletion of local bookmark.
-    let output = work_dir.run_jj(["bookmark", "delete", "bookmark-2"]);
+    // The changes are shown relative to the selected parent
+    let output = work_dir.run_jj(["op", "show", "--no-graph", "--against-parent=1"]);
     insta::assert_snapshot!(output, @r"
-    ------- stderr -------
-    Deleted 1 bookmarks.
+    10e4be57df2c test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    reconcile divergent operations
+    args: jj debug snapshot
+
+    Changed commits:
+    + kkmpptxz a6fdc45b (empty) concurrent
     [EOF]
     ");
-    let output = work_dir.run_jj(["op", "show"]);
+    let output = work_dir.run_jj(["op", "show", "--no-graph", "--against-parent=2"]);
     insta::assert_snapshot!(output, @r"
-    5dc28083437f test-username@host.example.com 2001-02-03 04:05:31.000 +07:00 - 2001-02-03 04:05:31.000 +07:00
-    delete bookmark bookmark-2
-    args: jj bookmark delete bookmark-2
+    10e4be57df2c test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    reconcile divergent operations
+    args: jj debug snapshot
 
-    Changed local bookmarks:
-    bookmark-2:
-    + (absent)
-    - kulxwnxm e1a239a5 bookmark-2@origin | Commit 5
+    Changed commits:
+    + kkmpptxz a6fdc45b (empty) concurrent
+    - kkmpptxz hidden 1bf918cc (empty) concurrent
+    + qpvuntsm 68a50538 (empty) first
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+      (description changed)
+
+    Changed working copy default@:
+    + qpvuntsm 68a50538 (empty) first
+    - kkmpptxz hidden 1bf918cc (empty) concurrent
     [EOF]
     ");
 
-    // Test pushing to Git remote.
-    let output = work_dir.run_jj(["git", "push", "--tracked", "--deleted"]);
+    // The index must be in range
+    let output = work_dir.run_jj(["op", "show", "--against-parent=3"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Changes to push to origin:
-      Move forward bookmark bookmark-1 from 0dee631320b1 to 8f340dd76dc6
-      Delete bookmark bookmark-2 from e1a239a57eb1
+    Error: Operation 10e4be57df2c has only 2 parent(s)
     [EOF]
+    [exit status: 1]
     ");
-    let output = work_dir.run_jj(["op", "show"]);
+    let output = work_dir.run_jj(["op", "show", "--against-parent=0"]);
     insta::assert_snapshot!(output, @r"
-    7d47c561dcb4 test-username@host.example.com 2001-02-03 04:05:33.000 +07:00 - 2001-02-03 04:05:33.000 +07:00
-    push all tracked bookmarks to git remote origin
-    args: jj git push --tracked --deleted
+    ------- stderr -------
+    error: invalid value '0' for '--against-parent <INDEX>': number would be zero for non-zero type
 
-    Changed remote bookmarks:
-    bookmark-1@origin:
-    + tracked tlkvzzqu 8f340dd7 bookmark-1 | (empty) new commit
-    - tracked zkmtkqvo 0dee6313 Commit 4
-    bookmark-2@origin:
-    + untracked (absent)
-    - tracked kulxwnxm e1a239a5 Commit 5
+    For more information, try '--help'.
     [EOF]
+    [exit status: 2]
     ");
 }
 
@@ -2710,6 +4113,386 @@ fn test_op_show_patch() {
     ");
 }
 
+#[test]
+fn test_op_patch_default_format() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"ui.diff-formatter = ":git""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    // The configured format is used without --git
+    let output = work_dir.run_jj(["op", "show", "-p", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    b8e8ee153014 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj debug snapshot
+
+    Changed commits:
+    + qpvuntsm 6b57e33c (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..7898192261
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +a
+
+    Changed working copy default@:
+    + qpvuntsm 6b57e33c (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-p", "--no-graph", "-n1"]);
+    insta::assert_snapshot!(output, @r"
+    b8e8ee153014 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj debug snapshot
+
+    Changed commits:
+    + qpvuntsm 6b57e33c (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..7898192261
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +a
+
+    Changed working copy default@:
+    + qpvuntsm 6b57e33c (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "diff", "-p", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+      To operation: b8e8ee153014 (2001-02-03 08:05:08) snapshot working copy
+
+    Changed commits:
+    + qpvuntsm 6b57e33c (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..7898192261
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +a
+
+    Changed working copy default@:
+    + qpvuntsm 6b57e33c (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_show_max_patch_bytes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "line\n".repeat(10));
+    work_dir.write_file("file2", "a\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    // Each file's patch is truncated separately
+    let output = work_dir.run_jj(["op", "show", "-p", "--git", "--max-patch-bytes=30"]);
+    insta::assert_snapshot!(output, @r"
+    f77b647e3c4f test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj debug snapshot
+
+    Changed commits:
+    ○  + qpvuntsm 3897354b (no description set)
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+       diff --git a/file1 b/file1
+       new file mode 100644
+       index 0000000000..99945e70e3
+       --- /dev/null
+       +++ b/file1
+       @@ -0,0 +1,10 @@
+       +line
+       +line
+       ... (patch truncated)
+       diff --git a/file2 b/file2
+       new file mode 100644
+       index 0000000000..7898192261
+       --- /dev/null
+       +++ b/file2
+       @@ -0,0 +1,1 @@
+       +a
+
+    Changed working copy default@:
+    + qpvuntsm 3897354b (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "diff", "--color-words", "--max-patch-bytes=30"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+      To operation: f77b647e3c4f (2001-02-03 08:05:08) snapshot working copy
+
+    Changed commits:
+    ○  + qpvuntsm 3897354b (no description set)
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+       Added regular file file1:
+               1: line
+           ... (patch truncated)
+       Added regular file file2:
+               1: a
+
+    Changed working copy default@:
+    + qpvuntsm 3897354b (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // The limit can be configured, and the flag overrides the config
+    let output = work_dir.run_jj([
+        "op",
+        "diff",
+        "--color-words",
+        "--config=ui.op-diff.max-patch-bytes=30",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+      To operation: f77b647e3c4f (2001-02-03 08:05:08) snapshot working copy
+
+    Changed commits:
+    ○  + qpvuntsm 3897354b (no description set)
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+       Added regular file file1:
+               1: line
+           ... (patch truncated)
+       Added regular file file2:
+               1: a
+
+    Changed working copy default@:
+    + qpvuntsm 3897354b (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "show",
+        "--git",
+        "--config=ui.op-diff.max-patch-bytes=0",
+        "--max-patch-bytes=1000",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    f77b647e3c4f test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj debug snapshot
+
+    Changed commits:
+    ○  + qpvuntsm 3897354b (no description set)
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+       diff --git a/file1 b/file1
+       new file mode 100644
+       index 0000000000..99945e70e3
+       --- /dev/null
+       +++ b/file1
+       @@ -0,0 +1,10 @@
+       +line
+       +line
+       +line
+       +line
+       +line
+       +line
+       +line
+       +line
+       +line
+       +line
+       diff --git a/file2 b/file2
+       new file mode 100644
+       index 0000000000..7898192261
+       --- /dev/null
+       +++ b/file2
+       @@ -0,0 +1,1 @@
+       +a
+
+    Changed working copy default@:
+    + qpvuntsm 3897354b (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Only the diff header is shown if the limit is 0
+    let output = work_dir.run_jj(["op", "show", "--git", "--max-patch-bytes=0"]);
+    insta::assert_snapshot!(output, @r"
+    f77b647e3c4f test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj debug snapshot
+
+    Changed commits:
+    ○  + qpvuntsm 3897354b (no description set)
+       - qpvuntsm hidden e8849ae1 (empty) (no description set)
+       diff --git a/file1 b/file1
+       new file mode 100644
+       index 0000000000..99945e70e3
+       --- /dev/null
+       +++ b/file1
+       ... (patch truncated)
+       diff --git a/file2 b/file2
+       new file mode 100644
+       index 0000000000..7898192261
+       --- /dev/null
+       +++ b/file2
+       ... (patch truncated)
+
+    Changed working copy default@:
+    + qpvuntsm 3897354b (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_log_patch_with_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "a\n");
+    work_dir.run_jj(["commit", "-m", "both"]).success();
+    work_dir.write_file("file2", "b\n");
+    work_dir.run_jj(["commit", "-m", "file2 only"]).success();
+
+    // Patches are restricted to the given paths, but all changed commits are
+    // listed.
+    let output = work_dir.run_jj(["op", "log", "-n4", "--no-graph", "-p", "--git", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    220c31b063b9 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    commit 9b484abfa65aeb7cb242d3cc80c7c17338cc50d7
+    args: jj commit -m 'file2 only'
+
+    Changed commits:
+    + kkmpptxz d9d639b6 (empty) (no description set)
+    + rlvkpnrz 242aa430 file2 only
+    - rlvkpnrz hidden 9b484abf (no description set)
+      (description changed)
+    Modified commit description:
+    @@ -0,0 +1,1 @@
+    +file2 only
+
+    Changed working copy default@:
+    + kkmpptxz d9d639b6 (empty) (no description set)
+    - rlvkpnrz hidden 9b484abf (no description set)
+    0ee056c7e976 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    snapshot working copy
+    args: jj commit -m 'file2 only'
+
+    Changed commits:
+    + rlvkpnrz 9b484abf (no description set)
+    - rlvkpnrz hidden eda25651 (empty) (no description set)
+
+    Changed working copy default@:
+    + rlvkpnrz 9b484abf (no description set)
+    - rlvkpnrz hidden eda25651 (empty) (no description set)
+    de9a0b89b5c5 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    commit fc6f5e823009d73bb850411667121f2b13ef5bc5
+    args: jj commit -m both
+
+    Changed commits:
+    + rlvkpnrz eda25651 (empty) (no description set)
+    + qpvuntsm 1c7b58e3 both
+    - qpvuntsm hidden fc6f5e82 (no description set)
+      (description changed)
+    Modified commit description:
+    @@ -0,0 +1,1 @@
+    +both
+
+    Changed working copy default@:
+    + rlvkpnrz eda25651 (empty) (no description set)
+    - qpvuntsm hidden fc6f5e82 (no description set)
+    409222a28c33 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj commit -m both
+
+    Changed commits:
+    + qpvuntsm fc6f5e82 (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    diff --git a/file1 b/file1
+    new file mode 100644
+    index 0000000000..7898192261
+    --- /dev/null
+    +++ b/file1
+    @@ -0,0 +1,1 @@
+    +a
+
+    Changed working copy default@:
+    + qpvuntsm fc6f5e82 (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Changed commits not touching the given paths can be omitted.
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-n4",
+        "--no-graph",
+        "--summary",
+        "--only-matching",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    220c31b063b9 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    commit 9b484abfa65aeb7cb242d3cc80c7c17338cc50d7
+    args: jj commit -m 'file2 only'
+
+    Changed working copy default@:
+    + kkmpptxz d9d639b6 (empty) (no description set)
+    - rlvkpnrz hidden 9b484abf (no description set)
+    0ee056c7e976 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    snapshot working copy
+    args: jj commit -m 'file2 only'
+
+    Changed working copy default@:
+    + rlvkpnrz 9b484abf (no description set)
+    - rlvkpnrz hidden eda25651 (empty) (no description set)
+    de9a0b89b5c5 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    commit fc6f5e823009d73bb850411667121f2b13ef5bc5
+    args: jj commit -m both
+
+    Changed working copy default@:
+    + rlvkpnrz eda25651 (empty) (no description set)
+    - qpvuntsm hidden fc6f5e82 (no description set)
+    409222a28c33 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    snapshot working copy
+    args: jj commit -m both
+
+    Changed commits:
+    + qpvuntsm fc6f5e82 (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    A file1
+
+    Changed working copy default@:
+    + qpvuntsm fc6f5e82 (no description set)
+    - qpvuntsm hidden e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "--only-matching"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      <FILESETS>...
+
+    Usage: jj operation log --only-matching <FILESETS>...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 fn init_bare_git_repo(git_repo_path: &Path) -> gix::Repository {
     let git_repo = git::init_bare(git_repo_path);
     let commit_result = git::add_commit(
@@ -314,6 +314,8 @@ fn test_show_with_no_template() {
     - builtin_log_compact_full_description
     - builtin_log_detailed
     - builtin_log_node
+    - builtin_log_node_ancestry_path
+    - builtin_log_node_ancestry_path_ascii
     - builtin_log_node_ascii
     - builtin_log_oneline
     - builtin_op_log_comfortable
@@ -14,6 +14,7 @@
 
 use std::path::PathBuf;
 
+use crate::common::create_commit_with_files;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
@@ -53,6 +54,7 @@ fn test_squash() {
     ------- stderr -------
     Working copy  (@) now at: vruxwmqv 2cf02eb8 (empty) (no description set)
     Parent commit (@-)      : kkmpptxz 9422c8d6 b c | (no description set)
+    Warning: Bookmarks b and c now point to the same commit
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -76,6 +78,7 @@ fn test_squash() {
     Rebased 1 descendant commits
     Working copy  (@) now at: mzvwutvl 441a7a3a c | (no description set)
     Parent commit (@-)      : qpvuntsm 105931bf a b | (no description set)
+    Warning: Bookmarks a and b now point to the same commit
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -199,6 +202,7 @@ fn test_squash_partial() {
     Rebased 1 descendant commits
     Working copy  (@) now at: mzvwutvl 34484d82 c | (no description set)
     Parent commit (@-)      : qpvuntsm 3141e675 a b | (no description set)
+    Warning: Bookmarks a and b now point to the same commit
     [EOF]
     ");
 
@@ -358,6 +362,144 @@ fn test_squash_partial() {
     ");
 }
 
+#[test]
+fn test_squash_partial_tool() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.write_file("file2", "b\n");
+    std::fs::write(&edit_script, "reset file2").unwrap();
+
+    // --tool overrides ui.diff-editor and implies --interactive
+    let output = work_dir.run_jj([
+        "squash",
+        "--config=ui.diff-editor=missing-diff-editor",
+        "--tool=fake-diff-editor",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: rlvkpnrz cde8288f (no description set)
+    Parent commit (@-)      : qpvuntsm 09f34aa4 (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    [EOF]
+    ");
+
+    // An unknown tool is an error
+    let output = work_dir.run_jj(["squash", "--tool=missing-diff-editor"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to edit diff
+    Caused by:
+    1: Error executing 'missing-diff-editor' (run with --debug to see the exact invocation)
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_squash_restore_descendants() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "a", &[], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["a"], &[("file2", "b\n")]);
+    create_commit_with_files(&work_dir, "c", &["b"], &[("file", "c\n")]);
+    create_commit_with_files(&work_dir, "d", &["a"], &[("file3", "d\n")]);
+    // Test the setup
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  1cfa7a69c330 d d
+    │ ○  03a21c9a9586 c c
+    │ ○  2e20bfea037f b b
+    ├─╯
+    ○  b27cb72210b6 a a
+    ◆  000000000000 (empty)
+    [EOF]
+    ");
+
+    // "c" keeps its content and diff since "b" was squashed into its parent.
+    // "d" keeps its content, but its diff now reverts the squashed changes.
+    let output = work_dir.run_jj([
+        "squash",
+        "--from=b",
+        "--into=a",
+        "-u",
+        "--restore-descendants",
+        "--verbose",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 descendant commits (while preserving their content)
+      royxmykx f5437667 c | c
+      vruxwmqv b0cd01df d | d (diff changed)
+    Working copy  (@) now at: vruxwmqv b0cd01df d | d
+    Parent commit (@-)      : rlvkpnrz 64dc8c7a a b | a
+    Warning: Bookmarks a and b now point to the same commit
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  b0cd01dfdc3a d d
+    │ ○  f54376673544 c c
+    ├─╯
+    ○  64dc8c7a1deb a b a
+    ◆  000000000000 (empty)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary", "-r=c"]);
+    insta::assert_snapshot!(output, @r"
+    M file
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary", "-r=d"]);
+    insta::assert_snapshot!(output, @r"
+    D file2
+    A file3
+    [EOF]
+    ");
+
+    // Without --verbose, only the number of descendants is reported
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj([
+        "squash",
+        "--from=b",
+        "--into=a",
+        "-u",
+        "--restore-descendants",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 descendant commits (while preserving their content)
+    Working copy  (@) now at: vruxwmqv 03f4e8b4 d | d
+    Parent commit (@-)      : rlvkpnrz 398bca73 a b | a
+    Warning: Bookmarks a and b now point to the same commit
+    [EOF]
+    ");
+
+    // --verbose only applies to --restore-descendants
+    let output = work_dir.run_jj(["squash", "--from=b", "--into=a", "--verbose"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --restore-descendants
+
+    Usage: jj squash --restore-descendants --from <REVSETS> --into <REVSET> --verbose [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_squash_keep_emptied() {
     let test_env = TestEnvironment::default();
@@ -489,6 +631,7 @@ fn test_squash_from_to() {
     Working copy  (@) now at: kmkuslsw 941ab024 f | (no description set)
     Parent commit (@-)      : znkkpsqq 4ebe104a e | (no description set)
     Added 0 files, modified 1 files, removed 0 files
+    Warning: Bookmarks b and c now point to the same commit
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -521,6 +664,7 @@ fn test_squash_from_to() {
     ------- stderr -------
     Working copy  (@) now at: kmkuslsw c102d2c4 f | (no description set)
     Parent commit (@-)      : znkkpsqq beb7c033 e | (no description set)
+    Warning: Bookmarks a and d now point to the same commit
     [EOF]
     ");
     // The change has been removed from the source (the change pointed to by 'd'
@@ -551,6 +695,7 @@ fn test_squash_from_to() {
     Rebased 1 descendant commits
     Working copy  (@) now at: kmkuslsw 1bc21d4e f | (no description set)
     Parent commit (@-)      : vruxwmqv 8b6b080a d e | (no description set)
+    Warning: Bookmarks d and e now point to the same commit
     [EOF]
     ");
     // The change has been removed from the source (the change pointed to by 'e'
@@ -573,6 +718,109 @@ fn test_squash_from_to() {
     ");
 }
 
+#[test]
+fn test_squash_from_including_destination() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.write_file("file2", "b\n");
+    work_dir.run_jj(["new", "-m", "c"]).success();
+    work_dir.write_file("file3", "c\n");
+    work_dir.run_jj(["new"]).success();
+
+    // Errors out by default
+    let output = work_dir.run_jj([
+        "squash",
+        "--from=description(a)::",
+        "--into=description(a)",
+        "-u",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Source and destination cannot be the same
+    Hint: Use `--exclude-destination` to squash the other source revisions
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The destination can be excluded from the sources
+    let output = work_dir.run_jj([
+        "squash",
+        "--from=description(a)::",
+        "--into=description(a)",
+        "-u",
+        "--exclude-destination",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Excluded the destination qpvuntsm 815074ec a from the source revisions
+    Working copy  (@) now at: yqosqzyt 329575dc (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm e8bc258a a
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output_with_description(&work_dir), @r"
+    @  329575dcc1fb
+    ○  e8bc258a0f87 a
+    ◆  000000000000
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file2
+    file3
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_squash_root() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // The root commit can be neither the source nor the destination
+    let output = work_dir.run_jj(["squash", "--into", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot squash into the root commit
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["squash", "--from", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot squash the root commit
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["squash", "--from", "root()", "--into", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot squash the root commit
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["squash", "-r", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot squash the root commit
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["squash"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot squash into the root commit
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_squash_from_to_partial() {
     let mut test_env = TestEnvironment::default();
@@ -627,6 +875,7 @@ fn test_squash_from_to_partial() {
     Working copy  (@) now at: vruxwmqv 85589465 d | (no description set)
     Parent commit (@-)      : qpvuntsm e3e04bea a | (no description set)
     Added 0 files, modified 2 files, removed 0 files
+    Warning: Bookmarks b and c now point to the same commit
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -776,6 +1025,33 @@ fn test_squash_from_to_partial() {
     ");
 }
 
+#[test]
+fn test_squash_into_multiple() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["bookmark", "create", "-r@", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.run_jj(["new", "a", "-m", "c"]).success();
+    work_dir.run_jj(["edit", "a"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // The destination must resolve to a single revision
+    let output = work_dir.run_jj(["squash", "--into", "@+"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revset `@+` resolved to more than one revision
+    Hint: The revset `@+` resolved to these revisions:
+      zsuskuln 41023c5d (empty) c
+      kkmpptxz d907e2a3 (empty) b
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+}
+
 #[test]
 fn test_squash_from_multiple() {
     let test_env = TestEnvironment::default();
@@ -836,7 +1112,7 @@ fn test_squash_from_multiple() {
 
     // Squash a few commits sideways
     let output = work_dir.run_jj(["squash", "--from=b", "--from=c", "--into=d"]);
-    insta::assert_snapshot!(output, @r###"
+    insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Rebased 2 descendant commits
     Working copy  (@) now at: kpqxywon 703c6f0c f | (no description set)
@@ -849,8 +1125,9 @@ fn test_squash_from_multiple() {
     Then use `jj resolve`, or edit the conflict markers in the file directly.
     Once the conflicts are resolved, you can inspect the result with `jj diff`.
     Then run `jj squash` to move the resolution into the conflicted commit.
+    Warning: Bookmarks a, b and c now point to the same commit
     [EOF]
-    "###);
+    ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
     @  703c6f0cae6f f
     ○    3d6a18995cae e
@@ -885,6 +1162,8 @@ fn test_squash_from_multiple() {
     Rebased 1 descendant commits
     Working copy  (@) now at: xznxytkn ec32238b (empty) (no description set)
     Parent commit (@-)      : yostqsxw 5298eef6 e f | (no description set)
+    Warning: Bookmarks a, b and c now point to the same commit
+    Warning: Bookmarks e and f now point to the same commit
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -1180,6 +1459,180 @@ fn test_squash_from_multiple_partial_no_op() {
     ");
 }
 
+#[test]
+fn test_squash_from_multiple_interactive() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Create history like this:
+    //   C
+    //   |
+    //   B D
+    //   |/
+    //   A
+    work_dir.run_jj(["describe", "-m=a"]).success();
+    work_dir.write_file("shared", "1\n2\n3\n");
+    work_dir.run_jj(["new", "-m=b"]).success();
+    work_dir.write_file("b", "b\n");
+    work_dir.write_file("shared", "1b\n2\n3\n");
+    work_dir.run_jj(["new", "-m=c"]).success();
+    work_dir.write_file("c", "c\n");
+    work_dir.write_file("shared", "1b\n2\n3c\n");
+    work_dir.run_jj(["new", "description(a)", "-m=d"]).success();
+
+    // The changes of all sources are shown in a single diff, and unselected
+    // changes are left in their sources
+    std::fs::write(
+        &edit_script,
+        ["dump JJ-INSTRUCTIONS instrs", "reset b"].join("\0"),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["squash", "--from=description(b)|description(c)", "-i", "-u"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: mzvwutvl 97241897 d
+    Parent commit (@-)      : qpvuntsm 1a1a3784 a
+    Added 1 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("instrs")).unwrap(), @r"
+    You are moving changes from:
+      kkmpptxz dc8e1735 b
+      zsuskuln bbab2cb4 c
+    into commit: mzvwutvl d6bc75c3 (empty) d
+
+    The left side of the diff shows the contents of the parent commits. The
+    right side initially shows the combined contents of the commits you're
+    moving changes from. If several commits changed the same file, the
+    contents of the last commit are shown, and the file can only be
+    selected as a whole.
+
+    Adjust the right side until the diff shows the changes you want to move
+    to the destination. If you don't make any changes, then all the changes
+    from the sources will be moved into the destination.
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  97241897f371 d
+    │ ○  f65a9dc92348 b
+    ├─╯
+    ○  1a1a3784a855 a
+    ◆  000000000000 (empty)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list", "-r=description(b)"]);
+    insta::assert_snapshot!(output, @r"
+    b
+    shared
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "shared"]);
+    insta::assert_snapshot!(output, @r"
+    1b
+    2
+    3c
+    [EOF]
+    ");
+
+    // If a file changed by several sources is deselected, it's left unchanged
+    // in all sources, and isn't moved to the destination
+    work_dir.run_jj(["undo"]).success();
+    std::fs::write(&edit_script, "reset shared").unwrap();
+    let output = work_dir.run_jj(["squash", "--from=description(b)|description(c)", "-i", "-u"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: mzvwutvl 5479cd85 d
+    Parent commit (@-)      : qpvuntsm 1a1a3784 a
+    Added 2 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=description(b)", "shared"]);
+    insta::assert_snapshot!(output, @r"
+    1b
+    2
+    3
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=description(c)", "shared"]);
+    insta::assert_snapshot!(output, @r"
+    1b
+    2
+    3c
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "shared"]);
+    insta::assert_snapshot!(output, @r"
+    1
+    2
+    3
+    [EOF]
+    ");
+
+    // Part of a file changed by several sources can't be selected
+    work_dir.run_jj(["undo"]).success();
+    std::fs::write(
+        &edit_script,
+        ["reset b", "write shared\n1b\n2\n3\n"].join("\0"),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["squash", "--from=description(b)|description(c)", "-i", "-u"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot select part of the changes to shared made by several source revisions
+    Hint: Select all or none of the changes to this file, or squash the sources one at a time.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_squash_evolog_note() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m=a"]).success();
+    work_dir.write_file("a", "a\n");
+    work_dir.run_jj(["new", "-m=b"]).success();
+    work_dir.write_file("b", "b\n");
+
+    work_dir
+        .run_jj(["squash", "-u", "--evolog-note=fixup applied"])
+        .success();
+    let output = work_dir.run_jj([
+        "evolog",
+        "-r=@-",
+        "--limit=2",
+        "-T",
+        r#"separate(" ", commit_id.short(), description)"#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○    3119beec9b87 a
+    ├─╮  -- operation 5cb832125d9b (2001-02-03 08:05:10) squash commits into 93d495c46d899bfc7a60b1cf8d6a4f0f9e2e6ff1
+    │ │  -- note: fixup applied
+    │ ○  b1a17f79a1a5 b
+    │ │  -- operation 8b8db40673e7 (2001-02-03 08:05:10) snapshot working copy
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "evolog",
+        "-r=@-",
+        "--no-graph",
+        "--limit=1",
+        "-T",
+        r#"separate(" ", commit_id.short(), description)"#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    3119beec9b87 a
+    -- operation 5cb832125d9b (2001-02-03 08:05:10) squash commits into 93d495c46d899bfc7a60b1cf8d6a4f0f9e2e6ff1
+    -- note: fixup applied
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(
@@ -1458,6 +1911,110 @@ fn test_squash_description() {
     insta::assert_snapshot!(get_description(&work_dir, "@-"), @"");
 }
 
+#[test]
+fn test_squash_dry_run() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    std::fs::write(&edit_script, "fail").unwrap();
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["describe", "-m", "destination"]).success();
+    work_dir.run_jj(["new", "-m", "source"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.write_file("file2", "b\n");
+    work_dir.run_jj(["new", "-m", "child"]).success();
+    work_dir.run_jj(["new"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // Both descriptions are non-empty, so the editor would be opened
+    let output = work_dir.run_jj(["squash", "--dry-run", "-r", "@--"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would squash into commit:
+      qpvuntsm b1700263 destination
+    Would abandon these commits:
+      kkmpptxz 47b03158 source
+    An editor would open to combine descriptions
+    Would rebase 2 descendant commits
+    Dry-run requested, not squashing.
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    // The resulting description is reported
+    let output = work_dir.run_jj(["squash", "--dry-run", "-r", "@--", "-m", "combined"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would squash into commit:
+      qpvuntsm b1700263 destination
+    Would abandon these commits:
+      kkmpptxz 47b03158 source
+    The resulting description would be:
+      combined
+    Would rebase 2 descendant commits
+    Dry-run requested, not squashing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["squash", "--dry-run", "-r", "@--", "-u"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would squash into commit:
+      qpvuntsm b1700263 destination
+    Would abandon these commits:
+      kkmpptxz 47b03158 source
+    The resulting description would be:
+      destination
+    Would rebase 2 descendant commits
+    Dry-run requested, not squashing.
+    [EOF]
+    ");
+
+    // Sources which still have changes are kept
+    let output = work_dir.run_jj(["squash", "--dry-run", "-r", "@--", "-u", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would squash into commit:
+      qpvuntsm b1700263 destination
+    Would keep these commits:
+      kkmpptxz 47b03158 source
+    The resulting description would be:
+      destination
+    Would rebase 3 descendant commits
+    Dry-run requested, not squashing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["squash", "--dry-run", "-r", "@--", "-u", "-k"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would squash into commit:
+      qpvuntsm b1700263 destination
+    Would keep these commits:
+      kkmpptxz 47b03158 source
+    The resulting description would be:
+      destination
+    Would rebase 3 descendant commits
+    Dry-run requested, not squashing.
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    // Cannot be combined with interactive selection
+    let output = work_dir.run_jj(["squash", "--dry-run", "-i"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--dry-run' cannot be used with '--interactive'
+
+    Usage: jj squash --dry-run [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_squash_description_editor_avoids_unc() {
     let mut test_env = TestEnvironment::default();
@@ -1507,14 +2064,161 @@ fn test_squash_empty() {
     [EOF]
     ");
 
+    // Only the descriptions are combined
     work_dir.run_jj(["describe", "-m", "child"]).success();
-    work_dir.run_jj(["squash"]).success();
+    let output = work_dir.run_jj(["squash"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No file changes to move; only combining descriptions
+    Working copy  (@) now at: royxmykx ee24c69e (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 85357f80 (empty) parent
+    [EOF]
+    ");
     insta::assert_snapshot!(get_description(&work_dir, "@-"), @r"
     parent
 
     child
     [EOF]
     ");
+
+    // No warning if the source description is discarded
+    work_dir.run_jj(["describe", "-m", "child"]).success();
+    let output = work_dir.run_jj(["squash", "--use-destination-message"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: yostqsxw 66662395 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 01b0e8d6 (empty) parent
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_squash_warns_bookmarks_collapsed() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "a"])
+        .success();
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "b"])
+        .success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "c"])
+        .success();
+    work_dir.write_file("file1", "c\n");
+
+    // Squashing into a commit with a bookmark of its own warns
+    let output = work_dir.run_jj(["squash", "--from=b", "--into=a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: mzvwutvl 11a35ed1 c | (no description set)
+    Parent commit (@-)      : qpvuntsm 902a3a2d a b | (no description set)
+    Warning: Bookmarks a and b now point to the same commit
+    [EOF]
+    ");
+
+    // The warning is suppressed by --quiet
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["squash", "--from=b", "--into=a", "--quiet"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Bookmarks that already pointed to the same commit aren't reported again
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file2", "d\n");
+    let output = work_dir.run_jj(["squash", "--into=a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: kpqxywon d9ec4419 (empty) (no description set)
+    Parent commit (@-)      : mzvwutvl e4096679 c | (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_squash_insert_before() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "a", &[], &[("file1", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["a"], &[("file2", "b\n")]);
+    create_commit_with_files(&work_dir, "c", &["b"], &[("file3", "c\n")]);
+    // Test the setup
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  0a5b222f50eb c c
+    ○  cb3fcb5d52e9 b b
+    ○  76920e024bce a a
+    ◆  000000000000 (empty)
+    [EOF]
+    ");
+
+    // Squash the working copy into a new commit before b
+    let output = work_dir.run_jj(["squash", "--insert-before=b", "-m", "new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: wmwvqwsz faf944f9 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 863542cc b c | b
+    Warning: Bookmarks b and c now point to the same commit
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output_with_description(&work_dir), @r"
+    @  faf944f92f85
+    ○  863542ccb594 b
+    ○  00c835feef09 new
+    ○  76920e024bce a
+    ◆  000000000000
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list", "-r=description(new)"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file3
+    [EOF]
+    ");
+
+    // The source can be the revision to insert before
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["squash", "--from=b", "-B=b", "-m", "new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: royxmykx 76af77d7 c | c
+    Parent commit (@-)      : wqnwkozp 25d8102e b | new
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output_with_description(&work_dir), @r"
+    @  76af77d75b29 c
+    ○  25d8102e7122 new
+    ○  76920e024bce a
+    ◆  000000000000
+    [EOF]
+    ");
+
+    // Cannot insert before an immutable revision
+    work_dir.run_jj(["undo"]).success();
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "a""#);
+    let output = work_dir.run_jj(["squash", "-B=a"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Commit 76920e024bce is immutable
+    Hint: Could not modify commit: rlvkpnrz 76920e02 a | a
+    Hint: Immutable commits are used to protect shared history.
+    Hint: For more information, see:
+          - https://jj-vcs.github.io/jj/latest/config/#set-of-immutable-commits
+          - `jj help -k config`, "Set of immutable commits"
+    Hint: This operation would rewrite 1 immutable commits.
+    [EOF]
+    [exit status: 1]
+    "#);
 }
 
 #[test]
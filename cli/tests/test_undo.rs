@@ -112,7 +112,7 @@ fn test_git_push_undo() {
     //    remote-tracking  | AA      |   AA   | AA
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
     let pre_push_opid = work_dir.current_operation_id();
@@ -125,7 +125,7 @@ fn test_git_push_undo() {
     //    remote-tracking  | BB      |   BB   | BB
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
     [EOF]
     ");
 
@@ -139,7 +139,7 @@ fn test_git_push_undo() {
     //    remote-tracking  | AA      |   AA   | BB
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
     test_env.advance_test_rng_seed_to_multiple_of(100_000);
@@ -158,7 +158,7 @@ fn test_git_push_undo() {
       - qpvuntsm hidden 3a44d6c5 (empty) AA
       + qpvuntsm?? 1e742089 (empty) CC
       + qpvuntsm?? d9a9f6a0 (empty) BB
-      @origin (behind by 1 commits): qpvuntsm?? d9a9f6a0 (empty) BB
+      @origin [tracked] (behind by 1 commits): qpvuntsm?? d9a9f6a0 (empty) BB
     [EOF]
     ");
 }
@@ -193,7 +193,7 @@ fn test_git_push_undo_with_import() {
     //    remote-tracking  | AA      |   AA   | AA
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
     let pre_push_opid = work_dir.current_operation_id();
@@ -206,7 +206,7 @@ fn test_git_push_undo_with_import() {
     //    remote-tracking  | BB      |   BB   | BB
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
     [EOF]
     ");
 
@@ -220,7 +220,7 @@ fn test_git_push_undo_with_import() {
     //    remote-tracking  | AA      |   AA   | BB
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
 
@@ -236,7 +236,7 @@ fn test_git_push_undo_with_import() {
     //    remote-tracking  | BB      |   BB   | BB
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
     [EOF]
     ");
     test_env.advance_test_rng_seed_to_multiple_of(100_000);
@@ -246,7 +246,7 @@ fn test_git_push_undo_with_import() {
     // was essentially a no-op.
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm 1e742089 (empty) CC
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden d9a9f6a0 (empty) BB
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden d9a9f6a0 (empty) BB
     [EOF]
     ");
 }
@@ -281,8 +281,8 @@ fn test_git_push_undo_colocated() {
     //    remote-tracking  | AA      |   AA   | AA
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @git: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @git [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
     let pre_push_opid = work_dir.current_operation_id();
@@ -295,8 +295,8 @@ fn test_git_push_undo_colocated() {
     //    remote-tracking  | BB      |   BB   | BB
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @git: qpvuntsm d9a9f6a0 (empty) BB
-      @origin: qpvuntsm d9a9f6a0 (empty) BB
+      @git [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
     [EOF]
     ");
 
@@ -318,8 +318,8 @@ fn test_git_push_undo_colocated() {
     //    remote-tracking  | AA      |   AA   | AA
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @git: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @git [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
     test_env.advance_test_rng_seed_to_multiple_of(100_000);
@@ -332,8 +332,8 @@ fn test_git_push_undo_colocated() {
       - qpvuntsm hidden 3a44d6c5 (empty) AA
       + qpvuntsm?? 1e742089 (empty) CC
       + qpvuntsm?? d9a9f6a0 (empty) BB
-      @git (behind by 1 commits): qpvuntsm?? 1e742089 (empty) CC
-      @origin (behind by 1 commits): qpvuntsm?? d9a9f6a0 (empty) BB
+      @git [tracked] (behind by 1 commits): qpvuntsm?? 1e742089 (empty) CC
+      @origin [tracked] (behind by 1 commits): qpvuntsm?? d9a9f6a0 (empty) BB
     [EOF]
     ");
 }
@@ -360,14 +360,14 @@ fn test_git_push_undo_repo_only() {
     work_dir.run_jj(["git", "push", "--allow-new"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm 3a44d6c5 (empty) AA
-      @origin: qpvuntsm 3a44d6c5 (empty) AA
+      @origin [tracked, synced]: qpvuntsm 3a44d6c5 (empty) AA
     [EOF]
     ");
     test_env.advance_test_rng_seed_to_multiple_of(100_000);
     work_dir.run_jj(["describe", "-m", "BB"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 3a44d6c5 (empty) AA
     [EOF]
     ");
     let pre_push_opid = work_dir.current_operation_id();
@@ -379,7 +379,7 @@ fn test_git_push_undo_repo_only() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm d9a9f6a0 (empty) BB
-      @origin: qpvuntsm d9a9f6a0 (empty) BB
+      @origin [tracked, synced]: qpvuntsm d9a9f6a0 (empty) BB
     [EOF]
     ");
     test_env.advance_test_rng_seed_to_multiple_of(100_000);
@@ -388,7 +388,46 @@ fn test_git_push_undo_repo_only() {
     // This currently gives an identical result to `test_git_push_undo_import`.
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     main: qpvuntsm 1e742089 (empty) CC
-      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden d9a9f6a0 (empty) BB
+      @origin [tracked] (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden d9a9f6a0 (empty) BB
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_push_undo_remote_tracking_only() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "none()""#);
+    let git_repo_path = test_env.env_root().join("git-repo");
+    git::init_bare(git_repo_path);
+    test_env
+        .run_jj_in(".", ["git", "clone", "git-repo", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    work_dir.run_jj(["describe", "-m", "AA"]).success();
+    work_dir.run_jj(["git", "push", "--allow-new"]).success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main: qpvuntsm 3a44d6c5 (empty) AA
+      @origin [tracked, synced]: qpvuntsm 3a44d6c5 (empty) AA
+    [EOF]
+    ");
+
+    // Undoing the push only for the remote-tracking bookmarks leaves the local
+    // bookmark without its tracked remote bookmark
+    let output = work_dir.run_jj(["undo", "--what=remote-tracking"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Undid operation: 088d0882a2dc (2001-02-04 11:51:49) push bookmark main to git remote origin
+    Warning: Local bookmark main no longer has tracked remote bookmark main@origin.
+    Hint: Run `jj git fetch` to update the remote-tracking bookmarks.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main: qpvuntsm 3a44d6c5 (empty) AA
     [EOF]
     ");
 }
@@ -414,9 +453,9 @@ fn test_bookmark_track_untrack_undo() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-      @origin: qpvuntsm bab5b5ef (empty) commit
+      @origin [tracked, synced]: qpvuntsm bab5b5ef (empty) commit
     feature2 (deleted)
-      @origin: qpvuntsm bab5b5ef (empty) commit
+      @origin [tracked]: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
@@ -426,25 +465,25 @@ fn test_bookmark_track_untrack_undo() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-    feature1@origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+    feature1@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
+    feature2@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
     work_dir.run_jj(["undo"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-      @origin: qpvuntsm bab5b5ef (empty) commit
+      @origin [tracked, synced]: qpvuntsm bab5b5ef (empty) commit
     feature2 (deleted)
-      @origin: qpvuntsm bab5b5ef (empty) commit
+      @origin [tracked]: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
     work_dir.run_jj(["undo"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-    feature1@origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+    feature1@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
+    feature2@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
@@ -453,16 +492,16 @@ fn test_bookmark_track_untrack_undo() {
         .success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-      @origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+      @origin [tracked, synced]: qpvuntsm bab5b5ef (empty) commit
+    feature2@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 
     work_dir.run_jj(["undo"]).success();
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
     feature1: qpvuntsm bab5b5ef (empty) commit
-    feature1@origin: qpvuntsm bab5b5ef (empty) commit
-    feature2@origin: qpvuntsm bab5b5ef (empty) commit
+    feature1@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
+    feature2@origin [untracked]: qpvuntsm bab5b5ef (empty) commit
     [EOF]
     ");
 }
@@ -1048,6 +1048,39 @@ fn test_workspaces_forget() {
     insta::assert_snapshot!(output, @"");
 }
 
+#[test]
+fn test_workspaces_working_copies_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let template = r#"separate(" ", commit_id.short(), working_copies) ++ "\n""#;
+
+    // A single workspace resolves to just `@`
+    let output = main_dir.run_jj(["log", "--no-graph", "-r=working_copies()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    e8849ae12c70
+    [EOF]
+    ");
+
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "--name=second", "../secondary"])
+        .success();
+    let output = main_dir.run_jj(["log", "--no-graph", "-r=working_copies()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    75b8620c4a30 second@
+    1c1c95df80e5 default@
+    [EOF]
+    ");
+
+    main_dir.run_jj(["workspace", "forget", "second"]).success();
+    let output = main_dir.run_jj(["log", "--no-graph", "-r=working_copies()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    1c1c95df80e5
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_workspaces_forget_multi_transaction() {
     let test_env = TestEnvironment::default();
@@ -1205,6 +1238,44 @@ fn test_list_workspaces_template() {
     ");
 }
 
+/// Test distinguishing the current workspace's working copy from the others
+#[test]
+fn test_workspaces_working_copy_keywords() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir.write_file("file", "contents");
+    main_dir.run_jj(["commit", "-m", "initial"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "--name", "second", "../secondary"])
+        .success();
+
+    let template = r#"
+        separate(" ",
+          description.first_line(),
+          if(current_working_copy, "current", if(any_working_copy, "other")),
+        ) ++ "\n"
+    "#;
+    let output = main_dir.run_jj(["log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    other
+    current
+    initial
+
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    current
+    other
+    initial
+
+    [EOF]
+    ");
+}
+
 /// Test getting the workspace root from primary and secondary workspaces
 #[test]
 fn test_workspaces_root() {
@@ -489,6 +489,8 @@ pub fn import_some_refs(
             target: new_target.clone(),
             state: if old_remote_ref.is_present() {
                 old_remote_ref.state
+            } else if mut_repo.is_remote_bookmark_pending(symbol) {
+                RemoteRefState::Tracked
             } else {
                 default_remote_ref_state_for(GitRefKind::Bookmark, symbol, git_settings)
             },
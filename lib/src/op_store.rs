@@ -16,6 +16,7 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -30,6 +31,7 @@ use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
 use crate::content_hash::ContentHash;
+use crate::content_hash::DigestUpdate;
 use crate::merge::Merge;
 use crate::object_id::id_type;
 use crate::object_id::HexPrefix;
@@ -295,7 +297,7 @@ impl View {
 }
 
 /// Represents the state of the remote repo.
-#[derive(ContentHash, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct RemoteView {
     // TODO: Do we need to support tombstones for remote bookmarks? For example, if the bookmark
     // has been deleted locally and you pull from a remote, maybe it should make a difference
@@ -303,6 +305,37 @@ pub struct RemoteView {
     // the bookmark if the bookmark's state on the remote was just not known.
     pub bookmarks: BTreeMap<RefNameBuf, RemoteRef>,
     // TODO: pub tags: BTreeMap<RefNameBuf, RemoteRef>,
+    /// Names of bookmarks which don't exist on the remote yet, but are tracked
+    /// by the local bookmarks. They will be created by the next push.
+    ///
+    /// These can't be recorded in `bookmarks` because remote refs there mirror
+    /// the last-known state of the remote, where absent refs are removed.
+    /// Importing from Git would also overwrite the tracking state of a remote
+    /// ref that reappears. A name is removed from this set once the remote
+    /// bookmark is present, or when the local bookmark or the tracking is
+    /// removed.
+    ///
+    /// The tracking intent is stored in the view, not in the config, so it is
+    /// recorded by the operation which created the local bookmark. It's shown
+    /// by `jj op diff`, reverted by `jj op undo`, and reconciled together with
+    /// the local bookmark when concurrent operations are merged.
+    pub pending_bookmarks: BTreeSet<RefNameBuf>,
+}
+
+impl ContentHash for RemoteView {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        let RemoteView {
+            bookmarks,
+            pending_bookmarks,
+        } = self;
+        bookmarks.hash(state);
+        // Not hashed if empty so the ids of views without pending bookmarks
+        // stay the same as the ones written before this field was added. See
+        // test_hash_view() in simple_op_store.
+        if !pending_bookmarks.is_empty() {
+            pending_bookmarks.hash(state);
+        }
+    }
 }
 
 /// Iterates pair of local and remote bookmarks by bookmark name.
@@ -448,6 +481,15 @@ pub struct RootOperationData {
     pub root_commit_id: CommitId,
 }
 
+/// Number and total size of operations and views in the operation store.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OpStoreObjectStats {
+    pub operation_count: usize,
+    pub view_count: usize,
+    /// Total size in bytes of the operation and view objects.
+    pub total_bytes: u64,
+}
+
 #[derive(Debug, Error)]
 pub enum OpStoreError {
     #[error("Object {hash} of type {object_type} not found")]
@@ -502,6 +544,25 @@ pub trait OpStore: Send + Sync + Debug {
     /// concurrently by another process.
     // TODO: return stats?
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()>;
+
+    /// Counts operations and views not reachable from the `head_ids`.
+    ///
+    /// These are the objects which would be removed by `gc()` if they were
+    /// old enough. Computing the sizes may be expensive. Returns an error by
+    /// default if the backend doesn't support this.
+    fn unreachable_object_stats(
+        &self,
+        head_ids: &[OperationId],
+    ) -> OpStoreResult<OpStoreObjectStats> {
+        let _ = head_ids;
+        Err(OpStoreError::Other(
+            format!(
+                "Counting unreachable objects is not supported by the {} operation store",
+                self.name()
+            )
+            .into(),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -535,16 +596,19 @@ mod tests {
                     "bookmark1".into() => git_bookmark1_remote_ref.clone(),
                     "bookmark2".into() => git_bookmark2_remote_ref.clone(),
                 },
+                ..Default::default()
             },
             "remote1".into() => RemoteView {
                 bookmarks: btreemap! {
                     "bookmark1".into() => remote1_bookmark1_remote_ref.clone(),
                 },
+                ..Default::default()
             },
             "remote2".into() => RemoteView {
                 bookmarks: btreemap! {
                     "bookmark2".into() => remote2_bookmark2_remote_ref.clone(),
                 },
+                ..Default::default()
             },
         };
         assert_eq!(
@@ -596,6 +660,7 @@ mod tests {
                 bookmarks: btreemap! {
                     "bookmark1".into() => remote1_bookmark1_remote_ref.clone(),
                 },
+                ..Default::default()
             },
         };
         assert_eq!(
@@ -141,19 +141,32 @@ fn resolve_single_op(
     get_head_ops: impl FnOnce() -> Result<Vec<Operation>, OpsetEvaluationError>,
     op_str: &str,
 ) -> Result<Operation, OpsetEvaluationError> {
-    let op_symbol = op_str.trim_end_matches(['-', '+']);
-    let op_postfix = &op_str[op_symbol.len()..];
+    let (op_symbol, op_postfix) = split_op_symbol(op_str);
     let head_ops = op_postfix.contains('+').then(get_head_ops).transpose()?;
     let mut operation = match op_symbol {
         "@" => get_current_op(),
         s => resolve_single_op_from_store(op_store, s),
     }?;
-    for (i, c) in op_postfix.chars().enumerate() {
-        let mut neighbor_ops = match c {
-            '-' => operation.parents().try_collect()?,
-            '+' => find_child_ops(head_ops.as_ref().unwrap(), operation.id())?,
-            _ => unreachable!(),
+    let mut postfix = op_postfix;
+    while !postfix.is_empty() {
+        let (mut neighbor_ops, rest) = if let Some(rest) = postfix.strip_prefix("-(") {
+            let (index, rest) = rest.split_once(')').unwrap();
+            let parent = match index.parse::<usize>().ok().and_then(|n| n.checked_sub(1)) {
+                Some(i) => operation.parents().nth(i).transpose()?,
+                None => None,
+            };
+            (parent.into_iter().collect_vec(), rest)
+        } else if let Some(rest) = postfix.strip_prefix('-') {
+            (operation.parents().try_collect()?, rest)
+        } else if let Some(rest) = postfix.strip_prefix('+') {
+            (
+                find_child_ops(head_ops.as_ref().unwrap(), operation.id())?,
+                rest,
+            )
+        } else {
+            unreachable!()
         };
+        postfix = rest;
         operation = match neighbor_ops.len() {
             // Since there is no hint provided for `EmptyOperations` in
             // `opset_resolution_error_hint()` (there would be no useful hint for the
@@ -168,7 +181,7 @@ fn resolve_single_op(
             // Returns the exact subexpression that resolves to multiple operations,
             // rather than the full expression provided by the user.
             _ => Err(OpsetResolutionError::MultipleOperations {
-                expr: op_str[..=op_symbol.len() + i].to_owned(),
+                expr: op_str[..op_str.len() - postfix.len()].to_owned(),
                 candidates: neighbor_ops.iter().map(|op| op.id().clone()).collect(),
             })?,
         };
@@ -176,6 +189,29 @@ fn resolve_single_op(
     Ok(operation)
 }
 
+/// Splits operation set expression into the symbol and the postfix operators.
+///
+/// The postfix consists of `-` (parents), `-(N)` (the N-th parent), and `+`
+/// (children) operators.
+pub fn split_op_symbol(op_str: &str) -> (&str, &str) {
+    let mut op_symbol = op_str;
+    loop {
+        if let Some(s) = op_symbol.strip_suffix(['-', '+']) {
+            op_symbol = s;
+        } else if let Some(s) = op_symbol.strip_suffix(')') {
+            let s_without_index = s.trim_end_matches(|c: char| c.is_ascii_digit());
+            let has_index = s_without_index.len() < s.len();
+            match s_without_index.strip_suffix("-(") {
+                Some(s) if has_index => op_symbol = s,
+                _ => break,
+            }
+        } else {
+            break;
+        }
+    }
+    (op_symbol, &op_str[op_symbol.len()..])
+}
+
 fn resolve_single_op_from_store(
     op_store: &Arc<dyn OpStore>,
     op_str: &str,
@@ -271,6 +307,20 @@ pub fn walk_ancestors(
     .map_ok(|OperationByEndTime(op)| op)
 }
 
+/// Finds the closest common ancestor of `ops1` and `ops2`. If there are
+/// multiple such ancestors, the one that was found first is returned.
+pub fn closest_common_ancestor(
+    ops1: &[Operation],
+    ops2: &[Operation],
+) -> OpStoreResult<Option<Operation>> {
+    dag_walk::closest_common_node_ok(
+        ops1.iter().cloned().map(Ok),
+        ops2.iter().cloned().map(Ok),
+        |op: &Operation| op.id().clone(),
+        |op: &Operation| op.parents().collect_vec(),
+    )
+}
+
 /// Walks ancestors from `head_ops` in reverse topological order, excluding
 /// ancestors of `root_ops`.
 pub fn walk_ancestors_range(
@@ -391,3 +441,54 @@ pub fn reparent_range(
         unreachable_count,
     })
 }
+
+/// Removes the ancestors of the `head_ops` matching the `predicate`. The
+/// children of the removed operations are reparented onto their parents.
+///
+/// The `head_ops` and the root operation are never removed. The
+/// `unreachable_count` of the returned stats is the number of removed
+/// operations.
+pub fn remove_matching(
+    op_store: &dyn OpStore,
+    head_ops: &[Operation],
+    predicate: impl Fn(&Operation) -> bool,
+) -> OpStoreResult<ReparentStats> {
+    let ops: Vec<_> = walk_ancestors(head_ops).try_collect()?;
+    // Operation ids to be used as the parents of the children of each operation
+    let mut new_ids_map: HashMap<OperationId, Vec<OperationId>> = HashMap::new();
+    let mut rewritten_count = 0;
+    let mut unreachable_count = 0;
+    for op in ops.into_iter().rev() {
+        let parent_ids = op
+            .parent_ids()
+            .iter()
+            .flat_map(|id| &new_ids_map[id])
+            .unique()
+            .cloned()
+            .collect_vec();
+        let new_ids = if parent_ids.is_empty() || head_ops.contains(&op) || !predicate(&op) {
+            if parent_ids == op.parent_ids() {
+                vec![op.id().clone()]
+            } else {
+                let mut data = op.store_operation().clone();
+                data.parents = parent_ids;
+                rewritten_count += 1;
+                vec![op_store.write_operation(&data)?]
+            }
+        } else {
+            unreachable_count += 1;
+            parent_ids
+        };
+        new_ids_map.insert(op.id().clone(), new_ids);
+    }
+
+    let new_head_ids = head_ops
+        .iter()
+        .map(|op| new_ids_map[op.id()][0].clone())
+        .collect();
+    Ok(ReparentStats {
+        new_head_ids,
+        rewritten_count,
+        unreachable_count,
+    })
+}
@@ -67,6 +67,13 @@ pub struct Bookmark {
     pub remote_bookmarks: ::prost::alloc::vec::Vec<RemoteBookmark>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingRemoteBookmark {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub remote_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GitRef {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
@@ -112,6 +119,10 @@ pub struct View {
     pub git_head_legacy: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "9")]
     pub git_head: ::core::option::Option<RefTarget>,
+    /// Tracked remote bookmarks which don't exist on the remote yet. Introduced in
+    /// jj 0.31.
+    #[prost(message, repeated, tag = "11")]
+    pub pending_remote_bookmarks: ::prost::alloc::vec::Vec<PendingRemoteBookmark>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Operation {
@@ -25,6 +25,7 @@ use std::path::Path;
 use std::slice;
 use std::sync::Arc;
 
+use itertools::EitherOrBoth;
 use itertools::Itertools as _;
 use once_cell::sync::OnceCell;
 use thiserror::Error;
@@ -1415,18 +1416,33 @@ impl MutableRepo {
     /// The content of those descendants will remain untouched.
     /// Returns the number of reparented descendants.
     pub fn reparent_descendants(&mut self) -> BackendResult<usize> {
-        let roots = self.parent_mapping.keys().cloned().collect_vec();
         let mut num_reparented = 0;
+        self.reparent_descendants_with(|_old_commit, _new_commit| {
+            num_reparented += 1;
+        })?;
+        Ok(num_reparented)
+    }
+
+    /// Reparent descendants of the rewritten commits.
+    ///
+    /// Same as [`MutableRepo::reparent_descendants`], but the `progress`
+    /// callback will be invoked for each reparented commit with
+    /// `(old_commit, new_commit)` as arguments.
+    pub fn reparent_descendants_with(
+        &mut self,
+        mut progress: impl FnMut(Commit, Commit),
+    ) -> BackendResult<()> {
+        let roots = self.parent_mapping.keys().cloned().collect_vec();
         self.transform_descendants(roots, |rewriter| {
             if rewriter.parents_changed() {
-                let builder = rewriter.reparent();
-                builder.write()?;
-                num_reparented += 1;
+                let old_commit = rewriter.old_commit().clone();
+                let new_commit = rewriter.reparent().write()?;
+                progress(old_commit, new_commit);
             }
             Ok(())
         })?;
         self.parent_mapping.clear();
-        Ok(num_reparented)
+        Ok(())
     }
 
     pub fn set_wc_commit(
@@ -1688,11 +1704,24 @@ impl MutableRepo {
         self.set_remote_bookmark(symbol, remote_ref);
     }
 
+    pub fn is_remote_bookmark_pending(&self, symbol: RemoteRefSymbol<'_>) -> bool {
+        self.view.with_ref(|v| v.is_remote_bookmark_pending(symbol))
+    }
+
+    /// Marks the specified remote bookmark, which doesn't exist on the remote
+    /// yet, as pending. The remote bookmark will be created by the next push.
+    ///
+    /// Does nothing if the remote bookmark is present.
+    pub fn set_remote_bookmark_pending(&mut self, symbol: RemoteRefSymbol<'_>, pending: bool) {
+        self.view_mut().set_remote_bookmark_pending(symbol, pending);
+    }
+
     /// Stops tracking the specified remote bookmark.
     pub fn untrack_remote_bookmark(&mut self, symbol: RemoteRefSymbol<'_>) {
         let mut remote_ref = self.get_remote_bookmark(symbol);
         remote_ref.state = RemoteRefState::New;
         self.set_remote_bookmark(symbol, remote_ref);
+        self.set_remote_bookmark_pending(symbol, false);
     }
 
     pub fn remove_remote(&mut self, remote_name: &RemoteName) {
@@ -1827,6 +1856,17 @@ impl MutableRepo {
             self.merge_remote_bookmark(symbol, base_ref, other_ref);
         }
 
+        let changed_pending_bookmarks = base
+            .pending_remote_bookmarks()
+            .merge_join_by(other.pending_remote_bookmarks(), |a, b| a.cmp(b));
+        for entry in changed_pending_bookmarks {
+            match entry {
+                EitherOrBoth::Left(symbol) => self.set_remote_bookmark_pending(symbol, false),
+                EitherOrBoth::Right(symbol) => self.set_remote_bookmark_pending(symbol, true),
+                EitherOrBoth::Both(..) => {}
+            }
+        }
+
         let new_git_head_target = merge_ref_targets(
             self.index(),
             self.view().git_head(),
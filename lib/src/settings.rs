@@ -51,6 +51,7 @@ struct UserSettingsData {
     user_email: String,
     commit_timestamp: Option<Timestamp>,
     operation_timestamp: Option<Timestamp>,
+    current_timestamp: Option<Timestamp>,
     operation_hostname: String,
     operation_username: String,
     signing_behavior: SignBehavior,
@@ -144,6 +145,9 @@ impl UserSettings {
         let operation_timestamp = config
             .get_value_with("debug.operation-timestamp", to_timestamp)
             .optional()?;
+        let current_timestamp = config
+            .get_value_with("debug.current-timestamp", to_timestamp)
+            .optional()?;
         let operation_hostname = config.get("operation.hostname")?;
         let operation_username = config.get("operation.username")?;
         let signing_behavior = config.get("signing.behavior")?;
@@ -153,6 +157,7 @@ impl UserSettings {
             user_email,
             commit_timestamp,
             operation_timestamp,
+            current_timestamp,
             operation_hostname,
             operation_username,
             signing_behavior,
@@ -204,6 +209,11 @@ impl UserSettings {
         self.data.operation_timestamp
     }
 
+    /// Fixed time to be used as "now" when formatting relative timestamps.
+    pub fn current_timestamp(&self) -> Option<Timestamp> {
+        self.data.current_timestamp
+    }
+
     pub fn operation_hostname(&self) -> &str {
         &self.data.operation_hostname
     }
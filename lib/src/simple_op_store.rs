@@ -48,6 +48,7 @@ use crate::object_id::PrefixResolution;
 use crate::op_store;
 use crate::op_store::OpStore;
 use crate::op_store::OpStoreError;
+use crate::op_store::OpStoreObjectStats;
 use crate::op_store::OpStoreResult;
 use crate::op_store::Operation;
 use crate::op_store::OperationId;
@@ -132,6 +133,25 @@ impl SimpleOpStore {
     fn operations_dir(&self) -> PathBuf {
         self.path.join("operations")
     }
+
+    /// Collects operations and views reachable from the `head_ids`.
+    fn collect_reachable_ids(
+        &self,
+        head_ids: &[OperationId],
+    ) -> OpStoreResult<(HashSet<OperationId>, HashSet<ViewId>)> {
+        let read_op = |id: &OperationId| self.read_operation(id).map(|data| (id.clone(), data));
+        let reachable_ops: HashMap<OperationId, Operation> = dag_walk::dfs_ok(
+            head_ids.iter().map(read_op),
+            |(id, _)| id.clone(),
+            |(_, data)| data.parents.iter().map(read_op).collect_vec(),
+        )
+        .try_collect()?;
+        let reachable_views = reachable_ops
+            .values()
+            .map(|data| data.view_id.clone())
+            .collect();
+        Ok((reachable_ops.into_keys().collect(), reachable_views))
+    }
 }
 
 impl OpStore for SimpleOpStore {
@@ -276,14 +296,6 @@ impl OpStore for SimpleOpStore {
 
     #[tracing::instrument(skip(self))]
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()> {
-        let to_op_id = |entry: &fs::DirEntry| -> Option<OperationId> {
-            let name = entry.file_name().into_string().ok()?;
-            OperationId::try_from_hex(&name).ok()
-        };
-        let to_view_id = |entry: &fs::DirEntry| -> Option<ViewId> {
-            let name = entry.file_name().into_string().ok()?;
-            ViewId::try_from_hex(&name).ok()
-        };
         let remove_file_if_not_new = |entry: &fs::DirEntry| -> Result<(), PathError> {
             let path = entry.path();
             // Check timestamp, but there's still TOCTOU problem if an existing
@@ -302,15 +314,7 @@ impl OpStore for SimpleOpStore {
         // Reachable objects are resolved without considering the keep_newer
         // parameter. We could collect ancestors of the "new" operations here,
         // but more files can be added anyway after that.
-        let read_op = |id: &OperationId| self.read_operation(id).map(|data| (id.clone(), data));
-        let reachable_ops: HashMap<OperationId, Operation> = dag_walk::dfs_ok(
-            head_ids.iter().map(read_op),
-            |(id, _)| id.clone(),
-            |(_, data)| data.parents.iter().map(read_op).collect_vec(),
-        )
-        .try_collect()?;
-        let reachable_views: HashSet<&ViewId> =
-            reachable_ops.values().map(|data| &data.view_id).collect();
+        let (reachable_ops, reachable_views) = self.collect_reachable_ids(head_ids)?;
         tracing::info!(
             reachable_op_count = reachable_ops.len(),
             reachable_view_count = reachable_views.len(),
@@ -325,7 +329,7 @@ impl OpStore for SimpleOpStore {
                     tracing::trace!(?entry, "skipping invalid file name");
                     continue;
                 };
-                if reachable_ops.contains_key(&id) {
+                if reachable_ops.contains(&id) {
                     continue;
                 }
                 // If the operation was added after collecting reachable_views,
@@ -356,6 +360,51 @@ impl OpStore for SimpleOpStore {
 
         Ok(())
     }
+
+    fn unreachable_object_stats(
+        &self,
+        head_ids: &[OperationId],
+    ) -> OpStoreResult<OpStoreObjectStats> {
+        let (reachable_ops, reachable_views) = self.collect_reachable_ids(head_ids)?;
+        let mut stats = OpStoreObjectStats::default();
+        let mut count_files = || -> Result<(), PathError> {
+            let op_dir = self.operations_dir();
+            for entry in op_dir.read_dir().context(&op_dir)? {
+                let entry = entry.context(&op_dir)?;
+                let Some(id) = to_op_id(&entry) else {
+                    continue;
+                };
+                if !reachable_ops.contains(&id) {
+                    stats.operation_count += 1;
+                    stats.total_bytes += entry.metadata().context(entry.path())?.len();
+                }
+            }
+            let view_dir = self.views_dir();
+            for entry in view_dir.read_dir().context(&view_dir)? {
+                let entry = entry.context(&view_dir)?;
+                let Some(id) = to_view_id(&entry) else {
+                    continue;
+                };
+                if !reachable_views.contains(&id) {
+                    stats.view_count += 1;
+                    stats.total_bytes += entry.metadata().context(entry.path())?.len();
+                }
+            }
+            Ok(())
+        };
+        count_files().map_err(|err| OpStoreError::Other(err.into()))?;
+        Ok(stats)
+    }
+}
+
+fn to_op_id(entry: &fs::DirEntry) -> Option<OperationId> {
+    let name = entry.file_name().into_string().ok()?;
+    OperationId::try_from_hex(&name).ok()
+}
+
+fn to_view_id(entry: &fs::DirEntry) -> Option<ViewId> {
+    let name = entry.file_name().into_string().ok()?;
+    ViewId::try_from_hex(&name).ok()
 }
 
 fn io_to_read_error(err: PathError, id: &impl ObjectId) -> OpStoreError {
@@ -544,6 +593,16 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
     }
 
     proto.bookmarks = bookmark_views_to_proto_legacy(&view.local_bookmarks, &view.remote_views);
+    for (remote_name, remote_view) in &view.remote_views {
+        for name in &remote_view.pending_bookmarks {
+            proto
+                .pending_remote_bookmarks
+                .push(crate::protos::op_store::PendingRemoteBookmark {
+                    name: name.into(),
+                    remote_name: remote_name.into(),
+                });
+        }
+    }
 
     for (name, target) in &view.tags {
         proto.tags.push(crate::protos::op_store::Tag {
@@ -588,6 +647,15 @@ fn view_from_proto(proto: crate::protos::op_store::View) -> View {
     let (local_bookmarks, remote_views) = bookmark_views_from_proto_legacy(proto.bookmarks);
     view.local_bookmarks = local_bookmarks;
     view.remote_views = remote_views;
+    for pending_proto in proto.pending_remote_bookmarks {
+        let remote_view = view
+            .remote_views
+            .entry(pending_proto.remote_name.into())
+            .or_default();
+        remote_view
+            .pending_bookmarks
+            .insert(pending_proto.name.into());
+    }
 
     for tag_proto in proto.tags {
         let name: RefNameBuf = tag_proto.name.into();
@@ -788,10 +856,12 @@ mod tests {
     use insta::assert_snapshot;
     use itertools::Itertools as _;
     use maplit::btreemap;
+    use maplit::btreeset;
     use maplit::hashmap;
     use maplit::hashset;
 
     use super::*;
+    use crate::ref_name::RemoteName;
     use crate::tests::new_temp_dir;
 
     fn create_view() -> View {
@@ -830,6 +900,7 @@ mod tests {
                         "main".into() => tracked_remote_ref(&bookmark_main_origin_target),
                         "deleted".into() => new_remote_ref(&bookmark_deleted_origin_target),
                     },
+                    pending_bookmarks: btreeset! {},
                 },
             },
             git_refs: btreemap! {
@@ -917,6 +988,32 @@ mod tests {
         assert_eq!(read_view, view);
     }
 
+    #[test]
+    fn test_read_write_view_with_pending_remote_bookmarks() {
+        let temp_dir = new_temp_dir();
+        let root_data = RootOperationData {
+            root_commit_id: CommitId::from_hex("000000"),
+        };
+        let store = SimpleOpStore::init(temp_dir.path(), root_data).unwrap();
+        let mut view = create_view();
+        let origin_view = view
+            .remote_views
+            .get_mut(RemoteName::new("origin"))
+            .unwrap();
+        origin_view.pending_bookmarks.insert("feature".into());
+        view.remote_views.insert(
+            "upstream".into(),
+            RemoteView {
+                bookmarks: btreemap! {},
+                pending_bookmarks: btreeset! {"feature".into(), "main".into()},
+            },
+        );
+        let view_id = store.write_view(&view).unwrap();
+        assert_ne!(view_id, ViewId::new(blake2b_hash(&create_view()).to_vec()));
+        let read_view = store.read_view(&view_id).unwrap();
+        assert_eq!(read_view, view);
+    }
+
     #[test]
     fn test_read_write_operation() {
         let temp_dir = new_temp_dir();
@@ -955,11 +1052,13 @@ mod tests {
                 bookmarks: btreemap! {
                     "bookmark1".into() => tracked_remote_ref(&git_bookmark1_target),
                 },
+                ..Default::default()
             },
             "remote1".into() => RemoteView {
                 bookmarks: btreemap! {
                     "bookmark1".into() => tracked_remote_ref(&remote1_bookmark1_target),
                 },
+                ..Default::default()
             },
             "remote2".into() => RemoteView {
                 bookmarks: btreemap! {
@@ -967,6 +1066,7 @@ mod tests {
                     "bookmark2".into() => new_remote_ref(&remote2_bookmark2_target),
                     "bookmark4".into() => tracked_remote_ref(&remote2_bookmark4_target),
                 },
+                ..Default::default()
             },
         };
 
@@ -20,6 +20,7 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Deref;
+use std::ops::Range;
 
 use either::Either;
 use thiserror::Error;
@@ -60,6 +61,16 @@ fn parse_glob(src: &str) -> Result<GlobPattern, StringPatternParseError> {
         .map_err(StringPatternParseError::GlobPattern)
 }
 
+fn substring_ranges(haystack: &str, needle: &str) -> Vec<Range<usize>> {
+    if needle.is_empty() {
+        return vec![];
+    }
+    haystack
+        .match_indices(needle)
+        .map(|(start, matched)| start..start + matched.len())
+        .collect()
+}
+
 /// Pattern to be tested against string property like commit description or
 /// bookmark name.
 #[derive(Clone, Debug)]
@@ -102,6 +113,18 @@ impl StringPattern {
         }
     }
 
+    /// Parses the given string as a [`StringPattern`] in the same way as
+    /// [`StringPattern::parse()`], except that a `StringPattern::Substring` is
+    /// returned if the string has no kind prefix. This is suitable for
+    /// searching text.
+    pub fn parse_default_substring(src: &str) -> Result<StringPattern, StringPatternParseError> {
+        if let Some((kind, pat)) = src.split_once(':') {
+            StringPattern::from_str_kind(pat, kind)
+        } else {
+            Ok(StringPattern::substring(src))
+        }
+    }
+
     /// Constructs a pattern that matches exactly.
     pub fn exact(src: impl Into<String>) -> Self {
         StringPattern::Exact(src.into())
@@ -221,6 +244,38 @@ impl StringPattern {
         }
     }
 
+    /// Returns the byte ranges of the parts of `haystack` matching this
+    /// pattern.
+    ///
+    /// Substring and regex patterns can match anywhere in the `haystack`.
+    /// Exact and glob patterns are tested against each whitespace-separated
+    /// word, and match whole words.
+    pub fn match_ranges(&self, haystack: &str) -> Vec<Range<usize>> {
+        match self {
+            StringPattern::Substring(needle) => substring_ranges(haystack, needle),
+            StringPattern::SubstringI(needle) => {
+                substring_ranges(&haystack.to_ascii_lowercase(), &needle.to_ascii_lowercase())
+            }
+            StringPattern::Regex(pattern) | StringPattern::RegexI(pattern) => pattern
+                .find_iter(haystack)
+                .map(|m| m.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+            StringPattern::Exact(_)
+            | StringPattern::ExactI(_)
+            | StringPattern::Glob(_)
+            | StringPattern::GlobI(_) => haystack
+                .split_whitespace()
+                .filter(|word| self.matches(word))
+                .map(|word| {
+                    // word is a subslice of haystack
+                    let start = word.as_ptr() as usize - haystack.as_ptr() as usize;
+                    start..start + word.len()
+                })
+                .collect(),
+        }
+    }
+
     /// Returns true if this pattern matches the `haystack`.
     ///
     /// When matching against a case‐insensitive pattern, only ASCII case
@@ -349,6 +404,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_string_pattern_match_ranges() {
+        let find = |pattern: StringPattern, haystack: &str| pattern.match_ranges(haystack);
+        assert_eq!(find(StringPattern::exact("a.b"), "a.b"), vec![0..3]);
+        assert_eq!(find(StringPattern::exact("a.b"), "x a.b a.bc"), vec![2..5]);
+        assert!(find(StringPattern::exact("a.b"), "axb").is_empty());
+        assert!(find(StringPattern::exact("a"), "aa").is_empty());
+        assert_eq!(find(StringPattern::exact_i("a"), "b A"), vec![2..3]);
+        assert_eq!(
+            find(StringPattern::substring("a+"), "a+a+"),
+            vec![0..2, 2..4]
+        );
+        assert_eq!(
+            find(StringPattern::substring_i("ab"), "xAbaB"),
+            vec![1..3, 3..5]
+        );
+        assert!(find(StringPattern::everything(), "abc").is_empty());
+        assert_eq!(
+            find(StringPattern::glob("a*").unwrap(), "abc ba a\nc"),
+            vec![0..3, 7..8]
+        );
+        assert_eq!(
+            find(StringPattern::glob("[]a]x").unwrap(), "]x"),
+            vec![0..2]
+        );
+        assert!(find(StringPattern::glob("[!a-c]").unwrap(), "b").is_empty());
+        assert_eq!(
+            find(StringPattern::glob_i("A*").unwrap(), "abc"),
+            vec![0..3]
+        );
+        assert_eq!(
+            find(StringPattern::regex("a+").unwrap(), "baab"),
+            vec![1..3]
+        );
+        assert!(find(StringPattern::regex("x*").unwrap(), "ab").is_empty());
+        assert_eq!(
+            find(StringPattern::regex_i("a+").unwrap(), "bAab"),
+            vec![1..3]
+        );
+    }
+
     #[test]
     fn test_parse() {
         // Parse specific pattern kinds.
@@ -419,4 +515,20 @@ mod tests {
             Err(StringPatternParseError::InvalidKind(_))
         );
     }
+
+    #[test]
+    fn test_parse_default_substring() {
+        assert_matches!(
+            StringPattern::parse_default_substring("foo"),
+            Ok(StringPattern::Substring(s)) if s == "foo"
+        );
+        assert_matches!(
+            StringPattern::parse_default_substring("exact:foo:bar"),
+            Ok(StringPattern::Exact(s)) if s == "foo:bar"
+        );
+        assert_matches!(
+            StringPattern::parse_default_substring("unknown-prefix:foo"),
+            Err(StringPatternParseError::InvalidKind(_))
+        );
+    }
 }
@@ -14,13 +14,12 @@
 
 #![allow(missing_docs)]
 
+use std::slice;
 use std::sync::Arc;
 
-use itertools::Itertools as _;
 use thiserror::Error;
 
 use crate::backend::Timestamp;
-use crate::dag_walk;
 use crate::index::IndexWriteError;
 use crate::index::ReadonlyIndex;
 use crate::op_heads_store::OpHeadsStore;
@@ -29,6 +28,7 @@ use crate::op_store;
 use crate::op_store::OpStoreError;
 use crate::op_store::OperationMetadata;
 use crate::op_store::TimestampRange;
+use crate::op_walk;
 use crate::operation::Operation;
 use crate::repo::MutableRepo;
 use crate::repo::ReadonlyRepo;
@@ -95,13 +95,9 @@ impl Transaction {
     }
 
     pub fn merge_operation(&mut self, other_op: Operation) -> Result<(), RepoLoaderError> {
-        let ancestor_op = dag_walk::closest_common_node_ok(
-            self.parent_ops.iter().cloned().map(Ok),
-            [Ok(other_op.clone())],
-            |op: &Operation| op.id().clone(),
-            |op: &Operation| op.parents().collect_vec(),
-        )?
-        .unwrap();
+        let ancestor_op =
+            op_walk::closest_common_ancestor(&self.parent_ops, slice::from_ref(&other_op))?
+                .unwrap();
         let repo_loader = self.base_repo().loader();
         let base_repo = repo_loader.load_at(&ancestor_op)?;
         let other_repo = repo_loader.load_at(&other_op)?;
@@ -164,12 +164,16 @@ impl View {
 
     /// Sets local bookmark to point to the given target. If the target is
     /// absent, and if no associated remote bookmarks exist, the bookmark
-    /// will be removed.
+    /// will be removed. Pending remote bookmarks of the same name are removed
+    /// as well since there's nothing left to push.
     pub fn set_local_bookmark_target(&mut self, name: &RefName, target: RefTarget) {
         if target.is_present() {
             self.data.local_bookmarks.insert(name.to_owned(), target);
         } else {
             self.data.local_bookmarks.remove(name);
+            for remote_view in self.data.remote_views.values_mut() {
+                remote_view.pending_bookmarks.remove(name);
+            }
         }
     }
 
@@ -226,7 +230,8 @@ impl View {
     }
 
     /// Sets remote-tracking bookmark to the given target and state. If the
-    /// target is absent, the bookmark will be removed.
+    /// target is absent, the bookmark will be removed. If the target is
+    /// present, the bookmark is no longer pending.
     pub fn set_remote_bookmark(&mut self, symbol: RemoteRefSymbol<'_>, remote_ref: RemoteRef) {
         if remote_ref.is_present() {
             let remote_view = self
@@ -234,6 +239,7 @@ impl View {
                 .remote_views
                 .entry(symbol.remote.to_owned())
                 .or_default();
+            remote_view.pending_bookmarks.remove(symbol.name);
             remote_view
                 .bookmarks
                 .insert(symbol.name.to_owned(), remote_ref);
@@ -242,6 +248,50 @@ impl View {
         }
     }
 
+    /// Iterates over symbols of all pending remote bookmarks in
+    /// lexicographical order.
+    ///
+    /// A pending remote bookmark doesn't exist on the remote yet, but is
+    /// tracked by the local bookmark of the same name. It will be created by
+    /// the next push.
+    pub fn pending_remote_bookmarks(&self) -> impl Iterator<Item = RemoteRefSymbol<'_>> {
+        self.data
+            .remote_views
+            .iter()
+            .map(|(remote, remote_view)| {
+                remote_view
+                    .pending_bookmarks
+                    .iter()
+                    .map(|name| name.to_remote_symbol(remote))
+            })
+            .kmerge()
+    }
+
+    pub fn is_remote_bookmark_pending(&self, symbol: RemoteRefSymbol<'_>) -> bool {
+        self.data
+            .remote_views
+            .get(symbol.remote)
+            .is_some_and(|remote_view| remote_view.pending_bookmarks.contains(symbol.name))
+    }
+
+    /// Marks or unmarks the specified remote bookmark as pending. Does nothing
+    /// if the remote bookmark is present.
+    pub fn set_remote_bookmark_pending(&mut self, symbol: RemoteRefSymbol<'_>, pending: bool) {
+        if pending {
+            if self.get_remote_bookmark(symbol).is_present() {
+                return;
+            }
+            let remote_view = self
+                .data
+                .remote_views
+                .entry(symbol.remote.to_owned())
+                .or_default();
+            remote_view.pending_bookmarks.insert(symbol.name.to_owned());
+        } else if let Some(remote_view) = self.data.remote_views.get_mut(symbol.remote) {
+            remote_view.pending_bookmarks.remove(symbol.name);
+        }
+    }
+
     /// Iterates over `(name, {local_ref, remote_ref})`s for every bookmark
     /// present locally and/or on the specified remote, in lexicographical
     /// order.
@@ -387,7 +437,10 @@ impl View {
             local_bookmarks.values().flat_map(ref_target_ids),
             tags.values().flat_map(ref_target_ids),
             remote_views.values().flat_map(|remote_view| {
-                let op_store::RemoteView { bookmarks } = remote_view;
+                let op_store::RemoteView {
+                    bookmarks,
+                    pending_bookmarks: _,
+                } = remote_view;
                 bookmarks
                     .values()
                     .flat_map(|remote_ref| ref_target_ids(&remote_ref.target))
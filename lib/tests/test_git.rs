@@ -893,6 +893,51 @@ fn test_import_refs_reimport_with_moved_untracked_remote_ref() {
     );
 }
 
+#[test]
+fn test_import_refs_reimport_with_pending_remote_ref() {
+    let git_settings = GitSettings {
+        auto_local_bookmark: false,
+        ..Default::default()
+    };
+    let test_workspace = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_workspace.repo;
+    let git_repo = get_git_repo(repo);
+
+    // Mark the remote bookmark which doesn't exist yet as pending. It shouldn't
+    // be unmarked by import.
+    let mut tx = repo.start_transaction();
+    tx.repo_mut()
+        .set_remote_bookmark_pending(remote_symbol("feature", "origin"), true);
+    git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    let repo = tx.commit("test").unwrap();
+    let view = repo.view();
+    assert_eq!(view.local_bookmarks().count(), 0);
+    assert!(view
+        .get_remote_bookmark(remote_symbol("feature", "origin"))
+        .is_absent());
+    assert!(view.is_remote_bookmark_pending(remote_symbol("feature", "origin")));
+
+    // Once the remote bookmark is created, it should be imported as tracked.
+    let remote_ref_name = "refs/remotes/origin/feature";
+    let commit = empty_git_commit(&git_repo, remote_ref_name, &[]);
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    let repo = tx.commit("test").unwrap();
+    let view = repo.view();
+    assert_eq!(
+        view.get_local_bookmark("feature".as_ref()),
+        &RefTarget::normal(jj_id(commit)),
+    );
+    assert_eq!(
+        view.get_remote_bookmark(remote_symbol("feature", "origin")),
+        &RemoteRef {
+            target: RefTarget::normal(jj_id(commit)),
+            state: RemoteRefState::Tracked,
+        },
+    );
+    assert!(!view.is_remote_bookmark_pending(remote_symbol("feature", "origin")));
+}
+
 #[test]
 fn test_import_refs_reimport_with_deleted_untracked_intermediate_remote_ref() {
     let git_settings = GitSettings {
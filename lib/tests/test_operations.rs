@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::path::Path;
+use std::path::PathBuf;
 use std::slice;
 use std::sync::Arc;
 use std::time::SystemTime;
@@ -23,6 +24,7 @@ use jj_lib::backend::CommitId;
 use jj_lib::config::ConfigLayer;
 use jj_lib::config::ConfigSource;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OpStoreObjectStats;
 use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
 use jj_lib::op_walk::OpsetEvaluationError;
@@ -310,6 +312,69 @@ fn test_reparent_range_linear() {
     assert_eq!(stats.unreachable_count, 3);
 }
 
+#[test]
+fn test_remove_matching_linear() {
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+    let loader = repo_0.loader();
+    let op_store = repo_0.op_store();
+
+    let read_op = |id| loader.load_operation(id).unwrap();
+
+    fn op_parents<const N: usize>(op: &Operation) -> [Operation; N] {
+        let parents: Vec<_> = op.parents().try_collect().unwrap();
+        parents.try_into().unwrap()
+    }
+
+    // Set up linear operation graph:
+    // D
+    // C
+    // B
+    // A
+    // 0 (initial)
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A").unwrap();
+    let repo_b = random_tx(&repo_a).commit("op B").unwrap();
+    let repo_c = random_tx(&repo_b).commit("op C").unwrap();
+    let repo_d = random_tx(&repo_c).commit("op D").unwrap();
+
+    // Remove B and D, but D is the head and is kept:
+    // D'
+    // C'
+    // A
+    // 0 (initial)
+    let stats = op_walk::remove_matching(
+        op_store.as_ref(),
+        slice::from_ref(repo_d.operation()),
+        |op| ["op B", "op D"].contains(&op.metadata().description.as_str()),
+    )
+    .unwrap();
+    assert_eq!(stats.new_head_ids.len(), 1);
+    assert_eq!(stats.rewritten_count, 2);
+    assert_eq!(stats.unreachable_count, 1);
+    let new_op_d = read_op(&stats.new_head_ids[0]);
+    assert_eq!(new_op_d.metadata(), repo_d.operation().metadata());
+    assert_eq!(new_op_d.view_id(), repo_d.operation().view_id());
+    let [new_op_c] = op_parents(&new_op_d);
+    assert_eq!(new_op_c.metadata(), repo_c.operation().metadata());
+    assert_eq!(new_op_c.parent_ids(), slice::from_ref(repo_a.op_id()));
+
+    // Nothing matches
+    let stats = op_walk::remove_matching(
+        op_store.as_ref(),
+        slice::from_ref(repo_d.operation()),
+        |_| false,
+    )
+    .unwrap();
+    assert_eq!(stats.new_head_ids, vec![repo_d.op_id().clone()]);
+    assert_eq!(stats.rewritten_count, 0);
+    assert_eq!(stats.unreachable_count, 0);
+}
+
 #[test]
 fn test_reparent_range_branchy() {
     let test_repo = TestRepo::init();
@@ -645,6 +710,44 @@ fn test_resolve_op_parents_children() {
         (&parents_op_str, parent_op_ids)
     );
 
+    // Parent by index
+    assert_eq!(
+        op_walk::resolve_op_with_repo(&repo, &format!("{op5_id_hex}-(1)"))
+            .unwrap()
+            .id(),
+        &parent_op_ids[0]
+    );
+    assert_eq!(
+        op_walk::resolve_op_with_repo(&repo, &format!("{op5_id_hex}-(2)"))
+            .unwrap()
+            .id(),
+        &parent_op_ids[1]
+    );
+    assert_eq!(
+        op_walk::resolve_op_with_repo(&repo, &format!("{op5_id_hex}-(2)-")).unwrap(),
+        *operations[2]
+    );
+    assert_eq!(
+        op_walk::resolve_op_with_repo(&repo, &format!("{op5_id_hex}-(1)+"))
+            .unwrap()
+            .id(),
+        repo.operation().id()
+    );
+    for index in ["0", "3"] {
+        assert_matches!(
+            op_walk::resolve_op_with_repo(&repo, &format!("{op5_id_hex}-({index})")),
+            Err(OpsetEvaluationError::OpsetResolution(
+                OpsetResolutionError::EmptyOperations(_)
+            ))
+        );
+    }
+    assert_matches!(
+        op_walk::resolve_op_with_repo(&repo, &format!("{op5_id_hex}-()")),
+        Err(OpsetEvaluationError::OpsetResolution(
+            OpsetResolutionError::InvalidIdPrefix(_)
+        ))
+    );
+
     let op2_id_hex = operations[2].id().hex();
     let op_str = format!("{op2_id_hex}+");
     let error = op_walk::resolve_op_with_repo(&repo, &op_str).unwrap_err();
@@ -654,6 +757,19 @@ fn test_resolve_op_parents_children() {
     );
 }
 
+#[test]
+fn test_split_op_symbol() {
+    assert_eq!(op_walk::split_op_symbol("@"), ("@", ""));
+    assert_eq!(op_walk::split_op_symbol("@-+"), ("@", "-+"));
+    assert_eq!(
+        op_walk::split_op_symbol("abc-(2)-(10)+"),
+        ("abc", "-(2)-(10)+")
+    );
+    assert_eq!(op_walk::split_op_symbol("my-ws@-(1)"), ("my-ws@", "-(1)"));
+    assert_eq!(op_walk::split_op_symbol("abc-()"), ("abc-()", ""));
+    assert_eq!(op_walk::split_op_symbol("abc(1)"), ("abc(1)", ""));
+}
+
 #[test]
 fn test_walk_ancestors() {
     let test_repo = TestRepo::init();
@@ -784,6 +900,56 @@ fn test_walk_ancestors() {
     );
 }
 
+#[test]
+fn test_closest_common_ancestor() {
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+
+    // Set up operation graph:
+    // B2
+    // B1 A
+    //  |/
+    //  0 (initial)
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A").unwrap();
+    let repo_b1 = random_tx(&repo_0).commit("op B1").unwrap();
+    let repo_b2 = random_tx(&repo_b1).commit("op B2").unwrap();
+
+    let common_ancestor = |ops1: &[&Arc<ReadonlyRepo>], ops2: &[&Arc<ReadonlyRepo>]| {
+        let ops1 = ops1
+            .iter()
+            .map(|repo| repo.operation().clone())
+            .collect_vec();
+        let ops2 = ops2
+            .iter()
+            .map(|repo| repo.operation().clone())
+            .collect_vec();
+        op_walk::closest_common_ancestor(&ops1, &ops2)
+            .unwrap()
+            .map(|op| op.id().clone())
+    };
+    assert_eq!(
+        common_ancestor(&[&repo_a], &[&repo_b2]).as_ref(),
+        Some(repo_0.op_id())
+    );
+    assert_eq!(
+        common_ancestor(&[&repo_b2], &[&repo_b1]).as_ref(),
+        Some(repo_b1.op_id())
+    );
+    assert_eq!(
+        common_ancestor(&[&repo_a], &[&repo_a]).as_ref(),
+        Some(repo_a.op_id())
+    );
+    assert_eq!(
+        common_ancestor(&[&repo_a, &repo_b1], &[&repo_b2]).as_ref(),
+        Some(repo_b1.op_id())
+    );
+}
+
 #[test]
 fn test_gc() {
     let settings = stable_op_id_settings();
@@ -862,6 +1028,54 @@ fn test_gc() {
     assert_eq!(expected_view_entries.len(), 1);
 }
 
+#[test]
+fn test_unreachable_object_stats() {
+    let settings = stable_op_id_settings();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let op_dir = test_repo.repo_path().join("op_store").join("operations");
+    let view_dir = test_repo.repo_path().join("op_store").join("views");
+    let repo_0 = test_repo.repo;
+    let op_store = repo_0.op_store();
+
+    // Set up operation graph:
+    //
+    // B C (empty)
+    // |/
+    // A
+    // 0 (root)
+    let repo_a = {
+        let mut tx = repo_0.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx.commit("op A").unwrap()
+    };
+    let repo_b = {
+        let mut tx = repo_a.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx.commit("op B").unwrap()
+    };
+    let repo_c = repo_a.start_transaction().commit("op C").unwrap();
+
+    // All reachable from heads
+    let head_ids = [repo_b.op_id().clone(), repo_c.op_id().clone()];
+    let stats = op_store.unreachable_object_stats(&head_ids).unwrap();
+    assert_eq!(stats, OpStoreObjectStats::default());
+
+    // B is no longer reachable, but C shares the view with A
+    let stats = op_store
+        .unreachable_object_stats(slice::from_ref(repo_c.op_id()))
+        .unwrap();
+    let file_len = |path: PathBuf| path.metadata().unwrap().len();
+    assert_eq!(
+        stats,
+        OpStoreObjectStats {
+            operation_count: 1,
+            view_count: 1,
+            total_bytes: file_len(op_dir.join(repo_b.op_id().hex()))
+                + file_len(view_dir.join(repo_b.operation().view_id().hex())),
+        }
+    );
+}
+
 #[track_caller]
 fn extract_multiple_operations_error(
     error: &OpsetEvaluationError,
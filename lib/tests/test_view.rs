@@ -14,6 +14,7 @@
 
 use std::collections::BTreeMap;
 
+use itertools::Itertools as _;
 use jj_lib::op_store::BookmarkTarget;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
@@ -352,6 +353,40 @@ fn test_merge_views_tags() {
     );
 }
 
+#[test]
+fn test_merge_views_pending_remote_bookmarks() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    mut_repo.set_remote_bookmark_pending(remote_symbol("main", "origin"), true);
+    mut_repo.set_remote_bookmark_pending(remote_symbol("feature", "origin"), true);
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    let mut_repo = tx1.repo_mut();
+    mut_repo.set_remote_bookmark_pending(remote_symbol("main", "origin"), false);
+    mut_repo.set_remote_bookmark_pending(remote_symbol("main", "upstream"), true);
+
+    let mut tx2 = repo.start_transaction();
+    let mut_repo = tx2.repo_mut();
+    let commit = write_random_commit(mut_repo);
+    mut_repo.set_remote_bookmark(
+        remote_symbol("feature", "origin"),
+        RemoteRef {
+            target: RefTarget::normal(commit.id().clone()),
+            state: RemoteRefState::Tracked,
+        },
+    );
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view().pending_remote_bookmarks().collect_vec(),
+        vec![remote_symbol("main", "upstream")],
+    );
+}
+
 #[test]
 fn test_merge_views_git_refs() {
     // Tests merging of git refs (by performing divergent operations). See